@@ -0,0 +1,62 @@
+-- Customer accounts (separate from the staff admin login)
+
+-- Customers table
+CREATE TABLE IF NOT EXISTS customers (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    email VARCHAR(255) UNIQUE,
+    phone VARCHAR(50) UNIQUE NOT NULL, -- normalized: digits with optional leading +
+    password_hash VARCHAR(255), -- NULL for phone-only (OTP) accounts
+    phone_verified BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Saved delivery addresses
+CREATE TABLE IF NOT EXISTS customer_addresses (
+    id SERIAL PRIMARY KEY,
+    customer_id INTEGER NOT NULL REFERENCES customers(id) ON DELETE CASCADE,
+    label VARCHAR(100), -- "Home", "Work", ...
+    street VARCHAR(255) NOT NULL,
+    building VARCHAR(50) NOT NULL,
+    apartment VARCHAR(50),
+    floor VARCHAR(50),
+    entrance VARCHAR(50),
+    intercom VARCHAR(50),
+    city VARCHAR(100) NOT NULL,
+    postal_code VARCHAR(20) NOT NULL,
+    country VARCHAR(100) NOT NULL DEFAULT 'Poland',
+    is_default BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- One-time login codes sent by SMS
+CREATE TABLE IF NOT EXISTS customer_otp_codes (
+    id SERIAL PRIMARY KEY,
+    phone VARCHAR(50) NOT NULL,
+    code_hash VARCHAR(255) NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    expires_at TIMESTAMPTZ NOT NULL,
+    consumed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Link orders to customer accounts (guest orders keep NULL)
+ALTER TABLE orders
+ADD COLUMN IF NOT EXISTS customer_id INTEGER REFERENCES customers(id) ON DELETE SET NULL;
+
+-- Indexes for performance
+CREATE INDEX IF NOT EXISTS idx_customer_addresses_customer ON customer_addresses(customer_id);
+CREATE INDEX IF NOT EXISTS idx_customer_otp_codes_phone ON customer_otp_codes(phone, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_orders_customer ON orders(customer_id);
+CREATE INDEX IF NOT EXISTS idx_orders_guest_phone
+    ON orders ((regexp_replace(customer_phone, '[^0-9+]', '', 'g')))
+    WHERE customer_id IS NULL;
+
+-- Triggers for updated_at
+CREATE TRIGGER update_customers_updated_at BEFORE UPDATE ON customers
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER update_customer_addresses_updated_at BEFORE UPDATE ON customer_addresses
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
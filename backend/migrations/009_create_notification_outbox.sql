@@ -0,0 +1,30 @@
+-- Customer notifications (email/SMS) on order lifecycle events
+
+-- Preferred language of the customer who placed the order (en, ru, pl)
+ALTER TABLE orders
+ADD COLUMN IF NOT EXISTS locale VARCHAR(5) NOT NULL DEFAULT 'en';
+
+-- Outbox: rows are written in the same transaction as the order change
+-- and delivered by a background worker with retries
+CREATE TABLE IF NOT EXISTS notification_outbox (
+    id SERIAL PRIMARY KEY,
+    order_id INTEGER REFERENCES orders(id) ON DELETE CASCADE,
+    event VARCHAR(50) NOT NULL, -- order.created, order.status_changed, order.cancelled
+    channel VARCHAR(20) NOT NULL, -- email, sms
+    recipient VARCHAR(255) NOT NULL,
+    locale VARCHAR(5) NOT NULL DEFAULT 'en',
+    subject TEXT NOT NULL,
+    body TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending', -- pending, sent, failed
+    attempts INTEGER NOT NULL DEFAULT 0,
+    max_attempts INTEGER NOT NULL DEFAULT 5,
+    last_error TEXT,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    sent_at TIMESTAMPTZ
+);
+
+-- Indexes for performance
+CREATE INDEX IF NOT EXISTS idx_notification_outbox_due
+    ON notification_outbox(next_attempt_at) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_notification_outbox_order ON notification_outbox(order_id);
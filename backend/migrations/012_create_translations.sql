@@ -0,0 +1,87 @@
+-- Translations for any locale. The base columns (name, description...) hold the
+-- default locale; every other locale lives here.
+
+CREATE TABLE IF NOT EXISTS translations (
+    id SERIAL PRIMARY KEY,
+    entity VARCHAR(50) NOT NULL, -- category, menu_item, restaurant_info
+    entity_id INTEGER NOT NULL,
+    field VARCHAR(50) NOT NULL, -- name, description, hero_title...
+    locale VARCHAR(10) NOT NULL, -- ru, pl, uk, de...
+    text TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (entity, entity_id, field, locale)
+);
+
+CREATE INDEX IF NOT EXISTS idx_translations_locale ON translations(entity, locale);
+
+CREATE TRIGGER update_translations_updated_at BEFORE UPDATE ON translations
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- The old `<field>_ru` / `<field>_pl` columns stay for existing clients and are
+-- mirrored into `translations`. Arguments: entity name, then the legacy fields.
+CREATE OR REPLACE FUNCTION sync_legacy_translations()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_entity TEXT := TG_ARGV[0];
+    v_row JSONB;
+    v_field TEXT;
+    v_locale TEXT;
+    v_text TEXT;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        DELETE FROM translations WHERE entity = v_entity AND entity_id = OLD.id;
+        RETURN OLD;
+    END IF;
+
+    v_row := to_jsonb(NEW);
+
+    FOR i IN 1 .. TG_NARGS - 1 LOOP
+        v_field := TG_ARGV[i];
+
+        FOREACH v_locale IN ARRAY ARRAY['ru', 'pl'] LOOP
+            v_text := v_row ->> (v_field || '_' || v_locale);
+
+            IF v_text IS NULL OR btrim(v_text) = '' THEN
+                DELETE FROM translations
+                WHERE entity = v_entity AND entity_id = NEW.id AND field = v_field AND locale = v_locale;
+            ELSE
+                INSERT INTO translations (entity, entity_id, field, locale, text)
+                VALUES (v_entity, NEW.id, v_field, v_locale, v_text)
+                ON CONFLICT (entity, entity_id, field, locale)
+                DO UPDATE SET text = EXCLUDED.text
+                WHERE translations.text IS DISTINCT FROM EXCLUDED.text;
+            END IF;
+        END LOOP;
+    END LOOP;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER sync_categories_translations AFTER INSERT OR UPDATE OR DELETE ON categories
+    FOR EACH ROW EXECUTE FUNCTION sync_legacy_translations('category', 'name');
+
+CREATE TRIGGER sync_menu_items_translations AFTER INSERT OR UPDATE OR DELETE ON menu_items
+    FOR EACH ROW EXECUTE FUNCTION sync_legacy_translations('menu_item', 'name', 'description');
+
+CREATE TRIGGER sync_restaurant_info_translations AFTER INSERT OR UPDATE OR DELETE ON restaurant_info
+    FOR EACH ROW EXECUTE FUNCTION sync_legacy_translations('restaurant_info', 'name', 'description');
+
+-- Backfill existing rows
+INSERT INTO translations (entity, entity_id, field, locale, text)
+SELECT entity, entity_id, field, locale, text
+FROM (
+    SELECT 'category' AS entity, id AS entity_id, 'name' AS field, 'ru' AS locale, name_ru AS text FROM categories
+    UNION ALL SELECT 'category', id, 'name', 'pl', name_pl FROM categories
+    UNION ALL SELECT 'menu_item', id, 'name', 'ru', name_ru FROM menu_items
+    UNION ALL SELECT 'menu_item', id, 'name', 'pl', name_pl FROM menu_items
+    UNION ALL SELECT 'menu_item', id, 'description', 'ru', description_ru FROM menu_items
+    UNION ALL SELECT 'menu_item', id, 'description', 'pl', description_pl FROM menu_items
+    UNION ALL SELECT 'restaurant_info', id, 'name', 'ru', name_ru FROM restaurant_info
+    UNION ALL SELECT 'restaurant_info', id, 'name', 'pl', name_pl FROM restaurant_info
+    UNION ALL SELECT 'restaurant_info', id, 'description', 'ru', description_ru FROM restaurant_info
+    UNION ALL SELECT 'restaurant_info', id, 'description', 'pl', description_pl FROM restaurant_info
+) legacy
+WHERE btrim(COALESCE(text, '')) <> ''
+ON CONFLICT (entity, entity_id, field, locale) DO NOTHING;
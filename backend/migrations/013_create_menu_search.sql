@@ -0,0 +1,93 @@
+-- Menu search: full-text (all languages, ingredients, tags) plus trigram typo tolerance.
+-- Text is lowercased and unaccented so "losos" finds "Łosoś".
+
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+CREATE EXTENSION IF NOT EXISTS unaccent;
+
+-- unaccent() is only STABLE; pinning the dictionary makes it usable in indexes
+CREATE OR REPLACE FUNCTION immutable_unaccent(input TEXT)
+RETURNS TEXT AS $$
+    SELECT public.unaccent('public.unaccent'::regdictionary, input)
+$$ LANGUAGE sql IMMUTABLE PARALLEL SAFE STRICT;
+
+CREATE OR REPLACE FUNCTION search_normalize(input TEXT)
+RETURNS TEXT AS $$
+    SELECT lower(immutable_unaccent(COALESCE(input, '')))
+$$ LANGUAGE sql IMMUTABLE PARALLEL SAFE;
+
+-- Prefix query from free text: "łoso phila" -> 'loso':* & 'phila':*
+CREATE OR REPLACE FUNCTION menu_search_query(input TEXT)
+RETURNS tsquery AS $$
+    SELECT to_tsquery('simple', COALESCE(string_agg(quote_literal(word) || ':*', ' & '), ''))
+    FROM regexp_split_to_table(search_normalize(input), '[^[:alnum:]]+') AS word
+    WHERE word <> ''
+$$ LANGUAGE sql IMMUTABLE PARALLEL SAFE;
+
+ALTER TABLE menu_items ADD COLUMN IF NOT EXISTS search_vector TSVECTOR;
+ALTER TABLE menu_items ADD COLUMN IF NOT EXISTS search_text TEXT;
+
+-- Names weigh most, then ingredients and tags, then descriptions.
+-- Locales beyond the legacy ru/pl columns come from `translations`.
+CREATE OR REPLACE FUNCTION menu_items_search_update()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_names TEXT;
+    v_descriptions TEXT;
+    v_keywords TEXT;
+BEGIN
+    SELECT
+        concat_ws(' ', NEW.name, NEW.name_ru, NEW.name_pl,
+            string_agg(text, ' ') FILTER (WHERE field = 'name')),
+        concat_ws(' ', NEW.description, NEW.description_ru, NEW.description_pl,
+            string_agg(text, ' ') FILTER (WHERE field = 'description'))
+    INTO v_names, v_descriptions
+    FROM translations
+    WHERE entity = 'menu_item' AND entity_id = NEW.id AND locale NOT IN ('ru', 'pl');
+
+    v_keywords := concat_ws(' ',
+        array_to_string(NEW.ingredients, ' '),
+        array_to_string(NEW.tags, ' '));
+
+    NEW.search_vector :=
+        setweight(to_tsvector('simple', search_normalize(v_names)), 'A') ||
+        setweight(to_tsvector('simple', search_normalize(v_keywords)), 'B') ||
+        setweight(to_tsvector('simple', search_normalize(v_descriptions)), 'C');
+    NEW.search_text := search_normalize(concat_ws(' ', v_names, v_keywords));
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER menu_items_search_update BEFORE INSERT OR UPDATE ON menu_items
+    FOR EACH ROW EXECUTE FUNCTION menu_items_search_update();
+
+-- New translations (uk, de...) refresh the item's search document
+CREATE OR REPLACE FUNCTION translations_refresh_menu_search()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_row translations%ROWTYPE;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        v_row := OLD;
+    ELSE
+        v_row := NEW;
+    END IF;
+
+    IF v_row.entity = 'menu_item' AND v_row.locale NOT IN ('ru', 'pl') THEN
+        UPDATE menu_items SET search_vector = NULL WHERE id = v_row.entity_id;
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER translations_refresh_menu_search AFTER INSERT OR UPDATE OR DELETE ON translations
+    FOR EACH ROW EXECUTE FUNCTION translations_refresh_menu_search();
+
+-- Backfill through the trigger without touching updated_at
+ALTER TABLE menu_items DISABLE TRIGGER update_menu_items_updated_at;
+UPDATE menu_items SET search_vector = NULL;
+ALTER TABLE menu_items ENABLE TRIGGER update_menu_items_updated_at;
+
+CREATE INDEX IF NOT EXISTS idx_menu_items_search_vector ON menu_items USING GIN (search_vector);
+CREATE INDEX IF NOT EXISTS idx_menu_items_search_text ON menu_items USING GIN (search_text gin_trgm_ops);
@@ -0,0 +1,93 @@
+-- The 14 allergens that must be declared in the EU (Regulation 1169/2011, Annex II),
+-- dietary flags and spice levels for menu items
+
+CREATE TABLE IF NOT EXISTS allergens (
+    code VARCHAR(30) PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    name_ru VARCHAR(100) NOT NULL,
+    name_pl VARCHAR(100) NOT NULL,
+    icon VARCHAR(10) NOT NULL,
+    sort_order INTEGER NOT NULL
+);
+
+INSERT INTO allergens (code, name, name_ru, name_pl, icon, sort_order) VALUES
+('gluten', 'Cereals containing gluten', 'Злаки, содержащие глютен', 'Zboża zawierające gluten', '🌾', 1),
+('crustaceans', 'Crustaceans', 'Ракообразные', 'Skorupiaki', '🦐', 2),
+('eggs', 'Eggs', 'Яйца', 'Jaja', '🥚', 3),
+('fish', 'Fish', 'Рыба', 'Ryby', '🐟', 4),
+('peanuts', 'Peanuts', 'Арахис', 'Orzeszki ziemne', '🥜', 5),
+('soybeans', 'Soybeans', 'Соя', 'Soja', '🫘', 6),
+('milk', 'Milk', 'Молоко', 'Mleko', '🥛', 7),
+('nuts', 'Tree nuts', 'Орехи', 'Orzechy', '🌰', 8),
+('celery', 'Celery', 'Сельдерей', 'Seler', '🥬', 9),
+('mustard', 'Mustard', 'Горчица', 'Gorczyca', '🟡', 10),
+('sesame', 'Sesame seeds', 'Кунжут', 'Nasiona sezamu', '⚪', 11),
+('sulphites', 'Sulphur dioxide and sulphites', 'Диоксид серы и сульфиты', 'Dwutlenek siarki i siarczyny', '🍷', 12),
+('lupin', 'Lupin', 'Люпин', 'Łubin', '🌼', 13),
+('molluscs', 'Molluscs', 'Моллюски', 'Mięczaki', '🦑', 14)
+ON CONFLICT (code) DO NOTHING;
+
+ALTER TABLE menu_items ADD COLUMN IF NOT EXISTS is_vegan BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE menu_items ADD COLUMN IF NOT EXISTS is_gluten_free BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE menu_items ADD COLUMN IF NOT EXISTS is_halal BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE menu_items ADD COLUMN IF NOT EXISTS is_lactose_free BOOLEAN NOT NULL DEFAULT false;
+-- 0 = not spicy, 1 = mild, 2 = medium, 3 = hot; is_spicy stays as spice_level > 0
+ALTER TABLE menu_items ADD COLUMN IF NOT EXISTS spice_level SMALLINT NOT NULL DEFAULT 0
+    CHECK (spice_level BETWEEN 0 AND 3);
+
+-- Kitchen tickets print what the item contained when it was ordered
+ALTER TABLE order_items ADD COLUMN IF NOT EXISTS allergens TEXT[] NOT NULL DEFAULT '{}';
+ALTER TABLE order_items ADD COLUMN IF NOT EXISTS spice_level SMALLINT NOT NULL DEFAULT 0;
+
+-- Existing free-text allergens become codes. Values that are not one of the 14
+-- are moved to unmapped_allergens so staff can review them and fix the items.
+CREATE TABLE IF NOT EXISTS unmapped_allergens (
+    menu_item_id INTEGER NOT NULL REFERENCES menu_items(id) ON DELETE CASCADE,
+    value TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (menu_item_id, value)
+);
+
+CREATE OR REPLACE FUNCTION allergen_code(p_value TEXT)
+RETURNS TEXT AS $$
+    SELECT CASE lower(btrim(p_value))
+        WHEN 'wheat' THEN 'gluten'
+        WHEN 'egg' THEN 'eggs'
+        WHEN 'peanut' THEN 'peanuts'
+        WHEN 'soy' THEN 'soybeans'
+        WHEN 'soya' THEN 'soybeans'
+        WHEN 'dairy' THEN 'milk'
+        WHEN 'lactose' THEN 'milk'
+        WHEN 'tree nuts' THEN 'nuts'
+        WHEN 'sulfites' THEN 'sulphites'
+        ELSE lower(btrim(p_value))
+    END;
+$$ LANGUAGE sql IMMUTABLE;
+
+INSERT INTO unmapped_allergens (menu_item_id, value)
+SELECT DISTINCT m.id, btrim(raw.value)
+FROM menu_items m
+CROSS JOIN LATERAL unnest(m.allergens) AS raw(value)
+WHERE btrim(raw.value) <> ''
+  AND NOT EXISTS (SELECT 1 FROM allergens a WHERE a.code = allergen_code(raw.value))
+ON CONFLICT DO NOTHING;
+
+ALTER TABLE menu_items DISABLE TRIGGER update_menu_items_updated_at;
+
+UPDATE menu_items SET spice_level = 1 WHERE is_spicy = true AND spice_level = 0;
+
+-- In label order, as the API saves them
+UPDATE menu_items m
+SET allergens = ARRAY(
+    SELECT a.code
+    FROM allergens a
+    WHERE a.code IN (SELECT allergen_code(raw.value) FROM unnest(m.allergens) AS raw(value))
+    ORDER BY a.sort_order
+)
+WHERE cardinality(m.allergens) > 0;
+
+ALTER TABLE menu_items ENABLE TRIGGER update_menu_items_updated_at;
+
+DROP FUNCTION allergen_code(TEXT);
+
+CREATE INDEX IF NOT EXISTS idx_menu_items_allergens ON menu_items USING GIN (allergens);
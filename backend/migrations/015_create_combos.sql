@@ -0,0 +1,38 @@
+-- Combo meals / set menus. A combo is a regular menu item (its price is the base
+-- price) with slots the customer fills, e.g. "2 rolls" + "1 soup".
+
+CREATE TABLE IF NOT EXISTS combo_slots (
+    id SERIAL PRIMARY KEY,
+    combo_item_id INTEGER NOT NULL REFERENCES menu_items(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    name_ru VARCHAR(255) NOT NULL DEFAULT '',
+    name_pl VARCHAR(255) NOT NULL DEFAULT '',
+    -- Total quantity the customer picks for this slot
+    min_choices INTEGER NOT NULL DEFAULT 1,
+    max_choices INTEGER NOT NULL DEFAULT 1,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (min_choices >= 0 AND max_choices >= 1 AND max_choices >= min_choices)
+);
+
+CREATE INDEX IF NOT EXISTS idx_combo_slots_combo ON combo_slots(combo_item_id, sort_order);
+
+-- What fits a slot: a whole category or one specific item, with an optional surcharge.
+-- An item-specific option overrides its category's surcharge.
+CREATE TABLE IF NOT EXISTS combo_slot_options (
+    id SERIAL PRIMARY KEY,
+    slot_id INTEGER NOT NULL REFERENCES combo_slots(id) ON DELETE CASCADE,
+    category_id INTEGER REFERENCES categories(id) ON DELETE CASCADE,
+    menu_item_id INTEGER REFERENCES menu_items(id) ON DELETE CASCADE,
+    surcharge NUMERIC(10, 2) NOT NULL DEFAULT 0 CHECK (surcharge >= 0),
+    CHECK ((category_id IS NULL) <> (menu_item_id IS NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_combo_slot_options_slot ON combo_slot_options(slot_id);
+
+-- Chosen components are stored as child rows of the combo's order item
+-- (price 0, the surcharges are included in the combo line's price)
+ALTER TABLE order_items ADD COLUMN IF NOT EXISTS parent_item_id INTEGER REFERENCES order_items(id) ON DELETE CASCADE;
+ALTER TABLE order_items ADD COLUMN IF NOT EXISTS combo_slot VARCHAR(255);
+
+CREATE INDEX IF NOT EXISTS idx_order_items_parent ON order_items(parent_item_id) WHERE parent_item_id IS NOT NULL;
@@ -0,0 +1,135 @@
+-- Time-based pricing (happy hours, lunch prices) and availability windows.
+-- Both apply to one menu item or a whole category and are evaluated in the
+-- restaurant's local time by menu_items_at(), which serves the public menu
+-- and prices orders, so shown and charged prices always match.
+
+CREATE TABLE IF NOT EXISTS price_rules (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    menu_item_id INTEGER REFERENCES menu_items(id) ON DELETE CASCADE,
+    category_id INTEGER REFERENCES categories(id) ON DELETE CASCADE,
+    -- percent: value % off, amount_off: value off, fixed_price: price = value
+    kind VARCHAR(20) NOT NULL CHECK (kind IN ('percent', 'amount_off', 'fixed_price')),
+    value NUMERIC(10, 2) NOT NULL CHECK (value >= 0),
+    -- ISO weekdays (1 = Monday ... 7 = Sunday), NULL = every day
+    days_of_week SMALLINT[],
+    -- Local time; NULL = all day, end before start = overnight
+    start_time TIME,
+    end_time TIME,
+    -- Inclusive date range, NULL = open-ended
+    starts_on DATE,
+    ends_on DATE,
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((menu_item_id IS NULL) <> (category_id IS NULL)),
+    CHECK ((start_time IS NULL) = (end_time IS NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_price_rules_item ON price_rules(menu_item_id) WHERE menu_item_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_price_rules_category ON price_rules(category_id) WHERE category_id IS NOT NULL;
+
+-- When an item (or, if the item has none, its category) has windows,
+-- it can only be ordered inside one of them
+CREATE TABLE IF NOT EXISTS availability_windows (
+    id SERIAL PRIMARY KEY,
+    menu_item_id INTEGER REFERENCES menu_items(id) ON DELETE CASCADE,
+    category_id INTEGER REFERENCES categories(id) ON DELETE CASCADE,
+    days_of_week SMALLINT[],
+    start_time TIME,
+    end_time TIME,
+    starts_on DATE,
+    ends_on DATE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((menu_item_id IS NULL) <> (category_id IS NULL)),
+    CHECK ((start_time IS NULL) = (end_time IS NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_availability_windows_item ON availability_windows(menu_item_id) WHERE menu_item_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_availability_windows_category ON availability_windows(category_id) WHERE category_id IS NOT NULL;
+
+DROP TRIGGER IF EXISTS update_price_rules_updated_at ON price_rules;
+CREATE TRIGGER update_price_rules_updated_at BEFORE UPDATE ON price_rules
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+DROP TRIGGER IF EXISTS update_availability_windows_updated_at ON availability_windows;
+CREATE TRIGGER update_availability_windows_updated_at BEFORE UPDATE ON availability_windows
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Whether a local timestamp falls inside a schedule. The part of an overnight
+-- window after midnight belongs to the day the window started.
+CREATE OR REPLACE FUNCTION schedule_matches(
+    days SMALLINT[], start_time TIME, end_time TIME, starts_on DATE, ends_on DATE, local_ts TIMESTAMP
+) RETURNS BOOLEAN
+LANGUAGE sql IMMUTABLE AS $$
+    SELECT COALESCE(bool_or(
+        (days IS NULL OR EXTRACT(ISODOW FROM day)::SMALLINT = ANY(days))
+        AND (starts_on IS NULL OR day >= starts_on)
+        AND (ends_on IS NULL OR day <= ends_on)
+    ), false)
+    FROM (
+        SELECT local_ts::DATE AS day
+        WHERE start_time IS NULL
+           OR (start_time < end_time AND local_ts::TIME >= start_time AND local_ts::TIME < end_time)
+           OR (start_time > end_time AND local_ts::TIME >= start_time)
+        UNION ALL
+        SELECT (local_ts - INTERVAL '1 day')::DATE
+        WHERE start_time > end_time AND local_ts::TIME < end_time
+    ) matched
+$$;
+
+-- Price, crossed-out price and availability of one item at a local timestamp.
+-- When several rules match, the lowest resulting price wins.
+CREATE OR REPLACE FUNCTION menu_item_schedule(
+    item_id INTEGER, item_category_id INTEGER, item_price NUMERIC, item_original_price NUMERIC,
+    item_is_available BOOLEAN, local_ts TIMESTAMP
+) RETURNS TABLE (price NUMERIC, original_price NUMERIC, is_available BOOLEAN)
+LANGUAGE sql STABLE AS $$
+    WITH rule AS (
+        SELECT MIN(GREATEST(0, ROUND(
+            CASE r.kind
+                WHEN 'percent' THEN item_price * (100 - LEAST(r.value, 100)) / 100
+                WHEN 'amount_off' THEN item_price - r.value
+                ELSE r.value
+            END, 2))) AS price
+        FROM price_rules r
+        WHERE r.is_active
+          AND (r.menu_item_id = item_id OR r.category_id = item_category_id)
+          AND schedule_matches(r.days_of_week, r.start_time, r.end_time, r.starts_on, r.ends_on, local_ts)
+    ),
+    windows AS (
+        SELECT w.*
+        FROM availability_windows w
+        WHERE w.menu_item_id = item_id
+           OR (w.category_id = item_category_id
+               AND NOT EXISTS (SELECT 1 FROM availability_windows iw WHERE iw.menu_item_id = item_id))
+    )
+    SELECT
+        COALESCE(rule.price, item_price),
+        CASE WHEN rule.price < item_price THEN GREATEST(item_price, item_original_price) ELSE item_original_price END,
+        item_is_available AND (
+            NOT EXISTS (SELECT 1 FROM windows)
+            OR EXISTS (
+                SELECT 1 FROM windows w
+                WHERE schedule_matches(w.days_of_week, w.start_time, w.end_time, w.starts_on, w.ends_on, local_ts)
+            )
+        )
+    FROM rule
+$$;
+
+-- menu_items as customers see them at a moment: scheduled price, the regular
+-- price as original_price while a discount runs, and is_available = false
+-- outside availability windows
+CREATE OR REPLACE FUNCTION menu_items_at(at TIMESTAMPTZ, tz TEXT)
+RETURNS SETOF menu_items
+LANGUAGE sql STABLE AS $$
+    SELECT scheduled.*
+    FROM menu_items m
+    CROSS JOIN LATERAL menu_item_schedule(
+        m.id, m.category_id, m.price, m.original_price, m.is_available, at AT TIME ZONE tz
+    ) s
+    CROSS JOIN LATERAL json_populate_record(m, json_build_object(
+        'price', s.price, 'original_price', s.original_price, 'is_available', s.is_available
+    )) scheduled
+$$;
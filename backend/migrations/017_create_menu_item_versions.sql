@@ -0,0 +1,88 @@
+-- History of menu item prices, names and availability. A trigger records a
+-- version whenever one of the tracked fields changes, whatever the source
+-- (admin API, import, translation sync), so the menu can be reconstructed
+-- for any past date. Rows are kept after the item itself is deleted.
+
+CREATE TABLE IF NOT EXISTS menu_item_versions (
+    id SERIAL PRIMARY KEY,
+    menu_item_id INTEGER NOT NULL,
+    version INTEGER NOT NULL,
+    -- created, updated or deleted
+    action VARCHAR(20) NOT NULL,
+    changed_fields TEXT[] NOT NULL DEFAULT '{}',
+    category_id INTEGER,
+    name VARCHAR(255) NOT NULL,
+    name_ru VARCHAR(255) NOT NULL,
+    name_pl VARCHAR(255) NOT NULL,
+    price NUMERIC(10, 2) NOT NULL,
+    original_price NUMERIC(10, 2),
+    is_available BOOLEAN,
+    -- Set per transaction with set_config('app.changed_by', ..., true)
+    changed_by VARCHAR(100) NOT NULL DEFAULT 'system',
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (menu_item_id, version)
+);
+
+CREATE INDEX IF NOT EXISTS idx_menu_item_versions_created_at ON menu_item_versions(created_at);
+
+CREATE OR REPLACE FUNCTION record_menu_item_version()
+RETURNS TRIGGER AS $$
+DECLARE
+    item menu_items;
+    changed TEXT[] := '{}';
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        item := OLD;
+    ELSE
+        item := NEW;
+    END IF;
+
+    IF TG_OP = 'UPDATE' THEN
+        IF NEW.name IS DISTINCT FROM OLD.name THEN changed := changed || 'name'::TEXT; END IF;
+        IF NEW.name_ru IS DISTINCT FROM OLD.name_ru THEN changed := changed || 'name_ru'::TEXT; END IF;
+        IF NEW.name_pl IS DISTINCT FROM OLD.name_pl THEN changed := changed || 'name_pl'::TEXT; END IF;
+        IF NEW.price IS DISTINCT FROM OLD.price THEN changed := changed || 'price'::TEXT; END IF;
+        IF NEW.original_price IS DISTINCT FROM OLD.original_price THEN changed := changed || 'original_price'::TEXT; END IF;
+        IF NEW.is_available IS DISTINCT FROM OLD.is_available THEN changed := changed || 'is_available'::TEXT; END IF;
+        IF NEW.category_id IS DISTINCT FROM OLD.category_id THEN changed := changed || 'category_id'::TEXT; END IF;
+
+        IF cardinality(changed) = 0 THEN
+            RETURN NULL;
+        END IF;
+    END IF;
+
+    INSERT INTO menu_item_versions (
+        menu_item_id, version, action, changed_fields, category_id,
+        name, name_ru, name_pl, price, original_price, is_available, changed_by, note
+    )
+    VALUES (
+        item.id,
+        (SELECT COALESCE(MAX(version), 0) + 1 FROM menu_item_versions WHERE menu_item_id = item.id),
+        CASE TG_OP WHEN 'INSERT' THEN 'created' WHEN 'UPDATE' THEN 'updated' ELSE 'deleted' END,
+        changed,
+        item.category_id,
+        item.name, item.name_ru, item.name_pl, item.price, item.original_price, item.is_available,
+        COALESCE(NULLIF(current_setting('app.changed_by', true), ''), 'system'),
+        NULLIF(current_setting('app.change_note', true), '')
+    );
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS record_menu_item_version ON menu_items;
+CREATE TRIGGER record_menu_item_version
+    AFTER INSERT OR UPDATE OR DELETE ON menu_items
+    FOR EACH ROW EXECUTE FUNCTION record_menu_item_version();
+
+-- Existing items start with version 1 at their creation time
+INSERT INTO menu_item_versions (
+    menu_item_id, version, action, category_id,
+    name, name_ru, name_pl, price, original_price, is_available, note, created_at
+)
+SELECT m.id, 1, 'created', m.category_id,
+       m.name, m.name_ru, m.name_pl, m.price, m.original_price, m.is_available,
+       'Initial version', m.created_at
+FROM menu_items m
+WHERE NOT EXISTS (SELECT 1 FROM menu_item_versions v WHERE v.menu_item_id = m.id);
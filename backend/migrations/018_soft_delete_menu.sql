@@ -0,0 +1,86 @@
+-- Soft deletion of categories and menu items. Deleted rows keep their id so
+-- order_items, versions and reports still resolve them; they are hidden from
+-- the public menu and can be restored by an admin.
+
+ALTER TABLE categories ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE menu_items ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_categories_deleted_at ON categories(deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_menu_items_deleted_at ON menu_items(deleted_at) WHERE deleted_at IS NOT NULL;
+
+-- Deleting a category used to wipe its items; a category with items can no
+-- longer be removed from the database at all
+ALTER TABLE menu_items DROP CONSTRAINT IF EXISTS menu_items_category_id_fkey;
+ALTER TABLE menu_items ADD CONSTRAINT menu_items_category_id_fkey
+    FOREIGN KEY (category_id) REFERENCES categories(id) ON DELETE RESTRICT;
+
+-- Deleting and restoring are recorded as versions too
+CREATE OR REPLACE FUNCTION record_menu_item_version()
+RETURNS TRIGGER AS $$
+DECLARE
+    item menu_items;
+    changed TEXT[] := '{}';
+    action VARCHAR(20);
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        item := OLD;
+        action := 'deleted';
+    ELSIF TG_OP = 'INSERT' THEN
+        item := NEW;
+        action := 'created';
+    ELSE
+        item := NEW;
+        action := 'updated';
+
+        IF NEW.name IS DISTINCT FROM OLD.name THEN changed := changed || 'name'::TEXT; END IF;
+        IF NEW.name_ru IS DISTINCT FROM OLD.name_ru THEN changed := changed || 'name_ru'::TEXT; END IF;
+        IF NEW.name_pl IS DISTINCT FROM OLD.name_pl THEN changed := changed || 'name_pl'::TEXT; END IF;
+        IF NEW.price IS DISTINCT FROM OLD.price THEN changed := changed || 'price'::TEXT; END IF;
+        IF NEW.original_price IS DISTINCT FROM OLD.original_price THEN changed := changed || 'original_price'::TEXT; END IF;
+        IF NEW.is_available IS DISTINCT FROM OLD.is_available THEN changed := changed || 'is_available'::TEXT; END IF;
+        IF NEW.category_id IS DISTINCT FROM OLD.category_id THEN changed := changed || 'category_id'::TEXT; END IF;
+
+        IF OLD.deleted_at IS NULL AND NEW.deleted_at IS NOT NULL THEN
+            action := 'deleted';
+        ELSIF OLD.deleted_at IS NOT NULL AND NEW.deleted_at IS NULL THEN
+            action := 'restored';
+        ELSIF cardinality(changed) = 0 THEN
+            RETURN NULL;
+        END IF;
+    END IF;
+
+    INSERT INTO menu_item_versions (
+        menu_item_id, version, action, changed_fields, category_id,
+        name, name_ru, name_pl, price, original_price, is_available, changed_by, note
+    )
+    VALUES (
+        item.id,
+        (SELECT COALESCE(MAX(version), 0) + 1 FROM menu_item_versions WHERE menu_item_id = item.id),
+        action,
+        changed,
+        item.category_id,
+        item.name, item.name_ru, item.name_pl, item.price, item.original_price, item.is_available,
+        COALESCE(NULLIF(current_setting('app.changed_by', true), ''), 'system'),
+        NULLIF(current_setting('app.change_note', true), '')
+    );
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- The public menu, search, combos and orders never see deleted items or
+-- items of deleted categories
+CREATE OR REPLACE FUNCTION menu_items_at(at TIMESTAMPTZ, tz TEXT)
+RETURNS SETOF menu_items
+LANGUAGE sql STABLE AS $$
+    SELECT scheduled.*
+    FROM menu_items m
+    CROSS JOIN LATERAL menu_item_schedule(
+        m.id, m.category_id, m.price, m.original_price, m.is_available, at AT TIME ZONE tz
+    ) s
+    CROSS JOIN LATERAL json_populate_record(m, json_build_object(
+        'price', s.price, 'original_price', s.original_price, 'is_available', s.is_available
+    )) scheduled
+    WHERE m.deleted_at IS NULL
+      AND NOT EXISTS (SELECT 1 FROM categories c WHERE c.id = m.category_id AND c.deleted_at IS NOT NULL)
+$$;
@@ -0,0 +1,18 @@
+-- Audit log of bulk admin operations on menu items (POST /admin/menu/bulk).
+-- Per-item versions are still written by the menu_items trigger.
+
+CREATE TABLE IF NOT EXISTS menu_bulk_operations (
+    id SERIAL PRIMARY KEY,
+    -- set_availability, set_flags, adjust_price, move_category, add_tags, remove_tags
+    operation VARCHAR(30) NOT NULL,
+    -- The request as sent: {"filter": {...}, "operation": {...}}
+    request JSONB NOT NULL,
+    matched INTEGER NOT NULL,
+    changed INTEGER NOT NULL,
+    -- [{"id", "name", "changes": [{"field", "from", "to"}]}] for the changed items
+    items JSONB NOT NULL DEFAULT '[]',
+    changed_by VARCHAR(100) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_menu_bulk_operations_created_at ON menu_bulk_operations(created_at DESC);
@@ -0,0 +1,102 @@
+-- Library of uploaded images. Which menu items, categories and restaurant
+-- info fields use an image is tracked by triggers; images nobody uses are
+-- deleted by the background sweep after a grace period.
+
+CREATE TABLE IF NOT EXISTS media_assets (
+    id SERIAL PRIMARY KEY,
+    storage VARCHAR(20) NOT NULL, -- backend holding the files: cloudinary, local, s3
+    public_id TEXT NOT NULL,
+    url TEXT NOT NULL UNIQUE,
+    format VARCHAR(10) NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    bytes INTEGER NOT NULL,
+    folder VARCHAR(255),
+    derivatives JSONB NOT NULL DEFAULT '{}', -- {"card": {"url", "public_id", "width", "height"}, ...}
+    urls TEXT[] NOT NULL, -- url and the derivative URLs; an entity may use any of them
+    uploaded_by VARCHAR(100) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    released_at TIMESTAMPTZ -- last time a reference to the image was removed
+);
+
+CREATE INDEX IF NOT EXISTS idx_media_assets_urls ON media_assets USING GIN (urls);
+CREATE INDEX IF NOT EXISTS idx_media_assets_created_at ON media_assets(created_at);
+
+CREATE TABLE IF NOT EXISTS media_asset_references (
+    asset_id INTEGER NOT NULL REFERENCES media_assets(id) ON DELETE CASCADE,
+    entity VARCHAR(30) NOT NULL, -- menu_item, category, restaurant_info
+    entity_id INTEGER NOT NULL,
+    field VARCHAR(50) NOT NULL,
+    PRIMARY KEY (asset_id, entity, entity_id, field)
+);
+
+CREATE INDEX IF NOT EXISTS idx_media_asset_references_entity ON media_asset_references(entity, entity_id);
+
+-- Replaces the references of one row with its current (field, url) pairs.
+-- URLs that are not uploads (external links, seed data) are ignored.
+CREATE OR REPLACE FUNCTION sync_media_references(
+    p_entity TEXT, p_entity_id INTEGER, p_fields TEXT[], p_urls TEXT[]
+)
+RETURNS VOID AS $$
+DECLARE
+    released INTEGER[];
+BEGIN
+    WITH removed AS (
+        DELETE FROM media_asset_references
+        WHERE entity = p_entity AND entity_id = p_entity_id
+        RETURNING asset_id
+    )
+    SELECT array_agg(asset_id) INTO released FROM removed;
+
+    INSERT INTO media_asset_references (asset_id, entity, entity_id, field)
+    SELECT DISTINCT a.id, p_entity, p_entity_id, r.field
+    FROM unnest(p_fields, p_urls) AS r(field, url)
+    JOIN media_assets a ON a.urls @> ARRAY[r.url];
+
+    -- The grace period of the sweep starts when the last reference goes
+    UPDATE media_assets a SET released_at = NOW()
+    WHERE a.id = ANY(released)
+      AND NOT EXISTS (SELECT 1 FROM media_asset_references r WHERE r.asset_id = a.id);
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION track_media_references()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        PERFORM sync_media_references(TG_ARGV[0], OLD.id, '{}', '{}');
+    ELSIF TG_TABLE_NAME = 'menu_items' THEN
+        PERFORM sync_media_references(
+            TG_ARGV[0], NEW.id,
+            ARRAY['image'] || array_fill('images'::TEXT, ARRAY[COALESCE(cardinality(NEW.images), 0)]),
+            ARRAY[NEW.image] || COALESCE(NEW.images, '{}')
+        );
+    ELSIF TG_TABLE_NAME = 'categories' THEN
+        PERFORM sync_media_references(TG_ARGV[0], NEW.id, ARRAY['image'], ARRAY[NEW.image]);
+    ELSIF TG_TABLE_NAME = 'restaurant_info' THEN
+        PERFORM sync_media_references(
+            TG_ARGV[0], NEW.id,
+            ARRAY['logo', 'hero_image', 'featured_dish_image'],
+            ARRAY[NEW.logo, NEW.hero_image, NEW.featured_dish_image]
+        );
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Soft-deleted rows keep their references: they can be restored
+DROP TRIGGER IF EXISTS menu_items_media_references ON menu_items;
+CREATE TRIGGER menu_items_media_references
+    AFTER INSERT OR DELETE OR UPDATE OF image, images ON menu_items
+    FOR EACH ROW EXECUTE FUNCTION track_media_references('menu_item');
+
+DROP TRIGGER IF EXISTS categories_media_references ON categories;
+CREATE TRIGGER categories_media_references
+    AFTER INSERT OR DELETE OR UPDATE OF image ON categories
+    FOR EACH ROW EXECUTE FUNCTION track_media_references('category');
+
+DROP TRIGGER IF EXISTS restaurant_info_media_references ON restaurant_info;
+CREATE TRIGGER restaurant_info_media_references
+    AFTER INSERT OR DELETE OR UPDATE OF logo, hero_image, featured_dish_image ON restaurant_info
+    FOR EACH ROW EXECUTE FUNCTION track_media_references('restaurant_info');
@@ -0,0 +1,5 @@
+-- The table predates tracked migrations, so only the publishing columns go;
+-- posts are kept (drafts become published if 021 is applied again)
+
+ALTER TABLE blog_posts DROP COLUMN IF EXISTS published_at;
+ALTER TABLE blog_posts DROP COLUMN IF EXISTS status;
@@ -0,0 +1,30 @@
+-- Blog posts (previously created at startup by db_single::run_migrations,
+-- so the table may already exist without the publishing columns)
+
+CREATE TABLE IF NOT EXISTS blog_posts (
+    id VARCHAR(36) PRIMARY KEY,
+    slug VARCHAR(255) UNIQUE NOT NULL,
+    title VARCHAR(500) NOT NULL,
+    excerpt TEXT NOT NULL,
+    content TEXT NOT NULL, -- Markdown
+    date DATE NOT NULL,
+    author VARCHAR(255) NOT NULL,
+    image VARCHAR(1000) NOT NULL,
+    category VARCHAR(100) NOT NULL,
+    read_time VARCHAR(50) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Drafts and scheduled publishing; existing posts stay published
+ALTER TABLE blog_posts ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'published'; -- draft, published
+ALTER TABLE blog_posts ADD COLUMN IF NOT EXISTS published_at TIMESTAMPTZ; -- public from this moment on
+
+UPDATE blog_posts SET published_at = created_at WHERE status = 'published' AND published_at IS NULL;
+
+-- New posts start as drafts
+ALTER TABLE blog_posts ALTER COLUMN status SET DEFAULT 'draft';
+
+CREATE INDEX IF NOT EXISTS idx_blog_posts_slug ON blog_posts(slug);
+CREATE INDEX IF NOT EXISTS idx_blog_posts_date ON blog_posts(date DESC);
+CREATE INDEX IF NOT EXISTS idx_blog_posts_category ON blog_posts(category);
@@ -0,0 +1,13 @@
+-- Staff accounts for the admin panel, managed with `fodi-admin staff`.
+-- The ADMIN_PASSWORD_HASH account keeps working next to them.
+
+CREATE TABLE IF NOT EXISTS staff_users (
+    id SERIAL PRIMARY KEY,
+    username VARCHAR(50) UNIQUE NOT NULL,
+    password_hash VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER update_staff_users_updated_at BEFORE UPDATE ON staff_users
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,4 @@
+-- Set by `fodi-admin customers anonymize` once personal data is removed
+
+ALTER TABLE customers ADD COLUMN IF NOT EXISTS anonymized_at TIMESTAMPTZ;
+ALTER TABLE orders ADD COLUMN IF NOT EXISTS anonymized_at TIMESTAMPTZ;
@@ -0,0 +1,61 @@
+-- Blog images are no longer tracked: the sweep may delete them again once
+-- their grace period has passed
+
+DROP TRIGGER IF EXISTS blog_posts_media_references ON blog_posts;
+-- Released now, so the sweep waits a full grace period
+SELECT sync_media_references('blog_post', id, '{}', '{}') FROM blog_posts;
+
+DROP FUNCTION IF EXISTS sync_media_references(TEXT, TEXT, TEXT[], TEXT[]);
+DROP FUNCTION IF EXISTS markdown_image_urls(TEXT);
+
+ALTER TABLE media_asset_references ALTER COLUMN entity_id TYPE INTEGER USING entity_id::INTEGER;
+
+CREATE OR REPLACE FUNCTION sync_media_references(
+    p_entity TEXT, p_entity_id INTEGER, p_fields TEXT[], p_urls TEXT[]
+)
+RETURNS VOID AS $$
+DECLARE
+    released INTEGER[];
+BEGIN
+    WITH removed AS (
+        DELETE FROM media_asset_references
+        WHERE entity = p_entity AND entity_id = p_entity_id
+        RETURNING asset_id
+    )
+    SELECT array_agg(asset_id) INTO released FROM removed;
+
+    INSERT INTO media_asset_references (asset_id, entity, entity_id, field)
+    SELECT DISTINCT a.id, p_entity, p_entity_id, r.field
+    FROM unnest(p_fields, p_urls) AS r(field, url)
+    JOIN media_assets a ON a.urls @> ARRAY[r.url];
+
+    UPDATE media_assets a SET released_at = NOW()
+    WHERE a.id = ANY(released)
+      AND NOT EXISTS (SELECT 1 FROM media_asset_references r WHERE r.asset_id = a.id);
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION track_media_references()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        PERFORM sync_media_references(TG_ARGV[0], OLD.id, '{}', '{}');
+    ELSIF TG_TABLE_NAME = 'menu_items' THEN
+        PERFORM sync_media_references(
+            TG_ARGV[0], NEW.id,
+            ARRAY['image'] || array_fill('images'::TEXT, ARRAY[COALESCE(cardinality(NEW.images), 0)]),
+            ARRAY[NEW.image] || COALESCE(NEW.images, '{}')
+        );
+    ELSIF TG_TABLE_NAME = 'categories' THEN
+        PERFORM sync_media_references(TG_ARGV[0], NEW.id, ARRAY['image'], ARRAY[NEW.image]);
+    ELSIF TG_TABLE_NAME = 'restaurant_info' THEN
+        PERFORM sync_media_references(
+            TG_ARGV[0], NEW.id,
+            ARRAY['logo', 'hero_image', 'featured_dish_image'],
+            ARRAY[NEW.logo, NEW.hero_image, NEW.featured_dish_image]
+        );
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
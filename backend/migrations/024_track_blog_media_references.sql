@@ -0,0 +1,93 @@
+-- Blog posts use uploads too: the cover image and images in the Markdown
+-- body. Their ids are UUID strings, so references key entities by text.
+
+ALTER TABLE media_asset_references ALTER COLUMN entity_id TYPE TEXT USING entity_id::TEXT;
+
+DROP FUNCTION IF EXISTS sync_media_references(TEXT, INTEGER, TEXT[], TEXT[]);
+
+-- Replaces the references of one row with its current (field, url) pairs.
+-- URLs that are not uploads (external links, seed data) are ignored.
+CREATE OR REPLACE FUNCTION sync_media_references(
+    p_entity TEXT, p_entity_id TEXT, p_fields TEXT[], p_urls TEXT[]
+)
+RETURNS VOID AS $$
+DECLARE
+    released INTEGER[];
+BEGIN
+    WITH removed AS (
+        DELETE FROM media_asset_references
+        WHERE entity = p_entity AND entity_id = p_entity_id
+        RETURNING asset_id
+    )
+    SELECT array_agg(asset_id) INTO released FROM removed;
+
+    INSERT INTO media_asset_references (asset_id, entity, entity_id, field)
+    SELECT DISTINCT a.id, p_entity, p_entity_id, r.field
+    FROM unnest(p_fields, p_urls) AS r(field, url)
+    JOIN media_assets a ON a.urls @> ARRAY[r.url];
+
+    -- The grace period of the sweep starts when the last reference goes
+    UPDATE media_assets a SET released_at = NOW()
+    WHERE a.id = ANY(released)
+      AND NOT EXISTS (SELECT 1 FROM media_asset_references r WHERE r.asset_id = a.id);
+END;
+$$ LANGUAGE plpgsql;
+
+-- Destinations of ![alt](url) and of reference definitions ([id]: url),
+-- which ![alt][id] images point to
+CREATE OR REPLACE FUNCTION markdown_image_urls(p_markdown TEXT)
+RETURNS TEXT[] AS $$
+    SELECT COALESCE(array_agg(m[1]), '{}')
+    FROM (
+        SELECT regexp_matches(p_markdown, '!\[[^\]]*\]\(\s*<?([^\s)>]+)', 'g') AS m
+        UNION ALL
+        SELECT regexp_matches(p_markdown, '^ {0,3}\[[^\]]+\]:\s*<?([^\s>]+)', 'gn')
+    ) matches;
+$$ LANGUAGE sql IMMUTABLE;
+
+CREATE OR REPLACE FUNCTION track_media_references()
+RETURNS TRIGGER AS $$
+DECLARE
+    urls TEXT[];
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        PERFORM sync_media_references(TG_ARGV[0], OLD.id::TEXT, '{}', '{}');
+    ELSIF TG_TABLE_NAME = 'menu_items' THEN
+        PERFORM sync_media_references(
+            TG_ARGV[0], NEW.id::TEXT,
+            ARRAY['image'] || array_fill('images'::TEXT, ARRAY[COALESCE(cardinality(NEW.images), 0)]),
+            ARRAY[NEW.image] || COALESCE(NEW.images, '{}')
+        );
+    ELSIF TG_TABLE_NAME = 'categories' THEN
+        PERFORM sync_media_references(TG_ARGV[0], NEW.id::TEXT, ARRAY['image'], ARRAY[NEW.image]);
+    ELSIF TG_TABLE_NAME = 'restaurant_info' THEN
+        PERFORM sync_media_references(
+            TG_ARGV[0], NEW.id::TEXT,
+            ARRAY['logo', 'hero_image', 'featured_dish_image'],
+            ARRAY[NEW.logo, NEW.hero_image, NEW.featured_dish_image]
+        );
+    ELSIF TG_TABLE_NAME = 'blog_posts' THEN
+        urls := markdown_image_urls(NEW.content);
+        PERFORM sync_media_references(
+            TG_ARGV[0], NEW.id::TEXT,
+            ARRAY['image'] || array_fill('content'::TEXT, ARRAY[cardinality(urls)]),
+            ARRAY[NEW.image] || urls
+        );
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS blog_posts_media_references ON blog_posts;
+CREATE TRIGGER blog_posts_media_references
+    AFTER INSERT OR DELETE OR UPDATE OF image, content ON blog_posts
+    FOR EACH ROW EXECUTE FUNCTION track_media_references('blog_post');
+
+-- Posts written before this migration
+SELECT sync_media_references(
+    'blog_post', id,
+    ARRAY['image'] || array_fill('content'::TEXT, ARRAY[cardinality(markdown_image_urls(content))]),
+    ARRAY[image] || markdown_image_urls(content)
+)
+FROM blog_posts;
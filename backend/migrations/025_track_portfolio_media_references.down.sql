@@ -0,0 +1,7 @@
+-- The trigger function keeps its portfolio branch; without the trigger it
+-- is never taken
+
+DROP TRIGGER IF EXISTS portfolio_media_references ON portfolio;
+-- Released now, so the sweep waits a full grace period
+SELECT sync_media_references('portfolio', id::TEXT, '{}', '{}') FROM portfolio;
+DROP FUNCTION IF EXISTS jsonb_string_values(JSONB);
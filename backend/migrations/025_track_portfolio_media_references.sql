@@ -0,0 +1,63 @@
+-- Images in the chef profile (hero.avatar, portfolio[].image and any other
+-- string in `data`, since unknown keys are kept) are uploads too
+
+CREATE OR REPLACE FUNCTION track_media_references()
+RETURNS TRIGGER AS $$
+DECLARE
+    urls TEXT[];
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        PERFORM sync_media_references(TG_ARGV[0], OLD.id::TEXT, '{}', '{}');
+    ELSIF TG_TABLE_NAME = 'menu_items' THEN
+        PERFORM sync_media_references(
+            TG_ARGV[0], NEW.id::TEXT,
+            ARRAY['image'] || array_fill('images'::TEXT, ARRAY[COALESCE(cardinality(NEW.images), 0)]),
+            ARRAY[NEW.image] || COALESCE(NEW.images, '{}')
+        );
+    ELSIF TG_TABLE_NAME = 'categories' THEN
+        PERFORM sync_media_references(TG_ARGV[0], NEW.id::TEXT, ARRAY['image'], ARRAY[NEW.image]);
+    ELSIF TG_TABLE_NAME = 'restaurant_info' THEN
+        PERFORM sync_media_references(
+            TG_ARGV[0], NEW.id::TEXT,
+            ARRAY['logo', 'hero_image', 'featured_dish_image'],
+            ARRAY[NEW.logo, NEW.hero_image, NEW.featured_dish_image]
+        );
+    ELSIF TG_TABLE_NAME = 'blog_posts' THEN
+        urls := markdown_image_urls(NEW.content);
+        PERFORM sync_media_references(
+            TG_ARGV[0], NEW.id::TEXT,
+            ARRAY['image'] || array_fill('content'::TEXT, ARRAY[cardinality(urls)]),
+            ARRAY[NEW.image] || urls
+        );
+    ELSIF TG_TABLE_NAME = 'portfolio' THEN
+        urls := jsonb_string_values(NEW.data);
+        PERFORM sync_media_references(
+            TG_ARGV[0], NEW.id::TEXT,
+            array_fill('data'::TEXT, ARRAY[cardinality(urls)]),
+            urls
+        );
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Every string anywhere in a JSON document
+CREATE OR REPLACE FUNCTION jsonb_string_values(p_document JSONB)
+RETURNS TEXT[] AS $$
+    SELECT COALESCE(array_agg(value #>> '{}'), '{}')
+    FROM jsonb_path_query(p_document, 'strict $.** ? (@.type() == "string")') AS value;
+$$ LANGUAGE sql IMMUTABLE;
+
+DROP TRIGGER IF EXISTS portfolio_media_references ON portfolio;
+CREATE TRIGGER portfolio_media_references
+    AFTER INSERT OR DELETE OR UPDATE OF data ON portfolio
+    FOR EACH ROW EXECUTE FUNCTION track_media_references('portfolio');
+
+-- The profile saved before this migration
+SELECT sync_media_references(
+    'portfolio', id::TEXT,
+    array_fill('data'::TEXT, ARRAY[cardinality(jsonb_string_values(data))]),
+    jsonb_string_values(data)
+)
+FROM portfolio;
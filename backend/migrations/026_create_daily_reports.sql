@@ -0,0 +1,13 @@
+-- Sales summary of each closed day (local date in Europe/Warsaw), written by
+-- the report.daily job shortly after midnight
+
+CREATE TABLE IF NOT EXISTS daily_reports (
+    date DATE PRIMARY KEY,
+    orders BIGINT NOT NULL,
+    revenue NUMERIC(12, 2) NOT NULL,
+    average_order_value NUMERIC(10, 2) NOT NULL,
+    items_sold BIGINT NOT NULL,
+    cancelled_orders BIGINT NOT NULL,
+    cancellation_rate NUMERIC(5, 2) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW() -- recomputed when the job runs again for the day
+);
@@ -0,0 +1,2 @@
+-- Phones stay in E.164: both formats were accepted before
+DROP FUNCTION IF EXISTS e164_phone(TEXT);
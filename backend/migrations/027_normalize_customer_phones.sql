@@ -0,0 +1,24 @@
+-- Customer phones are stored in E.164 ("+48600100200"); numbers typed without a
+-- country code are Polish. Mirrors `normalize_phone` in customer_service.rs, so
+-- guest orders (stored as typed) can be matched to an account.
+CREATE OR REPLACE FUNCTION e164_phone(p_phone TEXT)
+RETURNS TEXT AS $$
+    SELECT '+' || CASE
+        WHEN btrim(p_phone) LIKE '+%' THEN digits
+        WHEN digits LIKE '00%' THEN substr(digits, 3)
+        WHEN length(digits) = 9 THEN '48' || digits
+        ELSE digits
+    END
+    FROM (SELECT regexp_replace(p_phone, '[^0-9]', '', 'g') AS digits) phone;
+$$ LANGUAGE sql IMMUTABLE;
+
+-- Accounts created before. Anonymized accounts keep their placeholder, and an
+-- account whose number another account already has in E.164 is left as it was.
+UPDATE customers c
+SET phone = e164_phone(c.phone)
+WHERE c.anonymized_at IS NULL
+  AND c.phone <> e164_phone(c.phone)
+  AND NOT EXISTS (
+      SELECT 1 FROM customers other
+      WHERE other.id <> c.id AND e164_phone(other.phone) = e164_phone(c.phone)
+  );
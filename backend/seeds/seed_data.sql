@@ -1,12 +1,8 @@
 -- Seed data for restaurant database
 -- Run this after migrations to populate initial data
 
--- Clear existing data (for testing)
-TRUNCATE TABLE order_items CASCADE;
-TRUNCATE TABLE orders CASCADE;
-TRUNCATE TABLE menu_items CASCADE;
-TRUNCATE TABLE categories CASCADE;
-TRUNCATE TABLE restaurant_info CASCADE;
+-- Insert-only: rows that already exist (by id, slug or menu item name) are
+-- kept as they are, so running the seeds again never loses data
 
 -- Insert Restaurant Info
 INSERT INTO restaurant_info (
@@ -32,7 +28,8 @@ INSERT INTO restaurant_info (
     5.00,
     100.00,
     30 -- 30 minutes
-);
+)
+ON CONFLICT (id) DO NOTHING;
 
 -- Insert Categories
 INSERT INTO categories (id, name, name_ru, name_pl, slug, description, "order", is_active) VALUES
@@ -40,7 +37,8 @@ INSERT INTO categories (id, name, name_ru, name_pl, slug, description, "order",
 (2, 'Ramen', 'Рамен', 'Ramen', 'ramen', 'Traditional Japanese noodle soup', 2, true),
 (3, 'Appetizers', 'Закуски', 'Przystawki', 'appetizers', 'Delicious starters', 3, true),
 (4, 'Desserts', 'Десерты', 'Desery', 'desserts', 'Sweet treats', 4, true),
-(5, 'Drinks', 'Напитки', 'Napoje', 'drinks', 'Refreshing beverages', 5, true);
+(5, 'Drinks', 'Напитки', 'Napoje', 'drinks', 'Refreshing beverages', 5, true)
+ON CONFLICT DO NOTHING;
 
 -- Reset category sequence
 SELECT setval('categories_id_seq', (SELECT MAX(id) FROM categories));
@@ -52,7 +50,8 @@ INSERT INTO menu_items (
     category_id, name, name_ru, name_pl,
     description, description_ru, description_pl,
     price, image, is_popular, is_new, weight
-) VALUES
+)
+SELECT * FROM (VALUES
 (1, 'California Roll', 'Калифорния', 'California Roll',
  'Fresh avocado, crab stick, cucumber', 'Свежий авокадо, крабовая палочка, огурец', 'Świeże awokado, pałeczki krabowe, ogórek',
  45.00, 'https://res.cloudinary.com/demo/image/upload/v1/food/sushi-california.jpg', true, false, '8 шт'),
@@ -67,14 +66,21 @@ INSERT INTO menu_items (
 
 (1, 'Dragon Roll', 'Дракон', 'Dragon Roll',
  'Eel, avocado, cucumber, special sauce', 'Угорь, авокадо, огурец, специальный соус', 'Węgorz, awokado, ogórek, specjalny sos',
- 65.00, 'https://res.cloudinary.com/demo/image/upload/v1/food/sushi-dragon.jpg', true, false, '8 шт');
+ 65.00, 'https://res.cloudinary.com/demo/image/upload/v1/food/sushi-dragon.jpg', true, false, '8 шт')
+) AS v (
+    category_id, name, name_ru, name_pl,
+    description, description_ru, description_pl,
+    price, image, is_popular, is_new, weight
+)
+WHERE NOT EXISTS (SELECT 1 FROM menu_items m WHERE m.name = v.name);
 
 -- Ramen Category
 INSERT INTO menu_items (
     category_id, name, name_ru, name_pl,
     description, description_ru, description_pl,
     price, image, cooking_time, calories
-) VALUES
+)
+SELECT * FROM (VALUES
 (2, 'Tonkotsu Ramen', 'Тонкоцу рамен', 'Tonkotsu Ramen',
  'Rich pork bone broth, chashu pork, egg', 'Насыщенный свиной бульон, свинина чашу, яйцо', 'Bogaty bulion wieprzowy, wieprzowina chashu, jajko',
  42.00, 'https://res.cloudinary.com/demo/image/upload/v1/food/ramen-tonkotsu.jpg', 15, 650),
@@ -85,14 +91,21 @@ INSERT INTO menu_items (
 
 (2, 'Spicy Chicken Ramen', 'Острый куриный рамен', 'Pikantny ramen z kurczakiem',
  'Spicy chicken broth, chicken, vegetables', 'Острый куриный бульон, курица, овощи', 'Pikantny bulion z kurczaka, kurczak, warzywa',
- 40.00, 'https://res.cloudinary.com/demo/image/upload/v1/food/ramen-spicy-chicken.jpg', 13, 600);
+ 40.00, 'https://res.cloudinary.com/demo/image/upload/v1/food/ramen-spicy-chicken.jpg', 13, 600)
+) AS v (
+    category_id, name, name_ru, name_pl,
+    description, description_ru, description_pl,
+    price, image, cooking_time, calories
+)
+WHERE NOT EXISTS (SELECT 1 FROM menu_items m WHERE m.name = v.name);
 
 -- Appetizers Category
 INSERT INTO menu_items (
     category_id, name, name_ru, name_pl,
     description, description_ru, description_pl,
     price, image, is_vegetarian
-) VALUES
+)
+SELECT * FROM (VALUES
 (3, 'Edamame', 'Эдамаме', 'Edamame',
  'Steamed soybeans with sea salt', 'Вареные соевые бобы с морской солью', 'Gotowane soja z solą morską',
  15.00, 'https://res.cloudinary.com/demo/image/upload/v1/food/edamame.jpg', true),
@@ -103,28 +116,42 @@ INSERT INTO menu_items (
 
 (3, 'Spring Rolls', 'Спринг роллы', 'Spring Rolls',
  'Crispy vegetable spring rolls', 'Хрустящие овощные роллы', 'Chrupiące wiosenne rolki warzywne',
- 18.00, 'https://res.cloudinary.com/demo/image/upload/v1/food/spring-rolls.jpg', true);
+ 18.00, 'https://res.cloudinary.com/demo/image/upload/v1/food/spring-rolls.jpg', true)
+) AS v (
+    category_id, name, name_ru, name_pl,
+    description, description_ru, description_pl,
+    price, image, is_vegetarian
+)
+WHERE NOT EXISTS (SELECT 1 FROM menu_items m WHERE m.name = v.name);
 
 -- Desserts Category
 INSERT INTO menu_items (
     category_id, name, name_ru, name_pl,
     description, description_ru, description_pl,
     price, image
-) VALUES
+)
+SELECT * FROM (VALUES
 (4, 'Mochi Ice Cream', 'Моти с мороженым', 'Mochi z lodami',
  'Traditional Japanese rice cake with ice cream', 'Традиционный японский рисовый пирог с мороженым', 'Tradycyjne japońskie ciasto ryżowe z lodami',
  12.00, 'https://res.cloudinary.com/demo/image/upload/v1/food/mochi.jpg'),
 
 (4, 'Matcha Cheesecake', 'Чизкейк матча', 'Sernik matcha',
  'Green tea cheesecake', 'Чизкейк с зеленым чаем', 'Sernik z zieloną herbatą',
- 16.00, 'https://res.cloudinary.com/demo/image/upload/v1/food/matcha-cheesecake.jpg');
+ 16.00, 'https://res.cloudinary.com/demo/image/upload/v1/food/matcha-cheesecake.jpg')
+) AS v (
+    category_id, name, name_ru, name_pl,
+    description, description_ru, description_pl,
+    price, image
+)
+WHERE NOT EXISTS (SELECT 1 FROM menu_items m WHERE m.name = v.name);
 
 -- Drinks Category
 INSERT INTO menu_items (
     category_id, name, name_ru, name_pl,
     description, description_ru, description_pl,
     price, image
-) VALUES
+)
+SELECT * FROM (VALUES
 (5, 'Green Tea', 'Зеленый чай', 'Zielona herbata',
  'Traditional Japanese green tea', 'Традиционный японский зеленый чай', 'Tradycyjna japońska zielona herbata',
  8.00, 'https://res.cloudinary.com/demo/image/upload/v1/food/green-tea.jpg'),
@@ -135,7 +162,13 @@ INSERT INTO menu_items (
 
 (5, 'Sake (Hot)', 'Сакэ (горячее)', 'Sake (gorące)',
  'Traditional Japanese rice wine, served hot', 'Традиционное японское рисовое вино, подается горячим', 'Tradycyjne japońskie wino ryżowe, podawane na gorąco',
- 22.00, 'https://res.cloudinary.com/demo/image/upload/v1/food/sake.jpg');
+ 22.00, 'https://res.cloudinary.com/demo/image/upload/v1/food/sake.jpg')
+) AS v (
+    category_id, name, name_ru, name_pl,
+    description, description_ru, description_pl,
+    price, image
+)
+WHERE NOT EXISTS (SELECT 1 FROM menu_items m WHERE m.name = v.name);
 
 -- Reset menu items sequence
 SELECT setval('menu_items_id_seq', (SELECT MAX(id) FROM menu_items));
@@ -0,0 +1,366 @@
+// Operational tasks against the database in DATABASE_URL (or --database-url);
+// settings are read the same way as fodi-server, see Settings::load
+use anyhow::{bail, Context, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use rand::{distributions::Alphanumeric, Rng};
+use sqlx::PgPool;
+use std::path::{Path, PathBuf};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+use portfolio_api::config::Settings;
+use portfolio_api::db_single::{self, MIGRATOR};
+use portfolio_api::models::restaurant::RestaurantConfig;
+use portfolio_api::services::data_maintenance_service::DataMaintenanceService;
+use portfolio_api::services::menu_transfer_service::{self, MenuTransferService};
+use portfolio_api::services::restaurant_config_service::RestaurantConfigService;
+use portfolio_api::services::staff_service::{self, StaffService};
+
+// Recorded as the actor in the menu history
+const CLI_ACTOR: &str = "fodi-admin";
+
+const GENERATED_PASSWORD_LENGTH: usize = 16;
+
+#[derive(Parser)]
+#[command(name = "fodi-admin", about = "FodiFood operational tasks")]
+struct Cli {
+    /// Overrides DATABASE_URL
+    #[arg(long, global = true)]
+    database_url: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print a bcrypt hash for ADMIN_PASSWORD_HASH
+    HashPassword(PasswordArgs),
+    /// Admin panel staff accounts
+    #[command(subcommand)]
+    Staff(StaffCommand),
+    /// Database schema migrations
+    #[command(subcommand)]
+    Migrate(MigrateCommand),
+    /// Load the demo menu and restaurant data (backend/seeds)
+    Seed {
+        /// Run the seeds again even if already applied
+        #[arg(long)]
+        force: bool,
+        /// Allow seeding outside development
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Menu import and export (same formats as /api/restaurant/admin/menu/export)
+    #[command(subcommand)]
+    Menu(MenuCommand),
+    /// Stored aggregates
+    #[command(subcommand)]
+    Analytics(AnalyticsCommand),
+    /// Customer personal data
+    #[command(subcommand)]
+    Customers(CustomersCommand),
+    /// Restaurant settings and their translations
+    #[command(subcommand)]
+    Config(ConfigCommand),
+}
+
+#[derive(Args)]
+struct PasswordArgs {
+    /// Generated and printed when omitted
+    #[arg(long)]
+    password: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum StaffCommand {
+    List,
+    Create {
+        username: String,
+        #[command(flatten)]
+        password: PasswordArgs,
+    },
+    ResetPassword {
+        username: String,
+        #[command(flatten)]
+        password: PasswordArgs,
+    },
+}
+
+#[derive(Subcommand)]
+enum MigrateCommand {
+    /// Apply pending migrations
+    Run {
+        /// Last migration already applied to a database without migration history
+        #[arg(long)]
+        baseline: Option<i64>,
+    },
+    /// List migrations and whether they are applied
+    Status,
+    /// Revert migrations newer than the target (needs their .down.sql)
+    Rollback {
+        #[arg(long)]
+        to: i64,
+    },
+}
+
+#[derive(Subcommand)]
+enum MenuCommand {
+    Export {
+        #[arg(long, value_enum, default_value_t = MenuFormat::Json)]
+        format: MenuFormat,
+        /// Written to stdout when omitted
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Upsert categories and items; nothing is saved if any row is invalid
+    Import {
+        file: PathBuf,
+        /// Defaults to the file extension
+        #[arg(long, value_enum)]
+        format: Option<MenuFormat>,
+        /// Validate and report the diff without saving
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum MenuFormat {
+    Json,
+    Csv,
+}
+
+#[derive(Subcommand)]
+enum AnalyticsCommand {
+    /// Credit missed loyalty points and rebuild customer balances
+    Recompute,
+}
+
+#[derive(Subcommand)]
+enum CustomersCommand {
+    /// Remove personal data from finished orders and inactive accounts
+    Anonymize {
+        /// Only data older than this many days
+        #[arg(long)]
+        older_than_days: u32,
+        /// Count what would change without saving
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    Dump {
+        /// Written to stdout when omitted
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    Restore { file: PathBuf },
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| "portfolio_api=info,fodi_admin=info".into()),
+        )
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+        .init();
+
+    dotenvy::dotenv().ok();
+    let cli = Cli::parse();
+    let settings = Settings::load().context("Invalid configuration")?;
+
+    if let Command::HashPassword(args) = &cli.command {
+        let password = password_or_generated(args);
+        let hash = staff_service::hash_password(&password)?;
+        println!("ADMIN_PASSWORD_HASH={}", hash);
+        return Ok(());
+    }
+
+    let database_url = cli
+        .database_url
+        .clone()
+        .or_else(|| settings.get("DATABASE_URL"))
+        .context("DATABASE_URL is required (or pass --database-url)")?;
+    let pool = db_single::create_pool(&database_url, 2)
+        .await
+        .context("Failed to connect to the database")?;
+
+    match cli.command {
+        Command::HashPassword(_) => unreachable!("handled before connecting"),
+        Command::Staff(command) => staff(pool, command).await,
+        Command::Migrate(command) => migrate(pool, command).await,
+        Command::Seed { force, yes } => {
+            if !settings.environment()?.is_development() && !yes {
+                bail!("Seeds add demo menu and restaurant data; pass --yes to run them outside development");
+            }
+            db_single::run_seeds(&pool, force).await
+        }
+        Command::Menu(command) => menu(pool, command).await,
+        Command::Analytics(AnalyticsCommand::Recompute) => {
+            let result = DataMaintenanceService::new(pool).recompute_loyalty().await?;
+            println!(
+                "Credited {} delivered orders, corrected {} loyalty balances",
+                result.orders_credited, result.balances_corrected
+            );
+            Ok(())
+        }
+        Command::Customers(CustomersCommand::Anonymize {
+            older_than_days,
+            dry_run,
+        }) => {
+            let before = chrono::Utc::now() - chrono::Duration::days(older_than_days.into());
+            let report = DataMaintenanceService::new(pool)
+                .anonymize_customers(before, dry_run)
+                .await?;
+            println!(
+                "{} {} customers and {} orders older than {}",
+                if dry_run { "Would anonymize" } else { "Anonymized" },
+                report.customers,
+                report.orders,
+                before.format("%Y-%m-%d")
+            );
+            Ok(())
+        }
+        Command::Config(command) => config(pool, command).await,
+    }
+}
+
+async fn staff(pool: PgPool, command: StaffCommand) -> Result<()> {
+    let staff = StaffService::new(pool);
+
+    match command {
+        StaffCommand::List => {
+            for user in staff.list().await? {
+                println!("{}\t{}\tcreated {}", user.id, user.username, user.created_at.format("%Y-%m-%d"));
+            }
+        }
+        StaffCommand::Create { username, password } => {
+            let generated = password.password.is_none();
+            let password = password_or_generated(&password);
+            let user = staff.create(&username, &password).await?;
+            println!("Created staff user '{}' (id {})", user.username, user.id);
+            if generated {
+                println!("Password: {}", password);
+            }
+        }
+        StaffCommand::ResetPassword { username, password } => {
+            let generated = password.password.is_none();
+            let password = password_or_generated(&password);
+            let user = staff.reset_password(&username, &password).await?;
+            println!("Password reset for '{}'", user.username);
+            if generated {
+                println!("Password: {}", password);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn migrate(pool: PgPool, command: MigrateCommand) -> Result<()> {
+    match command {
+        MigrateCommand::Run { baseline } => db_single::run_migrations(&pool, baseline).await,
+        MigrateCommand::Status => {
+            let applied = db_single::applied_migrations(&pool).await?;
+            for migration in MIGRATOR.iter().filter(|m| m.migration_type.is_up_migration()) {
+                println!(
+                    "{:03}  {:<8} {}{}",
+                    migration.version,
+                    if applied.contains(&migration.version) { "applied" } else { "pending" },
+                    migration.description,
+                    if db_single::has_down_script(migration.version) { " (reversible)" } else { "" }
+                );
+            }
+            Ok(())
+        }
+        MigrateCommand::Rollback { to } => db_single::rollback_migrations(&pool, to).await,
+    }
+}
+
+async fn menu(pool: PgPool, command: MenuCommand) -> Result<()> {
+    let menu = MenuTransferService::new(pool);
+
+    match command {
+        MenuCommand::Export { format, output } => {
+            let export = menu.export().await?;
+            let contents = match format {
+                MenuFormat::Json => serde_json::to_string_pretty(&export)?,
+                MenuFormat::Csv => menu_transfer_service::export_csv(&export)?,
+            };
+            write_output(output.as_deref(), &contents)
+        }
+        MenuCommand::Import { file, format, dry_run } => {
+            let body = std::fs::read_to_string(&file).with_context(|| format!("Failed to read {}", file.display()))?;
+            let format = format.unwrap_or_else(|| match file.extension().and_then(|e| e.to_str()) {
+                Some("csv") => MenuFormat::Csv,
+                _ => MenuFormat::Json,
+            });
+            let parsed = match format {
+                MenuFormat::Json => menu_transfer_service::parse_json(&body)?,
+                MenuFormat::Csv => menu_transfer_service::parse_csv(&body)?,
+            };
+
+            let report = menu.import(parsed, dry_run, CLI_ACTOR).await?;
+            for row in report.rows.iter().filter(|row| !row.errors.is_empty()) {
+                eprintln!("row {} ({} {}): {}", row.row, row.entity, row.key, row.errors.join("; "));
+            }
+            let summary = &report.summary;
+            println!(
+                "{} created, {} updated, {} unchanged, {} errors{}",
+                summary.created,
+                summary.updated,
+                summary.unchanged,
+                summary.errors,
+                if report.committed { "" } else { " (nothing saved)" }
+            );
+
+            if summary.errors > 0 {
+                bail!("Import failed");
+            }
+            Ok(())
+        }
+    }
+}
+
+async fn config(pool: PgPool, command: ConfigCommand) -> Result<()> {
+    let restaurant = RestaurantConfigService::new(pool);
+
+    match command {
+        ConfigCommand::Dump { output } => {
+            let config = restaurant.dump().await?;
+            write_output(output.as_deref(), &serde_json::to_string_pretty(&config)?)
+        }
+        ConfigCommand::Restore { file } => {
+            let body = std::fs::read_to_string(&file).with_context(|| format!("Failed to read {}", file.display()))?;
+            let config: RestaurantConfig =
+                serde_json::from_str(&body).with_context(|| format!("Invalid config dump {}", file.display()))?;
+            let info = restaurant.restore(config).await?;
+            println!("Restored settings of '{}'", info.name);
+            Ok(())
+        }
+    }
+}
+
+fn password_or_generated(args: &PasswordArgs) -> String {
+    args.password.clone().unwrap_or_else(|| {
+        rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(GENERATED_PASSWORD_LENGTH)
+            .map(char::from)
+            .collect()
+    })
+}
+
+fn write_output(path: Option<&Path>, contents: &str) -> Result<()> {
+    match path {
+        Some(path) => std::fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display())),
+        None => {
+            println!("{}", contents);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,41 @@
+// Standalone server without Shuttle: settings from CONFIG_FILE (or
+// ./fodi.toml) and environment variables, see AppConfig::load
+use anyhow::Context;
+use portfolio_api::config::AppConfig;
+use portfolio_api::{create_app, db_single};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| "portfolio_api=info,fodi_server=info,tower_http=info".into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    dotenvy::dotenv().ok();
+    let config = AppConfig::load().context("Invalid configuration")?;
+
+    let database_url = config.database_url.clone().context("DATABASE_URL is required")?;
+    let pool = db_single::create_pool(&database_url, config.database_max_connections)
+        .await
+        .context("Failed to connect to the database")?;
+    tracing::info!("✅ Database connected");
+
+    let port = config.port;
+    let app = create_app(pool, config).await.context("Failed to start the API")?;
+
+    let listener = tokio::net::TcpListener::bind(("0.0.0.0", port)).await?;
+    tracing::info!("🚀 Listening on http://0.0.0.0:{}", port);
+
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async {
+            tokio::signal::ctrl_c().await.ok();
+            tracing::info!("Shutting down");
+        })
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,373 @@
+use anyhow::{bail, Context, Result};
+use axum::http::HeaderValue;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::locale::LocaleConfig;
+use crate::models_single::AdminConfig;
+use crate::services::cloudinary_service::CloudinaryConfig;
+use crate::services::image_storage::S3Config;
+use crate::services::notifier::SmtpConfig;
+use crate::services::sms_sender::SmsGatewayConfig;
+
+// Development fallbacks; refused anywhere else
+const DEFAULT_JWT_SECRET: &str = "your-secret-key-change-in-production";
+const DEFAULT_ADMIN_PASSWORD: &str = "admin123";
+
+// Read by the standalone server and fodi-admin when CONFIG_FILE is not set
+const DEFAULT_CONFIG_FILE: &str = "fodi.toml";
+
+/// Raw `KEY = value` settings. Shuttle secrets, environment variables and a
+/// TOML file in the same format as Secrets.toml all end up here.
+#[derive(Debug, Clone, Default)]
+pub struct Settings(BTreeMap<String, String>);
+
+impl Settings {
+    /// Empty values count as not set
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.0.get(key).filter(|v| !v.trim().is_empty()).cloned()
+    }
+
+    /// The TOML file at CONFIG_FILE (or ./fodi.toml when present) with
+    /// environment variables on top
+    pub fn load() -> Result<Self> {
+        let path = std::env::var("CONFIG_FILE").ok();
+        let mut settings = match path.as_deref() {
+            Some(path) => read_file(path)?,
+            None if Path::new(DEFAULT_CONFIG_FILE).exists() => read_file(DEFAULT_CONFIG_FILE)?,
+            None => Settings::default(),
+        };
+        settings.extend(std::env::vars());
+
+        Ok(settings)
+    }
+
+    pub fn environment(&self) -> Result<Environment> {
+        Environment::parse(self.get("ENVIRONMENT").as_deref())
+    }
+
+    /// Flat table of strings, numbers and booleans
+    pub fn from_toml(text: &str) -> Result<Self> {
+        let table: toml::Table = toml::from_str(text)?;
+        let mut settings = BTreeMap::new();
+        for (key, value) in table {
+            let value = match value {
+                toml::Value::String(s) => s,
+                toml::Value::Integer(i) => i.to_string(),
+                toml::Value::Float(f) => f.to_string(),
+                toml::Value::Boolean(b) => b.to_string(),
+                _ => bail!("{} must be a string, number or boolean", key),
+            };
+            settings.insert(key, value);
+        }
+        Ok(Self(settings))
+    }
+}
+
+impl FromIterator<(String, String)> for Settings {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Extend<(String, String)> for Settings {
+    fn extend<I: IntoIterator<Item = (String, String)>>(&mut self, iter: I) {
+        self.0.extend(iter)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Environment {
+    Development,
+    Production,
+}
+
+impl Environment {
+    fn parse(value: Option<&str>) -> Result<Self> {
+        match value {
+            Some("development") | Some("dev") => Ok(Environment::Development),
+            Some("production") | Some("prod") | None => Ok(Environment::Production),
+            Some(other) => bail!("Unknown ENVIRONMENT '{}' (expected development or production)", other),
+        }
+    }
+
+    pub fn is_development(self) -> bool {
+        self == Environment::Development
+    }
+}
+
+/// Where uploads go: IMAGE_STORAGE=cloudinary|local|s3. Defaults to Cloudinary
+/// when its secrets are set, otherwise to files on local disk.
+#[derive(Debug, Clone)]
+pub enum ImageStorageConfig {
+    Cloudinary(CloudinaryConfig),
+    // Served by the API itself at `mount_path`, the path of `base_url`
+    Local { dir: PathBuf, base_url: String, mount_path: String },
+    S3(S3Config),
+}
+
+pub struct AppConfig {
+    pub environment: Environment,
+    // Standalone server only: Shuttle provisions the database and the port
+    pub database_url: Option<String>,
+    pub port: u16,
+    pub database_max_connections: u32,
+    pub jwt_secret: String,
+    pub admin: AdminConfig,
+    // Empty: any origin
+    pub cors_origins: Vec<HeaderValue>,
+    pub image_storage: ImageStorageConfig,
+    // Uploads nothing uses are deleted after this long (MEDIA_ORPHAN_GRACE_HOURS)
+    pub media_orphan_grace: chrono::Duration,
+    // Customer notifications; unconfigured channels are written to
+    // `notifications_log` (or the log) instead
+    pub smtp: Option<SmtpConfig>,
+    pub sms_gateway: Option<SmsGatewayConfig>,
+    pub notifications_log: Option<PathBuf>,
+    // CONTENT_LOCALES and LOCALE_FALLBACKS
+    pub(crate) locales: LocaleConfig,
+    // Last migration applied by hand to a database without migration history
+    pub migrations_baseline: Option<i64>,
+    // Run the dev seeds outside development
+    pub seed_data: bool,
+    // Off: jobs stay queued until another instance with the worker picks them up
+    pub job_worker: bool,
+}
+
+impl AppConfig {
+    /// Standalone server: see Settings::load
+    pub fn load() -> Result<Self> {
+        Self::from_settings(Settings::load()?)
+    }
+
+    pub fn from_settings(settings: Settings) -> Result<Self> {
+        let environment = settings.environment()?;
+        let development = environment.is_development();
+
+        let jwt_secret = match settings.get("JWT_SECRET") {
+            Some(secret) if secret != DEFAULT_JWT_SECRET => secret,
+            _ if development => {
+                tracing::warn!("⚠️  JWT_SECRET not set, using the development default");
+                DEFAULT_JWT_SECRET.to_string()
+            }
+            _ => bail!("JWT_SECRET must be set to a non-default value outside development"),
+        };
+
+        let admin_password_hash = match settings.get("ADMIN_PASSWORD_HASH") {
+            Some(hash) => {
+                let is_default = bcrypt::verify(DEFAULT_ADMIN_PASSWORD, &hash)
+                    .context("ADMIN_PASSWORD_HASH is not a bcrypt hash")?;
+                if is_default && !development {
+                    bail!("ADMIN_PASSWORD_HASH is the hash of the default password, set a real one");
+                }
+                hash
+            }
+            None if development => {
+                tracing::warn!("⚠️  ADMIN_PASSWORD_HASH not set, admin password is '{}'", DEFAULT_ADMIN_PASSWORD);
+                bcrypt::hash(DEFAULT_ADMIN_PASSWORD, bcrypt::DEFAULT_COST)?
+            }
+            None => bail!("ADMIN_PASSWORD_HASH must be set outside development"),
+        };
+
+        let cors_origins = settings
+            .get("CORS_ORIGINS")
+            .map(|origins| {
+                origins
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|o| !o.is_empty())
+                    .map(|o| HeaderValue::from_str(o).with_context(|| format!("Invalid CORS origin '{}'", o)))
+                    .collect::<Result<Vec<_>>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        let cloudinary = all_or_none(
+            &settings,
+            &["CLOUDINARY_CLOUD_NAME", "CLOUDINARY_API_KEY", "CLOUDINARY_API_SECRET", "CLOUDINARY_UPLOAD_PRESET"],
+        )?
+        .map(|[cloud_name, api_key, api_secret, upload_preset]| CloudinaryConfig {
+            cloud_name,
+            api_key,
+            api_secret,
+            upload_preset,
+        });
+
+        let image_storage = match (settings.get("IMAGE_STORAGE").as_deref(), cloudinary) {
+            (Some("cloudinary") | None, Some(cloudinary)) => ImageStorageConfig::Cloudinary(cloudinary),
+            (Some("cloudinary"), None) => bail!("IMAGE_STORAGE=cloudinary needs the CLOUDINARY_* secrets"),
+            (Some("local") | None, _) => {
+                let base_url = settings.get("MEDIA_BASE_URL").unwrap_or_else(|| "/media".to_string());
+                ImageStorageConfig::Local {
+                    dir: PathBuf::from(settings.get("MEDIA_DIR").unwrap_or_else(|| "uploads".to_string())),
+                    mount_path: media_mount_path(&base_url)?,
+                    base_url,
+                }
+            }
+            (Some("s3"), _) => {
+                let required = |key: &str| settings.get(key).with_context(|| format!("IMAGE_STORAGE=s3 needs {}", key));
+                ImageStorageConfig::S3(S3Config {
+                    endpoint: required("S3_ENDPOINT")?,
+                    region: settings.get("S3_REGION").unwrap_or_else(|| "us-east-1".to_string()),
+                    bucket: required("S3_BUCKET")?,
+                    access_key_id: required("S3_ACCESS_KEY_ID")?,
+                    secret_access_key: required("S3_SECRET_ACCESS_KEY")?,
+                    public_url: settings.get("S3_PUBLIC_URL"),
+                })
+            }
+            (Some(other), _) => bail!("Unknown IMAGE_STORAGE '{}' (expected cloudinary, local or s3)", other),
+        };
+
+        let smtp = all_or_none(&settings, &["SMTP_HOST", "SMTP_USERNAME", "SMTP_PASSWORD", "SMTP_FROM"])?
+            .map(|[host, username, password, from]| -> Result<SmtpConfig> {
+                from.parse::<lettre::message::Mailbox>()
+                    .with_context(|| format!("Invalid SMTP_FROM: '{}'", from))?;
+                Ok(SmtpConfig {
+                    host,
+                    port: parse(&settings, "SMTP_PORT")?.unwrap_or(587),
+                    username,
+                    password,
+                    from,
+                })
+            })
+            .transpose()?;
+
+        let sms_gateway = all_or_none(&settings, &["SMS_GATEWAY_URL", "SMS_GATEWAY_API_KEY"])?.map(|[url, api_key]| {
+            SmsGatewayConfig {
+                url,
+                api_key,
+                sender_id: settings.get("SMS_SENDER_ID").unwrap_or_else(|| "FodiFood".to_string()),
+            }
+        });
+
+        Ok(Self {
+            environment,
+            database_url: settings.get("DATABASE_URL"),
+            port: parse(&settings, "PORT")?.unwrap_or(8000),
+            database_max_connections: parse(&settings, "DATABASE_MAX_CONNECTIONS")?.unwrap_or(5),
+            jwt_secret,
+            admin: AdminConfig {
+                username: settings.get("ADMIN_USERNAME").unwrap_or_else(|| "admin".to_string()),
+                password_hash: admin_password_hash,
+            },
+            cors_origins,
+            image_storage,
+            media_orphan_grace: chrono::Duration::hours(
+                parse::<u32>(&settings, "MEDIA_ORPHAN_GRACE_HOURS")?.unwrap_or(24).into(),
+            ),
+            smtp,
+            sms_gateway,
+            notifications_log: settings.get("NOTIFICATIONS_LOG_FILE").map(PathBuf::from),
+            locales: LocaleConfig::from_settings(
+                settings.get("CONTENT_LOCALES").as_deref(),
+                settings.get("LOCALE_FALLBACKS").as_deref(),
+            ),
+            migrations_baseline: parse(&settings, "MIGRATIONS_BASELINE")?,
+            seed_data: parse(&settings, "SEED_DATA")?.unwrap_or(false),
+            job_worker: parse(&settings, "JOB_WORKER")?.unwrap_or(true),
+        })
+    }
+}
+
+fn read_file(path: &str) -> Result<Settings> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("Failed to read config file {}", path))?;
+    Settings::from_toml(&text).with_context(|| format!("Invalid config file {}", path))
+}
+
+fn parse<T: std::str::FromStr>(settings: &Settings, key: &str) -> Result<Option<T>> {
+    settings
+        .get(key)
+        .map(|value| value.trim().parse().ok().with_context(|| format!("Invalid {}: '{}'", key, value)))
+        .transpose()
+}
+
+/// Settings that only work together: all of them or none
+fn all_or_none<const N: usize>(settings: &Settings, keys: &[&str; N]) -> Result<Option<[String; N]>> {
+    let values = keys.map(|key| settings.get(key));
+    if values.iter().all(Option::is_none) {
+        return Ok(None);
+    }
+
+    let missing: Vec<&str> = keys.iter().zip(&values).filter(|(_, v)| v.is_none()).map(|(k, _)| *k).collect();
+    if !missing.is_empty() {
+        bail!("{} must be set together; missing {}", keys.join(", "), missing.join(", "));
+    }
+    Ok(Some(values.map(Option::unwrap_or_default)))
+}
+
+// `/media` for both `/media` and `http://localhost:8000/media`
+fn media_mount_path(base_url: &str) -> Result<String> {
+    let path = match reqwest::Url::parse(base_url) {
+        Ok(url) => url.path().to_string(),
+        Err(_) => base_url.to_string(),
+    };
+    let path = path.trim_end_matches('/');
+    if !path.starts_with('/') {
+        bail!("MEDIA_BASE_URL needs a path to serve the files at, such as /media (got '{}')", base_url);
+    }
+    Ok(path.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(extra: &[(&str, &str)]) -> Result<AppConfig> {
+        let password_hash = bcrypt::hash("not-the-default", 4).unwrap();
+        let base = [("JWT_SECRET", "test-secret"), ("ADMIN_PASSWORD_HASH", password_hash.as_str())];
+        AppConfig::from_settings(
+            base.iter()
+                .chain(extra)
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        )
+    }
+
+    fn error(extra: &[(&str, &str)]) -> String {
+        match config(extra) {
+            Ok(_) => panic!("{:?} was accepted", extra),
+            Err(e) => format!("{:#}", e),
+        }
+    }
+
+    #[test]
+    fn integrations_are_parsed_into_typed_settings() {
+        let config = config(&[
+            ("MEDIA_BASE_URL", "https://api.example.com/files/"),
+            ("MEDIA_ORPHAN_GRACE_HOURS", "48"),
+            ("SMTP_HOST", "smtp.example.com"),
+            ("SMTP_USERNAME", "fodi"),
+            ("SMTP_PASSWORD", "secret"),
+            ("SMTP_FROM", "FodiFood <orders@example.com>"),
+        ])
+        .unwrap();
+
+        match config.image_storage {
+            ImageStorageConfig::Local { mount_path, .. } => assert_eq!(mount_path, "/files"),
+            other => panic!("unexpected storage {:?}", other),
+        }
+        assert_eq!(config.media_orphan_grace, chrono::Duration::hours(48));
+        assert_eq!(config.smtp.unwrap().port, 587);
+        assert!(config.sms_gateway.is_none());
+    }
+
+    #[test]
+    fn broken_integration_settings_fail_with_the_setting_named() {
+        assert!(error(&[("IMAGE_STORAGE", "ftp")]).contains("IMAGE_STORAGE"));
+        assert!(error(&[("IMAGE_STORAGE", "cloudinary")]).contains("CLOUDINARY_"));
+        assert!(error(&[("IMAGE_STORAGE", "s3"), ("S3_ENDPOINT", "https://s3.example.com")]).contains("S3_BUCKET"));
+        assert!(error(&[("MEDIA_BASE_URL", "https://cdn.example.com")]).contains("MEDIA_BASE_URL"));
+        assert!(error(&[("MEDIA_ORPHAN_GRACE_HOURS", "a day")]).contains("MEDIA_ORPHAN_GRACE_HOURS"));
+        assert!(error(&[("SMTP_HOST", "smtp.example.com")]).contains("missing SMTP_USERNAME, SMTP_PASSWORD, SMTP_FROM"));
+        assert!(error(&[("SMS_GATEWAY_URL", "https://sms.example.com")]).contains("SMS_GATEWAY_API_KEY"));
+
+        let smtp = [
+            ("SMTP_HOST", "smtp.example.com"),
+            ("SMTP_USERNAME", "fodi"),
+            ("SMTP_PASSWORD", "secret"),
+            ("SMTP_FROM", "not an address"),
+        ];
+        assert!(error(&smtp).contains("SMTP_FROM"));
+        assert!(error(&[&smtp[..3], &[("SMTP_FROM", "orders@example.com"), ("SMTP_PORT", "smtp")]].concat()).contains("SMTP_PORT"));
+    }
+}
@@ -1,93 +1,197 @@
-use sqlx::{PgPool, postgres::PgPoolOptions};
-use anyhow::Result;
+use sqlx::{Executor, PgPool, postgres::PgPoolOptions};
+use sqlx::migrate::{Migrate, MigrateError, Migrator};
+use anyhow::{anyhow, bail, Context, Result};
+use sha2::{Digest, Sha256};
 
-pub async fn create_pool(database_url: &str) -> Result<PgPool> {
+// Схема БД: файлы backend/migrations встраиваются при сборке,
+// применённые версии и их checksum хранятся в `_sqlx_migrations`
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+// Тестовые данные для разработки, отдельно от миграций (история в `_seed_history`)
+const SEEDS: &[(&str, &str)] = &[("seed_data", include_str!("../seeds/seed_data.sql"))];
+
+pub async fn create_pool(database_url: &str, max_connections: u32) -> Result<PgPool> {
     let pool = PgPoolOptions::new()
-        .max_connections(5)
+        .max_connections(max_connections)
         .connect(database_url)
         .await?;
-    
+
     Ok(pool)
 }
 
-pub async fn run_migrations(pool: &PgPool) -> Result<()> {
-    // Спрощена таблиця: тільки одне портфоліо без users
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS portfolio (
-            id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
-            slug VARCHAR(255) NOT NULL UNIQUE,
-            theme VARCHAR(50) NOT NULL DEFAULT 'elegant',
-            data JSONB NOT NULL,
-            updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+/// Applies pending migrations. A database created before migrations were
+/// tracked has tables but no history: `baseline` (the last migration already
+/// applied by hand) records 1..=baseline as applied without running them.
+/// Fails on drift: an applied migration whose file changed or is missing.
+pub async fn run_migrations(pool: &PgPool, baseline: Option<i64>) -> Result<()> {
+    let tracked: bool = sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+        .fetch_one(pool)
+        .await?;
+
+    if !tracked {
+        let existing: bool = sqlx::query_scalar(
+            "SELECT to_regclass('portfolio') IS NOT NULL OR to_regclass('categories') IS NOT NULL",
         )
-        "#,
-    )
-    .execute(pool)
-    .await?;
+        .fetch_one(pool)
+        .await?;
+
+        match baseline {
+            Some(version) => record_baseline(pool, version).await?,
+            None if existing => bail!(
+                "Database has tables but no migration history. Set MIGRATIONS_BASELINE to the last \
+                 migration already applied to it (e.g. 20) to record migrations up to it as applied"
+            ),
+            None => {}
+        }
+    }
 
-    // Создание таблицы blog_posts
+    MIGRATOR.run(pool).await.map_err(describe_migrate_error)?;
+
+    let version: Option<i64> = sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations")
+        .fetch_one(pool)
+        .await?;
+    tracing::info!("🗄️  Database schema at migration {}", version.unwrap_or_default());
+
+    Ok(())
+}
+
+async fn record_baseline(pool: &PgPool, baseline: i64) -> Result<()> {
+    if !MIGRATOR.version_exists(baseline) {
+        bail!("MIGRATIONS_BASELINE={} does not match any migration in backend/migrations", baseline);
+    }
+
+    let mut tx = pool.begin().await?;
+    tx.ensure_migrations_table().await?;
+
+    for migration in MIGRATOR.iter().filter(|m| m.version <= baseline) {
+        sqlx::query(
+            r#"
+            INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time)
+            VALUES ($1, $2, TRUE, $3, -1)
+            "#,
+        )
+        .bind(migration.version)
+        .bind(&*migration.description)
+        .bind(&*migration.checksum)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    tracing::info!("🗄️  Recorded migrations up to {} as applied (baseline)", baseline);
+    Ok(())
+}
+
+/// Versions recorded in `_sqlx_migrations`; empty before the first run
+pub async fn applied_migrations(pool: &PgPool) -> Result<Vec<i64>> {
+    let tracked: bool = sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+        .fetch_one(pool)
+        .await?;
+    if !tracked {
+        return Ok(Vec::new());
+    }
+
+    let versions = sqlx::query_scalar("SELECT version FROM _sqlx_migrations ORDER BY version")
+        .fetch_all(pool)
+        .await?;
+    Ok(versions)
+}
+
+/// Reverts applied migrations newer than `target` with their `.down.sql`
+/// scripts. Refuses up front if any of them has none, since sqlx would skip
+/// it and leave the schema half rolled back.
+pub async fn rollback_migrations(pool: &PgPool, target: i64) -> Result<()> {
+    if target != 0 && !MIGRATOR.version_exists(target) {
+        bail!("Migration {} does not exist in backend/migrations", target);
+    }
+
+    let irreversible: Vec<i64> = applied_migrations(pool)
+        .await?
+        .into_iter()
+        .filter(|version| *version > target)
+        .filter(|version| !has_down_script(*version))
+        .collect();
+    if let Some(oldest) = irreversible.iter().max() {
+        bail!(
+            "Migrations {:?} have no down script; the oldest possible target is {}",
+            irreversible,
+            oldest
+        );
+    }
+
+    MIGRATOR.undo(pool, target).await.map_err(describe_migrate_error)?;
+    tracing::info!("🗄️  Database schema rolled back to migration {}", target);
+
+    Ok(())
+}
+
+pub fn has_down_script(version: i64) -> bool {
+    MIGRATOR
+        .iter()
+        .any(|m| m.version == version && m.migration_type.is_down_migration())
+}
+
+fn describe_migrate_error(e: MigrateError) -> anyhow::Error {
+    match e {
+        MigrateError::VersionMismatch(version) => anyhow!(
+            "Schema drift: migration {} was changed after it was applied (checksum differs from \
+             _sqlx_migrations). Restore the original file and put the change in a new migration",
+            version
+        ),
+        MigrateError::VersionMissing(version) => anyhow!(
+            "Schema drift: migration {} is applied to the database but missing from backend/migrations \
+             (database is newer than this build, or the file was renamed)",
+            version
+        ),
+        other => anyhow::Error::new(other).context("Failed to run migrations"),
+    }
+}
+
+/// Runs the dev seed files not yet applied to this database (or changed
+/// since). `force` runs them again regardless. Seeds only add demo rows that
+/// are missing, but are still never run in production unless asked to.
+pub async fn run_seeds(pool: &PgPool, force: bool) -> Result<()> {
     sqlx::query(
         r#"
-        CREATE TABLE IF NOT EXISTS blog_posts (
-            id VARCHAR(36) PRIMARY KEY,
-            slug VARCHAR(255) UNIQUE NOT NULL,
-            title VARCHAR(500) NOT NULL,
-            excerpt TEXT NOT NULL,
-            content TEXT NOT NULL,
-            date DATE NOT NULL,
-            author VARCHAR(255) NOT NULL,
-            image VARCHAR(1000) NOT NULL,
-            category VARCHAR(100) NOT NULL,
-            read_time VARCHAR(50) NOT NULL,
-            created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
-            updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+        CREATE TABLE IF NOT EXISTS _seed_history (
+            name TEXT PRIMARY KEY,
+            checksum TEXT NOT NULL,
+            applied_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
         )
         "#,
     )
     .execute(pool)
     .await?;
 
-    // Создание индексов для blog_posts
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_blog_posts_slug ON blog_posts(slug)")
-        .execute(pool)
-        .await?;
-    
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_blog_posts_date ON blog_posts(date DESC)")
-        .execute(pool)
-        .await?;
+    for (name, sql) in SEEDS {
+        let checksum = hex::encode(Sha256::digest(sql.as_bytes()));
+        let applied: Option<String> = sqlx::query_scalar("SELECT checksum FROM _seed_history WHERE name = $1")
+            .bind(name)
+            .fetch_optional(pool)
+            .await?;
 
-    // Перевірка: якщо портфоліо не існує, створюємо дефолтне
-    let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM portfolio")
-        .fetch_one(pool)
-        .await?;
+        if !force && applied.as_deref() == Some(checksum.as_str()) {
+            continue;
+        }
 
-    if count.0 == 0 {
-        // Створюємо дефолтне портфоліо
+        let mut tx = pool.begin().await?;
+        // Without arguments the whole file goes as one simple query
+        tx.execute(*sql)
+            .await
+            .with_context(|| format!("Seed {} failed", name))?;
         sqlx::query(
             r#"
-            INSERT INTO portfolio (slug, theme, data)
-            VALUES ($1, $2, $3)
+            INSERT INTO _seed_history (name, checksum) VALUES ($1, $2)
+            ON CONFLICT (name) DO UPDATE SET checksum = EXCLUDED.checksum, applied_at = NOW()
             "#,
         )
-        .bind("my-portfolio")
-        .bind("elegant")
-        .bind(serde_json::json!({
-            "name": "Your Name",
-            "title": "Your Title",
-            "bio": "Your bio here",
-            "contacts": {
-                "email": "your@email.com",
-                "phone": "+1234567890"
-            },
-            "experience": [],
-            "education": [],
-            "skills": []
-        }))
-        .execute(pool)
+        .bind(name)
+        .bind(&checksum)
+        .execute(&mut *tx)
         .await?;
-        
-        println!("✅ Created default portfolio with slug 'my-portfolio'");
+        tx.commit().await?;
+
+        tracing::info!("🌱 Seed {} applied", name);
     }
 
     Ok(())
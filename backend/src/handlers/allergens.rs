@@ -0,0 +1,22 @@
+use axum::{extract::State, response::Json};
+use sqlx::PgPool;
+
+use crate::error::AppError;
+use crate::models::allergen::Allergen;
+
+// EU allergens with localized names and icons (Public)
+pub async fn get_allergens(
+    State(pool): State<PgPool>,
+) -> Result<Json<Vec<Allergen>>, AppError> {
+    let allergens = sqlx::query_as::<_, Allergen>(
+        r#"
+        SELECT code, name, name_ru, name_pl, icon, sort_order
+        FROM allergens
+        ORDER BY sort_order ASC
+        "#,
+    )
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(Json(allergens))
+}
@@ -0,0 +1,74 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use std::sync::Arc;
+
+use crate::error::AppError;
+use crate::listing::{ListParams, Page};
+use crate::models_single::{BlogPost, BlogPostFilters, CreateBlogPost, UpdateBlogPost};
+use crate::services::blog_service::BlogService;
+
+// Published posts, newest first (?category=&page=&per_page=)
+pub async fn get_posts(
+    State(blog): State<Arc<BlogService>>,
+    Query(filters): Query<BlogPostFilters>,
+    Query(params): Query<ListParams>,
+) -> Result<Page<BlogPost>, AppError> {
+    // Status is not a public filter: drafts are never listed here
+    let filters = BlogPostFilters { status: None, ..filters };
+    blog.list_published(filters, &params).await
+}
+
+// Published post by slug
+pub async fn get_post(
+    State(blog): State<Arc<BlogService>>,
+    Path(slug): Path<String>,
+) -> Result<Json<BlogPost>, AppError> {
+    Ok(Json(blog.get_published(&slug).await?))
+}
+
+// All posts including drafts and scheduled ones (?status=&category=) - Admin only
+pub async fn get_all_posts(
+    State(blog): State<Arc<BlogService>>,
+    Query(filters): Query<BlogPostFilters>,
+    Query(params): Query<ListParams>,
+) -> Result<Page<BlogPost>, AppError> {
+    blog.list_all(filters, &params).await
+}
+
+// Post by ID - Admin only
+pub async fn get_post_by_id(
+    State(blog): State<Arc<BlogService>>,
+    Path(id): Path<String>,
+) -> Result<Json<BlogPost>, AppError> {
+    Ok(Json(blog.get(&id).await?))
+}
+
+// Create post - Admin only
+pub async fn create_post(
+    State(blog): State<Arc<BlogService>>,
+    Json(payload): Json<CreateBlogPost>,
+) -> Result<Response, AppError> {
+    let post = blog.create(payload).await?;
+    Ok((StatusCode::CREATED, Json(post)).into_response())
+}
+
+// Update post - Admin only
+pub async fn update_post(
+    State(blog): State<Arc<BlogService>>,
+    Path(id): Path<String>,
+    Json(payload): Json<UpdateBlogPost>,
+) -> Result<Json<BlogPost>, AppError> {
+    Ok(Json(blog.update(&id, payload).await?))
+}
+
+// Delete post - Admin only
+pub async fn delete_post(
+    State(blog): State<Arc<BlogService>>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    blog.delete(&id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
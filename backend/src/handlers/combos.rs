@@ -0,0 +1,36 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use std::sync::Arc;
+
+use crate::error::AppError;
+use crate::models::combo::{Combo, SaveCombo};
+use crate::services::combo_service::ComboService;
+
+// Combo slots with the items that can be chosen (Public)
+pub async fn get_combo(
+    State(combos): State<Arc<ComboService>>,
+    Path(id): Path<i32>,
+) -> Result<Json<Combo>, AppError> {
+    Ok(Json(combos.get(id).await?))
+}
+
+// Define or replace combo slots of a menu item - Admin only
+pub async fn set_combo(
+    State(combos): State<Arc<ComboService>>,
+    Path(id): Path<i32>,
+    Json(input): Json<SaveCombo>,
+) -> Result<Json<Combo>, AppError> {
+    Ok(Json(combos.set(id, input).await?))
+}
+
+// Turn a combo back into a regular menu item - Admin only
+pub async fn delete_combo(
+    State(combos): State<Arc<ComboService>>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, AppError> {
+    combos.delete(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
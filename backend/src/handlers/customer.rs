@@ -0,0 +1,109 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+    Extension,
+};
+use std::sync::Arc;
+
+use crate::error::AppError;
+use crate::models::customer::{
+    Customer, CustomerAddress, CustomerAuthResponse, CustomerClaims, CustomerLogin,
+    CreateCustomerAddress, RegisterCustomer, RequestOtp, UpdateCustomerAddress, VerifyOtp,
+};
+use crate::models::restaurant::OrderWithItems;
+use crate::services::customer_service::CustomerService;
+
+// Register with email + password (Public)
+pub async fn register(
+    State(customers): State<Arc<CustomerService>>,
+    Json(req): Json<RegisterCustomer>,
+) -> Result<Response, AppError> {
+    let response = customers.register(req).await?;
+    Ok((StatusCode::CREATED, Json(response)).into_response())
+}
+
+// Login with email + password (Public)
+pub async fn login(
+    State(customers): State<Arc<CustomerService>>,
+    Json(req): Json<CustomerLogin>,
+) -> Result<Json<CustomerAuthResponse>, AppError> {
+    let response = customers.login(req).await?;
+    Ok(Json(response))
+}
+
+// Send a one-time login code by SMS (Public)
+pub async fn request_otp(
+    State(customers): State<Arc<CustomerService>>,
+    Json(req): Json<RequestOtp>,
+) -> Result<StatusCode, AppError> {
+    customers.request_otp(&req.phone).await?;
+    Ok(StatusCode::ACCEPTED)
+}
+
+// Login or sign up with a one-time code (Public)
+pub async fn verify_otp(
+    State(customers): State<Arc<CustomerService>>,
+    Json(req): Json<VerifyOtp>,
+) -> Result<Json<CustomerAuthResponse>, AppError> {
+    let response = customers.verify_otp(req).await?;
+    Ok(Json(response))
+}
+
+// Current customer profile - Customer only
+pub async fn me(
+    State(customers): State<Arc<CustomerService>>,
+    Extension(claims): Extension<CustomerClaims>,
+) -> Result<Json<Customer>, AppError> {
+    let customer = customers.profile(claims.sub).await?;
+    Ok(Json(customer))
+}
+
+// Order history - Customer only
+pub async fn get_orders(
+    State(customers): State<Arc<CustomerService>>,
+    Extension(claims): Extension<CustomerClaims>,
+) -> Result<Json<Vec<OrderWithItems>>, AppError> {
+    let orders = customers.orders(claims.sub).await?;
+    Ok(Json(orders))
+}
+
+// Saved addresses - Customer only
+pub async fn get_addresses(
+    State(customers): State<Arc<CustomerService>>,
+    Extension(claims): Extension<CustomerClaims>,
+) -> Result<Json<Vec<CustomerAddress>>, AppError> {
+    let addresses = customers.addresses(claims.sub).await?;
+    Ok(Json(addresses))
+}
+
+// Create address - Customer only
+pub async fn create_address(
+    State(customers): State<Arc<CustomerService>>,
+    Extension(claims): Extension<CustomerClaims>,
+    Json(address_data): Json<CreateCustomerAddress>,
+) -> Result<Response, AppError> {
+    let address = customers.create_address(claims.sub, address_data).await?;
+    Ok((StatusCode::CREATED, Json(address)).into_response())
+}
+
+// Update address - Customer only
+pub async fn update_address(
+    State(customers): State<Arc<CustomerService>>,
+    Extension(claims): Extension<CustomerClaims>,
+    Path(id): Path<i32>,
+    Json(address_data): Json<UpdateCustomerAddress>,
+) -> Result<Json<CustomerAddress>, AppError> {
+    let address = customers.update_address(claims.sub, id, address_data).await?;
+    Ok(Json(address))
+}
+
+// Delete address - Customer only
+pub async fn delete_address(
+    State(customers): State<Arc<CustomerService>>,
+    Extension(claims): Extension<CustomerClaims>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, AppError> {
+    customers.delete_address(claims.sub, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,39 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use std::sync::Arc;
+
+use crate::error::AppError;
+use crate::services::job_queue::{Job, JobFilters, JobQueue, JobStats};
+
+// List recent jobs (?status=dead&kind=notification.deliver&limit=50) - Admin only
+pub async fn get_jobs(
+    State(jobs): State<Arc<JobQueue>>,
+    Query(filters): Query<JobFilters>,
+) -> Result<Json<Vec<Job>>, AppError> {
+    Ok(Json(jobs.list(filters).await?))
+}
+
+// Job counts by kind and status - Admin only
+pub async fn get_job_stats(
+    State(jobs): State<Arc<JobQueue>>,
+) -> Result<Json<Vec<JobStats>>, AppError> {
+    Ok(Json(jobs.stats().await?))
+}
+
+// Get job by ID - Admin only
+pub async fn get_job(
+    State(jobs): State<Arc<JobQueue>>,
+    Path(id): Path<i64>,
+) -> Result<Json<Job>, AppError> {
+    Ok(Json(jobs.get(id).await?))
+}
+
+// Requeue a dead job - Admin only
+pub async fn retry_job(
+    State(jobs): State<Arc<JobQueue>>,
+    Path(id): Path<i64>,
+) -> Result<Json<Job>, AppError> {
+    Ok(Json(jobs.retry(id).await?))
+}
@@ -0,0 +1,37 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use std::sync::Arc;
+
+use crate::error::AppError;
+use crate::listing::{ListParams, Page};
+use crate::models::media::{MediaAsset, MediaAssetDetails, MediaFilters};
+use crate::services::media_service::MediaService;
+
+// Uploaded images with usage counts (?unused=true&folder=menu&sort=-created_at&page=1) - Admin only
+pub async fn get_media(
+    State(media): State<Arc<MediaService>>,
+    Query(filters): Query<MediaFilters>,
+    Query(params): Query<ListParams>,
+) -> Result<Page<MediaAsset>, AppError> {
+    media.list(filters, &params).await
+}
+
+// Image with the menu items, categories and restaurant fields using it - Admin only
+pub async fn get_media_asset(
+    State(media): State<Arc<MediaService>>,
+    Path(id): Path<i32>,
+) -> Result<Json<MediaAssetDetails>, AppError> {
+    Ok(Json(media.get(id).await?))
+}
+
+// Delete an unused image with its derivatives - Admin only
+pub async fn delete_media_asset(
+    State(media): State<Arc<MediaService>>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, AppError> {
+    media.delete(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,30 @@
+use axum::{
+    extract::{Query, State},
+    response::Json,
+    Extension,
+};
+use std::sync::Arc;
+
+use crate::error::AppError;
+use crate::listing::{ListParams, Page};
+use crate::models::menu_bulk::{BulkMenuOptions, BulkMenuReport, BulkMenuRequest, MenuBulkOperation};
+use crate::models_single::Claims;
+use crate::services::menu_bulk_service::MenuBulkService;
+
+// Apply one operation to every menu item matching the filter (?dry_run=true) - Admin only
+pub async fn bulk_update(
+    State(bulk): State<Arc<MenuBulkService>>,
+    Extension(claims): Extension<Claims>,
+    Query(options): Query<BulkMenuOptions>,
+    Json(request): Json<BulkMenuRequest>,
+) -> Result<Json<BulkMenuReport>, AppError> {
+    Ok(Json(bulk.apply(request, options.dry_run, &claims.sub).await?))
+}
+
+// Audit log of bulk operations, newest first (?page=1&per_page=20) - Admin only
+pub async fn get_bulk_operations(
+    State(bulk): State<Arc<MenuBulkService>>,
+    Query(params): Query<ListParams>,
+) -> Result<Page<MenuBulkOperation>, AppError> {
+    bulk.history(&params).await
+}
@@ -0,0 +1,28 @@
+use axum::{
+    extract::{Path, State},
+    response::Json,
+    Extension,
+};
+use std::sync::Arc;
+
+use crate::error::AppError;
+use crate::models::menu_version::MenuItemVersion;
+use crate::models_single::Claims;
+use crate::services::menu_history_service::MenuHistoryService;
+
+// Price, name and availability history of a menu item, newest first - Admin only
+pub async fn get_versions(
+    State(history): State<Arc<MenuHistoryService>>,
+    Path(id): Path<i32>,
+) -> Result<Json<Vec<MenuItemVersion>>, AppError> {
+    Ok(Json(history.versions(id).await?))
+}
+
+// Restore a previous version (recorded as a new version) - Admin only
+pub async fn rollback(
+    State(history): State<Arc<MenuHistoryService>>,
+    Extension(claims): Extension<Claims>,
+    Path((id, version)): Path<(i32, i32)>,
+) -> Result<Json<MenuItemVersion>, AppError> {
+    Ok(Json(history.rollback(id, version, &claims.sub).await?))
+}
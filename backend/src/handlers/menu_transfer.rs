@@ -0,0 +1,69 @@
+use axum::{
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+    Extension,
+};
+use std::sync::Arc;
+
+use crate::error::AppError;
+use crate::models::menu_transfer::MenuTransferOptions;
+use crate::models_single::Claims;
+use crate::services::menu_transfer_service::{self, MenuTransferService};
+
+// Export the whole menu (?format=json|csv) - Admin only
+pub async fn export_menu(
+    State(menu): State<Arc<MenuTransferService>>,
+    Query(options): Query<MenuTransferOptions>,
+) -> Result<Response, AppError> {
+    let export = menu.export().await?;
+
+    match options.format.as_deref() {
+        None | Some("json") => Ok((
+            [(header::CONTENT_DISPOSITION, "attachment; filename=\"menu.json\"")],
+            Json(export),
+        )
+            .into_response()),
+        Some("csv") => Ok((
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+                (header::CONTENT_DISPOSITION, "attachment; filename=\"menu.csv\""),
+            ],
+            menu_transfer_service::export_csv(&export)?,
+        )
+            .into_response()),
+        Some(other) => Err(AppError::Validation(format!(
+            "Invalid format '{}'. Must be json or csv",
+            other
+        ))),
+    }
+}
+
+// Import (upsert) categories and menu items (?format=json|csv&dry_run=true) - Admin only
+// Responds 422 with the per-row report if any row is invalid; nothing is saved then
+pub async fn import_menu(
+    State(menu): State<Arc<MenuTransferService>>,
+    Extension(claims): Extension<Claims>,
+    Query(options): Query<MenuTransferOptions>,
+    body: String,
+) -> Result<Response, AppError> {
+    let parsed = match options.format.as_deref() {
+        None | Some("json") => menu_transfer_service::parse_json(&body)?,
+        Some("csv") => menu_transfer_service::parse_csv(&body)?,
+        Some(other) => {
+            return Err(AppError::Validation(format!(
+                "Invalid format '{}'. Must be json or csv",
+                other
+            )))
+        }
+    };
+
+    let report = menu.import(parsed, options.dry_run, &claims.sub).await?;
+    let status = if report.summary.errors > 0 {
+        StatusCode::UNPROCESSABLE_ENTITY
+    } else {
+        StatusCode::OK
+    };
+
+    Ok((status, Json(report)).into_response())
+}
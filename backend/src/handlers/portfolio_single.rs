@@ -0,0 +1,41 @@
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Json},
+};
+use std::sync::Arc;
+
+use crate::error::AppError;
+use crate::models_single::{Portfolio, UpdatePortfolioRequest};
+use crate::repositories::portfolio_repository_single::PortfolioRepository;
+use crate::services::portfolio_schema::{self, PORTFOLIO_SCHEMA};
+
+// Chef profile (hero, skills, experience)
+pub async fn get_portfolio(
+    State(portfolio): State<Arc<PortfolioRepository>>,
+) -> Result<Json<Portfolio>, AppError> {
+    let portfolio = portfolio.get().await?.ok_or(AppError::PortfolioNotFound)?;
+    Ok(Json(portfolio))
+}
+
+// JSON Schema the `data` of PUT /api/portfolio is checked against
+pub async fn get_portfolio_schema() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "application/schema+json")], PORTFOLIO_SCHEMA)
+}
+
+// Replace profile data and optionally switch the theme - Admin only
+pub async fn update_portfolio(
+    State(portfolio): State<Arc<PortfolioRepository>>,
+    Json(payload): Json<UpdatePortfolioRequest>,
+) -> Result<Json<Portfolio>, AppError> {
+    if let Some(theme) = &payload.theme {
+        portfolio_schema::validate_theme(theme)?;
+    }
+    portfolio_schema::validate_data(&payload.data)?;
+
+    // The profile is seeded by migration 004
+    if portfolio.get().await?.is_none() {
+        return Err(AppError::PortfolioNotFound);
+    }
+    Ok(Json(portfolio.update(payload).await?))
+}
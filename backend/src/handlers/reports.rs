@@ -0,0 +1,112 @@
+use axum::{
+    extract::{Query, State},
+    http::header,
+    response::{IntoResponse, Json, Response},
+};
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::error::AppError;
+use crate::services::report_service::{self, ReportFilters, ReportService};
+
+// JSON by default, `?format=csv` downloads the same rows as a file
+fn respond<T: Serialize>(rows: Vec<T>, filters: &ReportFilters, name: &str) -> Result<Response, AppError> {
+    match filters.format.as_deref() {
+        None | Some("json") => Ok(Json(rows).into_response()),
+        Some("csv") => {
+            let csv = report_service::to_csv(&rows)?;
+            let filename = match (filters.from, filters.to) {
+                (Some(from), Some(to)) => format!("{}_{}_{}.csv", name, from, to),
+                _ => format!("{}.csv", name),
+            };
+
+            Ok((
+                [
+                    (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                    (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename)),
+                ],
+                csv,
+            )
+                .into_response())
+        }
+        Some(other) => Err(AppError::Validation(format!(
+            "Invalid format '{}'. Must be json or csv",
+            other
+        ))),
+    }
+}
+
+// Totals, average order value and cancellation rate - Admin only
+pub async fn get_summary(
+    State(reports): State<Arc<ReportService>>,
+    Query(filters): Query<ReportFilters>,
+) -> Result<Response, AppError> {
+    let summary = reports.summary(&filters).await?;
+    match filters.format.as_deref() {
+        None | Some("json") => Ok(Json(summary).into_response()),
+        _ => respond(vec![summary], &filters, "summary"),
+    }
+}
+
+// Revenue by day/week/month (?period=week) - Admin only
+pub async fn get_revenue(
+    State(reports): State<Arc<ReportService>>,
+    Query(filters): Query<ReportFilters>,
+) -> Result<Response, AppError> {
+    let rows = reports.revenue(&filters).await?;
+    respond(rows, &filters, "revenue")
+}
+
+// Top-selling menu items (?limit=10) - Admin only
+pub async fn get_top_items(
+    State(reports): State<Arc<ReportService>>,
+    Query(filters): Query<ReportFilters>,
+) -> Result<Response, AppError> {
+    let rows = reports.top_items(&filters).await?;
+    respond(rows, &filters, "top_items")
+}
+
+// Top-selling categories - Admin only
+pub async fn get_top_categories(
+    State(reports): State<Arc<ReportService>>,
+    Query(filters): Query<ReportFilters>,
+) -> Result<Response, AppError> {
+    let rows = reports.top_categories(&filters).await?;
+    respond(rows, &filters, "top_categories")
+}
+
+// Orders by weekday and hour - Admin only
+pub async fn get_heatmap(
+    State(reports): State<Arc<ReportService>>,
+    Query(filters): Query<ReportFilters>,
+) -> Result<Response, AppError> {
+    let rows = reports.heatmap(&filters).await?;
+    respond(rows, &filters, "heatmap")
+}
+
+// Orders and revenue per payment method - Admin only
+pub async fn get_payment_methods(
+    State(reports): State<Arc<ReportService>>,
+    Query(filters): Query<ReportFilters>,
+) -> Result<Response, AppError> {
+    let rows = reports.payment_methods(&filters).await?;
+    respond(rows, &filters, "payment_methods")
+}
+
+// Menu as it was at the end of ?to= (prices, names, availability) - Admin only
+pub async fn get_menu_snapshot(
+    State(reports): State<Arc<ReportService>>,
+    Query(filters): Query<ReportFilters>,
+) -> Result<Response, AppError> {
+    let rows = reports.menu_snapshot(&filters).await?;
+    respond(rows, &filters, "menu")
+}
+
+// Summaries of closed days stored by the report.daily job - Admin only
+pub async fn get_daily(
+    State(reports): State<Arc<ReportService>>,
+    Query(filters): Query<ReportFilters>,
+) -> Result<Response, AppError> {
+    let rows = reports.daily(&filters).await?;
+    respond(rows, &filters, "daily")
+}
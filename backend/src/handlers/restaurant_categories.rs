@@ -1,69 +1,101 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::{IntoResponse, Json, Response},
+    Extension,
 };
+use serde::Deserialize;
 use sqlx::PgPool;
 
 use crate::error::AppError;
+use crate::listing::{ListParams, ListQuery, Page, SortField, Sql};
+use crate::locale::ContentLocale;
 use crate::models::restaurant::{Category, CreateCategory, UpdateCategory};
+use crate::models_single::Claims;
+use crate::services::{menu_history_service, translation_service, webhook_service};
 
-// Get all categories
+const CATEGORY_COLUMNS: &str = r#"id, name, name_ru, name_pl, slug, description, image,
+    "order", is_active, created_at, updated_at, deleted_at"#;
+
+const CATEGORY_SORT_FIELDS: &[SortField] = &[
+    ("id", "id"),
+    ("order", "\"order\""),
+    ("name", "name"),
+    ("slug", "slug"),
+    ("created_at", "created_at"),
+    ("updated_at", "updated_at"),
+];
+
+#[derive(Debug, Deserialize)]
+pub struct CategoryFilters {
+    // Admin listing only; the public list shows active categories
+    pub is_active: Option<bool>,
+    // Admin listing only; true lists deleted categories instead
+    pub deleted: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteCategoryParams {
+    // Moves the category's menu items here instead of refusing the delete
+    pub move_items_to: Option<i32>,
+}
+
+// Get all categories (?sort=name&page=1&per_page=20)
 pub async fn get_categories(
     State(pool): State<PgPool>,
-) -> Result<Json<Vec<Category>>, AppError> {
-    let categories = sqlx::query_as!(
-        Category,
-        r#"
-        SELECT id, name, name_ru, name_pl, slug, description, image, 
-               "order", is_active, created_at, updated_at
-        FROM categories
-        WHERE is_active = true
-        ORDER BY "order" ASC, id ASC
-        "#
-    )
-    .fetch_all(&pool)
-    .await?;
+    locale: ContentLocale,
+    Query(params): Query<ListParams>,
+) -> Result<Page<Category>, AppError> {
+    let mut list = ListQuery::new(CATEGORY_COLUMNS, "categories");
+    list.filter(Sql::new("is_active = true AND deleted_at IS NULL"));
+    list.sort(&params, CATEGORY_SORT_FIELDS, r#""order" ASC, id ASC"#)?.paginate(&params)?;
+
+    let mut page = list.fetch_page::<Category>(&pool).await?;
+
+    translation_service::localize(&pool, &mut page.items, &locale).await?;
 
-    Ok(Json(categories))
+    Ok(page)
 }
 
-// Get all categories (including inactive) - Admin only
+// Get all categories (including inactive, ?is_active=false; deleted ones with ?deleted=true) - Admin only
 pub async fn get_all_categories(
     State(pool): State<PgPool>,
-) -> Result<Json<Vec<Category>>, AppError> {
-    let categories = sqlx::query_as!(
-        Category,
-        r#"
-        SELECT id, name, name_ru, name_pl, slug, description, image, 
-               "order", is_active, created_at, updated_at
-        FROM categories
-        ORDER BY "order" ASC, id ASC
-        "#
-    )
-    .fetch_all(&pool)
-    .await?;
+    Query(filters): Query<CategoryFilters>,
+    Query(params): Query<ListParams>,
+) -> Result<Page<Category>, AppError> {
+    let mut list = ListQuery::new(CATEGORY_COLUMNS, "categories");
+    list.filter(Sql::new(if filters.deleted.unwrap_or(false) {
+        "deleted_at IS NOT NULL"
+    } else {
+        "deleted_at IS NULL"
+    }));
+    list.filter_opt("is_active = ", filters.is_active);
+    list.sort(&params, CATEGORY_SORT_FIELDS, r#""order" ASC, id ASC"#)?.paginate(&params)?;
 
-    Ok(Json(categories))
+    list.fetch_page::<Category>(&pool).await
 }
 
 // Get category by ID
 pub async fn get_category(
     State(pool): State<PgPool>,
+    locale: ContentLocale,
     Path(id): Path<i32>,
 ) -> Result<Json<Category>, AppError> {
-    let category = sqlx::query_as!(
+    let mut category = sqlx::query_as!(
         Category,
         r#"
         SELECT id, name, name_ru, name_pl, slug, description, image, 
-               "order", is_active, created_at, updated_at
+               "order", is_active, created_at, updated_at, deleted_at
         FROM categories
-        WHERE id = $1
+        WHERE id = $1 AND deleted_at IS NULL
         "#,
         id
     )
-    .fetch_one(&pool)
-    .await?;
+    .fetch_optional(&pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Category with ID {} not found", id)))?;
+
+    translation_service::localize(&pool, std::slice::from_mut(&mut category), &locale).await?;
 
     Ok(Json(category))
 }
@@ -71,20 +103,24 @@ pub async fn get_category(
 // Get category by slug
 pub async fn get_category_by_slug(
     State(pool): State<PgPool>,
+    locale: ContentLocale,
     Path(slug): Path<String>,
 ) -> Result<Json<Category>, AppError> {
-    let category = sqlx::query_as!(
+    let mut category = sqlx::query_as!(
         Category,
         r#"
         SELECT id, name, name_ru, name_pl, slug, description, image, 
-               "order", is_active, created_at, updated_at
+               "order", is_active, created_at, updated_at, deleted_at
         FROM categories
-        WHERE slug = $1
+        WHERE slug = $1 AND deleted_at IS NULL
         "#,
         slug
     )
-    .fetch_one(&pool)
-    .await?;
+    .fetch_optional(&pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Category '{}' not found", slug)))?;
+
+    translation_service::localize(&pool, std::slice::from_mut(&mut category), &locale).await?;
 
     Ok(Json(category))
 }
@@ -94,13 +130,15 @@ pub async fn create_category(
     State(pool): State<PgPool>,
     Json(category_data): Json<CreateCategory>,
 ) -> Result<Response, AppError> {
+    let mut tx = pool.begin().await?;
+
     let category = sqlx::query_as!(
         Category,
         r#"
         INSERT INTO categories (name, name_ru, name_pl, slug, description, image, "order")
         VALUES ($1, $2, $3, $4, $5, $6, $7)
         RETURNING id, name, name_ru, name_pl, slug, description, image, 
-                  "order", is_active, created_at, updated_at
+                  "order", is_active, created_at, updated_at, deleted_at
         "#,
         category_data.name,
         category_data.name_ru,
@@ -110,9 +148,20 @@ pub async fn create_category(
         category_data.image,
         category_data.order.unwrap_or(0)
     )
-    .fetch_one(&pool)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    webhook_service::dispatch_menu_change(
+        &mut tx,
+        "category",
+        "created",
+        category.id,
+        Some(serde_json::json!(&category)),
+    )
     .await?;
 
+    tx.commit().await?;
+
     Ok((StatusCode::CREATED, Json(category)).into_response())
 }
 
@@ -122,18 +171,20 @@ pub async fn update_category(
     Path(id): Path<i32>,
     Json(category_data): Json<UpdateCategory>,
 ) -> Result<Json<Category>, AppError> {
+    let mut tx = pool.begin().await?;
+
     // Get current category
     let mut category = sqlx::query_as!(
         Category,
         r#"
         SELECT id, name, name_ru, name_pl, slug, description, image, 
-               "order", is_active, created_at, updated_at
+               "order", is_active, created_at, updated_at, deleted_at
         FROM categories
         WHERE id = $1
         "#,
         id
     )
-    .fetch_one(&pool)
+    .fetch_one(&mut *tx)
     .await?;
 
     // Update fields if provided
@@ -171,7 +222,7 @@ pub async fn update_category(
             description = $6, image = $7, "order" = $8, is_active = $9
         WHERE id = $1
         RETURNING id, name, name_ru, name_pl, slug, description, image, 
-                  "order", is_active, created_at, updated_at
+                  "order", is_active, created_at, updated_at, deleted_at
         "#,
         id,
         category.name,
@@ -183,26 +234,132 @@ pub async fn update_category(
         category.order,
         category.is_active
     )
-    .fetch_one(&pool)
+    .fetch_one(&mut *tx)
     .await?;
 
+    webhook_service::dispatch_menu_change(
+        &mut tx,
+        "category",
+        "updated",
+        id,
+        Some(serde_json::json!(&updated_category)),
+    )
+    .await?;
+
+    tx.commit().await?;
+
     Ok(Json(updated_category))
 }
 
-// Delete category - Admin only
+// Delete category (soft; ?move_items_to=ID re-parents its menu items) - Admin only.
+// A category that still has menu items is not deleted without move_items_to.
 pub async fn delete_category(
     State(pool): State<PgPool>,
+    Extension(claims): Extension<Claims>,
     Path(id): Path<i32>,
+    Query(params): Query<DeleteCategoryParams>,
 ) -> Result<StatusCode, AppError> {
-    sqlx::query!(
-        r#"
-        DELETE FROM categories
-        WHERE id = $1
-        "#,
-        id
+    let mut tx = pool.begin().await?;
+    menu_history_service::set_actor(&mut tx, &claims.sub, None).await?;
+
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM categories WHERE id = $1 AND deleted_at IS NULL FOR UPDATE)",
+    )
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+    if !exists {
+        return Ok(StatusCode::NO_CONTENT);
+    }
+
+    let item_ids: Vec<i32> = sqlx::query_scalar(
+        "SELECT id FROM menu_items WHERE category_id = $1 AND deleted_at IS NULL ORDER BY id",
     )
-    .execute(&pool)
+    .bind(id)
+    .fetch_all(&mut *tx)
     .await?;
 
+    if !item_ids.is_empty() {
+        let target = params.move_items_to.ok_or_else(|| {
+            AppError::Validation(format!(
+                "Category {} still has {} menu items; delete them first or pass ?move_items_to=<category_id>",
+                id,
+                item_ids.len()
+            ))
+        })?;
+
+        let target_exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM categories WHERE id = $1 AND id <> $2 AND deleted_at IS NULL)",
+        )
+        .bind(target)
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if !target_exists {
+            return Err(AppError::Validation(format!(
+                "move_items_to: category with ID {} not found",
+                target
+            )));
+        }
+
+        sqlx::query("UPDATE menu_items SET category_id = $2 WHERE id = ANY($1)")
+            .bind(&item_ids)
+            .bind(target)
+            .execute(&mut *tx)
+            .await?;
+
+        for item_id in &item_ids {
+            webhook_service::dispatch_menu_change(
+                &mut tx,
+                "menu_item",
+                "updated",
+                *item_id,
+                Some(serde_json::json!({ "category_id": target })),
+            )
+            .await?;
+        }
+    }
+
+    sqlx::query("UPDATE categories SET deleted_at = NOW() WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+    webhook_service::dispatch_menu_change(&mut tx, "category", "deleted", id, None).await?;
+
+    tx.commit().await?;
+
     Ok(StatusCode::NO_CONTENT)
 }
+
+// Restore a deleted category - Admin only
+pub async fn restore_category(
+    State(pool): State<PgPool>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i32>,
+) -> Result<Json<Category>, AppError> {
+    let mut tx = pool.begin().await?;
+    menu_history_service::set_actor(&mut tx, &claims.sub, None).await?;
+
+    let category = sqlx::query_as::<_, Category>(&format!(
+        "UPDATE categories SET deleted_at = NULL WHERE id = $1 AND deleted_at IS NOT NULL RETURNING {}",
+        CATEGORY_COLUMNS
+    ))
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Deleted category with ID {} not found", id)))?;
+
+    webhook_service::dispatch_menu_change(
+        &mut tx,
+        "category",
+        "restored",
+        id,
+        Some(serde_json::json!(&category)),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(category))
+}
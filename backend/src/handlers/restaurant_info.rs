@@ -6,28 +6,18 @@ use axum::{
 use sqlx::PgPool;
 
 use crate::error::AppError;
+use crate::locale::ContentLocale;
 use crate::models::restaurant::{RestaurantInfo, UpdateRestaurantInfo};
+use crate::services::{restaurant_config_service, translation_service};
 
 // Get restaurant info (Public)
 pub async fn get_restaurant_info(
     State(pool): State<PgPool>,
+    locale: ContentLocale,
 ) -> Result<Json<RestaurantInfo>, AppError> {
-    let info = sqlx::query_as!(
-        RestaurantInfo,
-        r#"
-        SELECT id, name, name_ru, name_pl, description, description_ru, description_pl,
-               logo, phone, email, address, city, postal_code, opening_hours,
-               delivery_radius, minimum_order, delivery_fee, free_delivery_from,
-               average_delivery_time, social_media,
-               hero_image, hero_title, hero_subtitle, hero_description,
-               featured_dish_image, featured_dish_title, featured_dish_description,
-               featured_dish_price, updated_at
-        FROM restaurant_info
-        WHERE id = 1
-        "#
-    )
-    .fetch_one(&pool)
-    .await?;
+    let mut info = restaurant_config_service::info(&pool).await?;
+
+    translation_service::localize(&pool, std::slice::from_mut(&mut info), &locale).await?;
 
     Ok(Json(info))
 }
@@ -38,22 +28,7 @@ pub async fn update_restaurant_info(
     Json(info_data): Json<UpdateRestaurantInfo>,
 ) -> Result<Json<RestaurantInfo>, AppError> {
     // Get current info
-    let mut info = sqlx::query_as!(
-        RestaurantInfo,
-        r#"
-        SELECT id, name, name_ru, name_pl, description, description_ru, description_pl,
-               logo, phone, email, address, city, postal_code, opening_hours,
-               delivery_radius, minimum_order, delivery_fee, free_delivery_from,
-               average_delivery_time, social_media,
-               hero_image, hero_title, hero_subtitle, hero_description,
-               featured_dish_image, featured_dish_title, featured_dish_description,
-               featured_dish_price, updated_at
-        FROM restaurant_info
-        WHERE id = 1
-        "#
-    )
-    .fetch_one(&pool)
-    .await?;
+    let mut info = restaurant_config_service::info(&pool).await?;
 
     // Update fields if provided
     if let Some(name) = info_data.name {
@@ -141,58 +116,7 @@ pub async fn update_restaurant_info(
     }
 
     // Save to database
-    let updated_info = sqlx::query_as!(
-        RestaurantInfo,
-        r#"
-        UPDATE restaurant_info
-        SET name = $1, name_ru = $2, name_pl = $3,
-            description = $4, description_ru = $5, description_pl = $6,
-            logo = $7, phone = $8, email = $9, address = $10, city = $11, postal_code = $12,
-            opening_hours = $13, delivery_radius = $14, minimum_order = $15,
-            delivery_fee = $16, free_delivery_from = $17, average_delivery_time = $18,
-            social_media = $19,
-            hero_image = $20, hero_title = $21, hero_subtitle = $22, hero_description = $23,
-            featured_dish_image = $24, featured_dish_title = $25,
-            featured_dish_description = $26, featured_dish_price = $27
-        WHERE id = 1
-        RETURNING id, name, name_ru, name_pl, description, description_ru, description_pl,
-                  logo, phone, email, address, city, postal_code, opening_hours,
-                  delivery_radius, minimum_order, delivery_fee, free_delivery_from,
-                  average_delivery_time, social_media,
-                  hero_image, hero_title, hero_subtitle, hero_description,
-                  featured_dish_image, featured_dish_title, featured_dish_description,
-                  featured_dish_price, updated_at
-        "#,
-        info.name,
-        info.name_ru,
-        info.name_pl,
-        info.description,
-        info.description_ru,
-        info.description_pl,
-        info.logo,
-        info.phone,
-        info.email,
-        info.address,
-        info.city,
-        info.postal_code,
-        info.opening_hours,
-        info.delivery_radius,
-        info.minimum_order,
-        info.delivery_fee,
-        info.free_delivery_from,
-        info.average_delivery_time,
-        info.social_media,
-        info.hero_image,
-        info.hero_title,
-        info.hero_subtitle,
-        info.hero_description,
-        info.featured_dish_image,
-        info.featured_dish_title,
-        info.featured_dish_description,
-        info.featured_dish_price
-    )
-    .fetch_one(&pool)
-    .await?;
+    let updated_info = restaurant_config_service::save_info(&pool, &info).await?;
 
     Ok(Json(updated_info))
 }
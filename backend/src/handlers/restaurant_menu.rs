@@ -2,12 +2,41 @@ use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
     response::{IntoResponse, Json, Response},
+    Extension,
 };
+use bigdecimal::BigDecimal;
 use serde::Deserialize;
 use sqlx::PgPool;
 
 use crate::error::AppError;
+use crate::listing::{split_list, ListParams, ListQuery, Page, SortField, Sql};
+use crate::locale::ContentLocale;
+use crate::models::allergen::{normalize_allergens, DietaryInfo};
 use crate::models::restaurant::{MenuItem, CreateMenuItem, UpdateMenuItem};
+use crate::models_single::Claims;
+use crate::services::schedule_service::menu_items_now;
+use crate::services::{menu_history_service, translation_service, webhook_service};
+
+const MENU_ITEM_COLUMNS: &str = r#"id, category_id, name, name_ru, name_pl,
+    description, description_ru, description_pl,
+    price, original_price, image, images,
+    is_available, is_popular, is_new, is_vegetarian, is_spicy,
+    is_vegan, is_gluten_free, is_halal, is_lactose_free, spice_level,
+    allergens, weight, calories, cooking_time, ingredients, tags,
+    created_at, updated_at, deleted_at"#;
+
+const MENU_SORT_FIELDS: &[SortField] = &[
+    ("id", "id"),
+    ("name", "name"),
+    ("price", "price"),
+    ("calories", "calories"),
+    ("cooking_time", "cooking_time"),
+    ("popular", "is_popular"),
+    ("spice_level", "spice_level"),
+    ("category", "category_id"),
+    ("created_at", "created_at"),
+    ("updated_at", "updated_at"),
+];
 
 #[derive(Debug, Deserialize)]
 pub struct MenuFilters {
@@ -15,99 +44,109 @@ pub struct MenuFilters {
     pub is_popular: Option<bool>,
     pub is_new: Option<bool>,
     pub is_vegetarian: Option<bool>,
+    pub is_spicy: Option<bool>,
+    pub is_vegan: Option<bool>,
+    pub is_gluten_free: Option<bool>,
+    pub is_halal: Option<bool>,
+    pub is_lactose_free: Option<bool>,
+    pub max_spice_level: Option<i16>,
+    // Admin listing only; the public menu shows available items
+    pub is_available: Option<bool>,
+    // Admin listing only; true lists deleted items instead
+    pub deleted: Option<bool>,
+    // Comma-separated; items having any of these tags
+    pub tags: Option<String>,
+    // Comma-separated allergen codes, e.g. "gluten,milk"
+    pub exclude_allergens: Option<String>,
+    pub max_calories: Option<i32>,
+    pub min_price: Option<BigDecimal>,
+    pub max_price: Option<BigDecimal>,
 }
 
-// Get all menu items with optional filters
-pub async fn get_menu_items(
-    State(pool): State<PgPool>,
-    Query(filters): Query<MenuFilters>,
-) -> Result<Json<Vec<MenuItem>>, AppError> {
-    let mut query = String::from(
-        r#"
-        SELECT id, category_id, name, name_ru, name_pl, 
-               description, description_ru, description_pl,
-               price, original_price, image, images,
-               is_available, is_popular, is_new, is_vegetarian, is_spicy,
-               allergens, weight, calories, cooking_time, ingredients, tags,
-               created_at, updated_at
-        FROM menu_items
-        WHERE is_available = true
-        "#
-    );
+fn apply_menu_filters(list: &mut ListQuery, filters: &MenuFilters) {
+    list.filter_opt("category_id = ", filters.category_id)
+        .filter_opt("COALESCE(is_popular, false) = ", filters.is_popular)
+        .filter_opt("COALESCE(is_new, false) = ", filters.is_new)
+        .filter_opt("COALESCE(is_vegetarian, false) = ", filters.is_vegetarian)
+        .filter_opt("COALESCE(is_spicy, false) = ", filters.is_spicy)
+        .filter_opt("is_vegan = ", filters.is_vegan)
+        .filter_opt("is_gluten_free = ", filters.is_gluten_free)
+        .filter_opt("is_halal = ", filters.is_halal)
+        .filter_opt("is_lactose_free = ", filters.is_lactose_free)
+        .filter_opt("spice_level <= ", filters.max_spice_level.map(i32::from))
+        .filter_opt("calories <= ", filters.max_calories)
+        .filter_opt("price >= ", filters.min_price.clone())
+        .filter_opt("price <= ", filters.max_price.clone());
 
-    let mut conditions = vec![];
-    
-    if let Some(category_id) = filters.category_id {
-        conditions.push(format!("category_id = {}", category_id));
-    }
-    if let Some(is_popular) = filters.is_popular {
-        conditions.push(format!("is_popular = {}", is_popular));
+    if let Some(tags) = split_list(filters.tags.as_deref()) {
+        list.filter(Sql::new("tags && ").bind(tags));
     }
-    if let Some(is_new) = filters.is_new {
-        conditions.push(format!("is_new = {}", is_new));
-    }
-    if let Some(is_vegetarian) = filters.is_vegetarian {
-        conditions.push(format!("is_vegetarian = {}", is_vegetarian));
+    if let Some(allergens) = split_list(filters.exclude_allergens.as_deref()) {
+        // Unknown codes cannot match anything, so they are simply ignored
+        let codes = allergens
+            .iter()
+            .filter_map(|a| normalize_allergens(std::slice::from_ref(a)).ok())
+            .flatten()
+            .collect::<Vec<_>>();
+        list.filter(Sql::new("NOT (COALESCE(allergens, '{}') && ").bind(codes).sql(")"));
     }
+}
 
-    if !conditions.is_empty() {
-        query.push_str(" AND ");
-        query.push_str(&conditions.join(" AND "));
-    }
+// Get all menu items with optional filters (?category_id=1&sort=-price&page=1&per_page=20).
+// Prices and availability follow the current price rules and availability windows.
+pub async fn get_menu_items(
+    State(pool): State<PgPool>,
+    locale: ContentLocale,
+    Query(filters): Query<MenuFilters>,
+    Query(params): Query<ListParams>,
+) -> Result<Page<MenuItem>, AppError> {
+    let mut list = ListQuery::from_sql(MENU_ITEM_COLUMNS, menu_items_now());
+    list.filter(Sql::new("is_available = true"));
+    apply_menu_filters(&mut list, &filters);
+    list.sort(&params, MENU_SORT_FIELDS, "id ASC")?.paginate(&params)?;
 
-    query.push_str(" ORDER BY id ASC");
+    let mut page = list.fetch_page::<MenuItem>(&pool).await?;
 
-    let items = sqlx::query_as::<_, MenuItem>(&query)
-        .fetch_all(&pool)
-        .await?;
+    translation_service::localize(&pool, &mut page.items, &locale).await?;
 
-    Ok(Json(items))
+    Ok(page)
 }
 
-// Get all menu items (including unavailable) - Admin only
+// Get all menu items (including unavailable; deleted ones with ?deleted=true) - Admin only
 pub async fn get_all_menu_items(
     State(pool): State<PgPool>,
-) -> Result<Json<Vec<MenuItem>>, AppError> {
-    let items = sqlx::query_as!(
-        MenuItem,
-        r#"
-        SELECT id, category_id, name, name_ru, name_pl, 
-               description, description_ru, description_pl,
-               price, original_price, image, images,
-               is_available, is_popular, is_new, is_vegetarian, is_spicy,
-               allergens, weight, calories, cooking_time, ingredients, tags,
-               created_at, updated_at
-        FROM menu_items
-        ORDER BY category_id ASC, id ASC
-        "#
-    )
-    .fetch_all(&pool)
-    .await?;
+    Query(filters): Query<MenuFilters>,
+    Query(params): Query<ListParams>,
+) -> Result<Page<MenuItem>, AppError> {
+    let mut list = ListQuery::new(MENU_ITEM_COLUMNS, "menu_items");
+    list.filter(Sql::new(if filters.deleted.unwrap_or(false) {
+        "deleted_at IS NOT NULL"
+    } else {
+        "deleted_at IS NULL"
+    }));
+    list.filter_opt("is_available = ", filters.is_available);
+    apply_menu_filters(&mut list, &filters);
+    list.sort(&params, MENU_SORT_FIELDS, "category_id ASC, id ASC")?.paginate(&params)?;
 
-    Ok(Json(items))
+    list.fetch_page::<MenuItem>(&pool).await
 }
 
 // Get menu item by ID
 pub async fn get_menu_item(
     State(pool): State<PgPool>,
+    locale: ContentLocale,
     Path(id): Path<i32>,
 ) -> Result<Json<MenuItem>, AppError> {
-    let item = sqlx::query_as!(
-        MenuItem,
-        r#"
-        SELECT id, category_id, name, name_ru, name_pl, 
-               description, description_ru, description_pl,
-               price, original_price, image, images,
-               is_available, is_popular, is_new, is_vegetarian, is_spicy,
-               allergens, weight, calories, cooking_time, ingredients, tags,
-               created_at, updated_at
-        FROM menu_items
-        WHERE id = $1
-        "#,
-        id
-    )
-    .fetch_one(&pool)
-    .await?;
+    let mut list = ListQuery::from_sql(MENU_ITEM_COLUMNS, menu_items_now());
+    list.filter(Sql::new("id = ").bind(id));
+
+    let mut item = list
+        .fetch_all::<MenuItem>(&pool)
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::NotFound(format!("Menu item with ID {} not found", id)))?;
+
+    translation_service::localize(&pool, std::slice::from_mut(&mut item), &locale).await?;
 
     Ok(Json(item))
 }
@@ -115,34 +154,98 @@ pub async fn get_menu_item(
 // Get menu items by category
 pub async fn get_menu_items_by_category(
     State(pool): State<PgPool>,
+    locale: ContentLocale,
     Path(category_id): Path<i32>,
-) -> Result<Json<Vec<MenuItem>>, AppError> {
-    let items = sqlx::query_as!(
-        MenuItem,
-        r#"
-        SELECT id, category_id, name, name_ru, name_pl, 
-               description, description_ru, description_pl,
-               price, original_price, image, images,
-               is_available, is_popular, is_new, is_vegetarian, is_spicy,
-               allergens, weight, calories, cooking_time, ingredients, tags,
-               created_at, updated_at
-        FROM menu_items
-        WHERE category_id = $1 AND is_available = true
-        ORDER BY id ASC
-        "#,
-        category_id
-    )
-    .fetch_all(&pool)
-    .await?;
+    Query(params): Query<ListParams>,
+) -> Result<Page<MenuItem>, AppError> {
+    let mut list = ListQuery::from_sql(MENU_ITEM_COLUMNS, menu_items_now());
+    list.filter(Sql::new("category_id = ").bind(category_id).sql(" AND is_available = true"));
+    list.sort(&params, MENU_SORT_FIELDS, "id ASC")?.paginate(&params)?;
+
+    let mut page = list.fetch_page::<MenuItem>(&pool).await?;
 
-    Ok(Json(items))
+    translation_service::localize(&pool, &mut page.items, &locale).await?;
+
+    Ok(page)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MenuSearchParams {
+    pub q: String,
+}
+
+// Search available menu items (?q=łosoś&exclude_allergens=gluten&max_price=40)
+// Matches names, descriptions, ingredients and tags in every language,
+// tolerates typos and returns the most relevant items first (unless ?sort= is given)
+pub async fn search_menu_items(
+    State(pool): State<PgPool>,
+    locale: ContentLocale,
+    Query(search): Query<MenuSearchParams>,
+    Query(filters): Query<MenuFilters>,
+    Query(params): Query<ListParams>,
+) -> Result<Page<MenuItem>, AppError> {
+    let q = search.q.trim().to_string();
+    if q.chars().filter(|c| c.is_alphanumeric()).count() < 2 {
+        return Err(AppError::Validation("Search query must contain at least 2 letters or digits".to_string()));
+    }
+
+    let mut list = ListQuery::from_sql(MENU_ITEM_COLUMNS, menu_items_now());
+    list.filter(Sql::new("is_available = true"));
+    list.filter(
+        Sql::new("search_vector @@ menu_search_query(")
+            .bind(q.as_str())
+            .sql(") OR search_normalize(")
+            .bind(q.as_str())
+            .sql(") <% search_text"),
+    );
+    apply_menu_filters(&mut list, &filters);
+
+    if params.sort.is_none() {
+        list.order_by(
+            Sql::new("ts_rank(search_vector, menu_search_query(")
+                .bind(q.as_str())
+                .sql(")) + word_similarity(search_normalize(")
+                .bind(q.as_str())
+                .sql("), search_text) DESC"),
+        );
+    }
+    list.sort(&params, MENU_SORT_FIELDS, "is_popular DESC NULLS LAST, id ASC")?;
+
+    if params.page.is_some() || params.per_page.is_some() {
+        list.paginate(&params)?;
+    } else {
+        list.limit(20);
+    }
+
+    let mut page = list.fetch_page::<MenuItem>(&pool).await?;
+
+    translation_service::localize(&pool, &mut page.items, &locale).await?;
+
+    Ok(page)
 }
 
 // Create menu item - Admin only
 pub async fn create_menu_item(
     State(pool): State<PgPool>,
+    Extension(claims): Extension<Claims>,
     Json(item_data): Json<CreateMenuItem>,
 ) -> Result<Response, AppError> {
+    let is_spicy = item_data.is_spicy.unwrap_or(false);
+    let mut dietary = DietaryInfo {
+        allergens: item_data.allergens.clone().unwrap_or_default(),
+        is_vegetarian: item_data.is_vegetarian.unwrap_or(false),
+        is_vegan: item_data.is_vegan.unwrap_or(false),
+        is_gluten_free: item_data.is_gluten_free.unwrap_or(false),
+        spice_level: item_data.spice_level.unwrap_or(if is_spicy { 1 } else { 0 }),
+    };
+    let errors = dietary.validate();
+    if !errors.is_empty() {
+        return Err(AppError::Validation(errors.join("; ")));
+    }
+
+    let mut tx = pool.begin().await?;
+    menu_history_service::set_actor(&mut tx, &claims.sub, None).await?;
+
     let item = sqlx::query_as!(
         MenuItem,
         r#"
@@ -151,15 +254,18 @@ pub async fn create_menu_item(
             description, description_ru, description_pl,
             price, original_price, image, images,
             is_vegetarian, is_spicy, allergens, weight, calories, 
-            cooking_time, ingredients, tags
+            cooking_time, ingredients, tags,
+            is_vegan, is_gluten_free, is_halal, is_lactose_free, spice_level
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19,
+                $20, $21, $22, $23, $24)
         RETURNING id, category_id, name, name_ru, name_pl, 
                   description, description_ru, description_pl,
                   price, original_price, image, images,
                   is_available, is_popular, is_new, is_vegetarian, is_spicy,
+                  is_vegan, is_gluten_free, is_halal, is_lactose_free, spice_level,
                   allergens, weight, calories, cooking_time, ingredients, tags,
-                  created_at, updated_at
+                  created_at, updated_at, deleted_at
         "#,
         item_data.category_id,
         item_data.name,
@@ -172,27 +278,47 @@ pub async fn create_menu_item(
         item_data.original_price,
         item_data.image,
         item_data.images.as_deref(),
-        item_data.is_vegetarian.unwrap_or(false),
-        item_data.is_spicy.unwrap_or(false),
-        item_data.allergens.as_deref(),
+        dietary.is_vegetarian,
+        dietary.spice_level > 0,
+        &dietary.allergens,
         item_data.weight,
         item_data.calories,
         item_data.cooking_time,
         item_data.ingredients.as_deref(),
-        item_data.tags.as_deref()
+        item_data.tags.as_deref(),
+        dietary.is_vegan,
+        dietary.is_gluten_free,
+        item_data.is_halal.unwrap_or(false),
+        item_data.is_lactose_free.unwrap_or(false),
+        dietary.spice_level
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    webhook_service::dispatch_menu_change(
+        &mut tx,
+        "menu_item",
+        "created",
+        item.id,
+        Some(serde_json::json!(&item)),
     )
-    .fetch_one(&pool)
     .await?;
 
+    tx.commit().await?;
+
     Ok((StatusCode::CREATED, Json(item)).into_response())
 }
 
 // Update menu item - Admin only
 pub async fn update_menu_item(
     State(pool): State<PgPool>,
+    Extension(claims): Extension<Claims>,
     Path(id): Path<i32>,
     Json(item_data): Json<UpdateMenuItem>,
 ) -> Result<Json<MenuItem>, AppError> {
+    let mut tx = pool.begin().await?;
+    menu_history_service::set_actor(&mut tx, &claims.sub, None).await?;
+
     // Get current item
     let mut item = sqlx::query_as!(
         MenuItem,
@@ -201,14 +327,15 @@ pub async fn update_menu_item(
                description, description_ru, description_pl,
                price, original_price, image, images,
                is_available, is_popular, is_new, is_vegetarian, is_spicy,
+               is_vegan, is_gluten_free, is_halal, is_lactose_free, spice_level,
                allergens, weight, calories, cooking_time, ingredients, tags,
-               created_at, updated_at
+               created_at, updated_at, deleted_at
         FROM menu_items
         WHERE id = $1
         "#,
         id
     )
-    .fetch_one(&pool)
+    .fetch_one(&mut *tx)
     .await?;
 
     // Update fields if provided
@@ -257,8 +384,23 @@ pub async fn update_menu_item(
     if let Some(is_vegetarian) = item_data.is_vegetarian {
         item.is_vegetarian = Some(is_vegetarian);
     }
-    if let Some(is_spicy) = item_data.is_spicy {
-        item.is_spicy = Some(is_spicy);
+    if let Some(is_vegan) = item_data.is_vegan {
+        item.is_vegan = is_vegan;
+    }
+    if let Some(is_gluten_free) = item_data.is_gluten_free {
+        item.is_gluten_free = is_gluten_free;
+    }
+    if let Some(is_halal) = item_data.is_halal {
+        item.is_halal = is_halal;
+    }
+    if let Some(is_lactose_free) = item_data.is_lactose_free {
+        item.is_lactose_free = is_lactose_free;
+    }
+    // spice_level wins over the legacy is_spicy flag
+    if let Some(spice_level) = item_data.spice_level {
+        item.spice_level = spice_level;
+    } else if let Some(is_spicy) = item_data.is_spicy {
+        item.spice_level = if is_spicy { item.spice_level.max(1) } else { 0 };
     }
     if let Some(allergens) = item_data.allergens {
         item.allergens = Some(allergens);
@@ -279,6 +421,21 @@ pub async fn update_menu_item(
         item.tags = Some(tags);
     }
 
+    let mut dietary = DietaryInfo {
+        allergens: item.allergens.clone().unwrap_or_default(),
+        is_vegetarian: item.is_vegetarian.unwrap_or(false),
+        is_vegan: item.is_vegan,
+        is_gluten_free: item.is_gluten_free,
+        spice_level: item.spice_level,
+    };
+    let errors = dietary.validate();
+    if !errors.is_empty() {
+        return Err(AppError::Validation(errors.join("; ")));
+    }
+    item.allergens = Some(dietary.allergens);
+    item.is_vegetarian = Some(dietary.is_vegetarian);
+    item.is_spicy = Some(item.spice_level > 0);
+
     // Save to database
     let updated_item = sqlx::query_as!(
         MenuItem,
@@ -290,14 +447,17 @@ pub async fn update_menu_item(
             is_available = $13, is_popular = $14, is_new = $15,
             is_vegetarian = $16, is_spicy = $17, allergens = $18,
             weight = $19, calories = $20, cooking_time = $21,
-            ingredients = $22, tags = $23
+            ingredients = $22, tags = $23,
+            is_vegan = $24, is_gluten_free = $25, is_halal = $26,
+            is_lactose_free = $27, spice_level = $28
         WHERE id = $1
         RETURNING id, category_id, name, name_ru, name_pl, 
                   description, description_ru, description_pl,
                   price, original_price, image, images,
                   is_available, is_popular, is_new, is_vegetarian, is_spicy,
+                  is_vegan, is_gluten_free, is_halal, is_lactose_free, spice_level,
                   allergens, weight, calories, cooking_time, ingredients, tags,
-                  created_at, updated_at
+                  created_at, updated_at, deleted_at
         "#,
         id,
         item.category_id,
@@ -321,28 +481,109 @@ pub async fn update_menu_item(
         item.calories,
         item.cooking_time,
         item.ingredients.as_deref(),
-        item.tags.as_deref()
+        item.tags.as_deref(),
+        item.is_vegan,
+        item.is_gluten_free,
+        item.is_halal,
+        item.is_lactose_free,
+        item.spice_level
     )
-    .fetch_one(&pool)
+    .fetch_one(&mut *tx)
     .await?;
 
+    webhook_service::dispatch_menu_change(
+        &mut tx,
+        "menu_item",
+        "updated",
+        id,
+        Some(serde_json::json!(&updated_item)),
+    )
+    .await?;
+
+    tx.commit().await?;
+
     Ok(Json(updated_item))
 }
 
-// Delete menu item - Admin only
+// Delete menu item (soft; it stays in order history and reports) - Admin only
 pub async fn delete_menu_item(
     State(pool): State<PgPool>,
+    Extension(claims): Extension<Claims>,
     Path(id): Path<i32>,
 ) -> Result<StatusCode, AppError> {
-    sqlx::query!(
+    let mut tx = pool.begin().await?;
+    menu_history_service::set_actor(&mut tx, &claims.sub, None).await?;
+
+    let result = sqlx::query!(
         r#"
-        DELETE FROM menu_items
-        WHERE id = $1
+        UPDATE menu_items
+        SET deleted_at = NOW()
+        WHERE id = $1 AND deleted_at IS NULL
         "#,
         id
     )
-    .execute(&pool)
+    .execute(&mut *tx)
     .await?;
 
+    if result.rows_affected() > 0 {
+        webhook_service::dispatch_menu_change(&mut tx, "menu_item", "deleted", id, None).await?;
+    }
+
+    tx.commit().await?;
+
     Ok(StatusCode::NO_CONTENT)
 }
+
+// Restore a deleted menu item - Admin only
+pub async fn restore_menu_item(
+    State(pool): State<PgPool>,
+    Extension(claims): Extension<Claims>,
+    Path(id): Path<i32>,
+) -> Result<Json<MenuItem>, AppError> {
+    let mut tx = pool.begin().await?;
+    menu_history_service::set_actor(&mut tx, &claims.sub, None).await?;
+
+    let category_deleted: Option<bool> = sqlx::query_scalar(
+        r#"
+        SELECT c.deleted_at IS NOT NULL
+        FROM menu_items m
+        LEFT JOIN categories c ON c.id = m.category_id
+        WHERE m.id = $1 AND m.deleted_at IS NOT NULL
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    match category_deleted {
+        None => return Err(AppError::NotFound(format!("Deleted menu item with ID {} not found", id))),
+        Some(true) => {
+            return Err(AppError::Validation(format!(
+                "The category of menu item {} is deleted; restore it or move the item first",
+                id
+            )))
+        }
+        Some(false) => {}
+    }
+
+    let item = sqlx::query_as::<_, MenuItem>(&format!(
+        "UPDATE menu_items SET deleted_at = NULL WHERE id = $1 RETURNING {}",
+        MENU_ITEM_COLUMNS
+    ))
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    webhook_service::dispatch_menu_change(
+        &mut tx,
+        "menu_item",
+        "restored",
+        id,
+        Some(serde_json::json!(&item)),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(item))
+}
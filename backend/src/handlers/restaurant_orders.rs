@@ -1,16 +1,29 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
     response::{IntoResponse, Json, Response},
+    Extension,
 };
+use serde::Deserialize;
 use sqlx::PgPool;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use bigdecimal::{BigDecimal, FromPrimitive};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::error::AppError;
+use crate::listing::{split_list, ListParams, ListQuery, Page, SortField, Sql};
 use crate::models::restaurant::{Order, OrderItem, OrderWithItems, CreateOrder, UpdateOrderStatus};
+use crate::models::allergen::MAX_SPICE_LEVEL;
+use crate::models::customer::CustomerClaims;
 use crate::websocket::{WsState, WsMessage};
+use crate::services::combo_service;
+use crate::services::notification_service;
+use crate::services::webhook_service::{self, EVENT_ORDER_CREATED, EVENT_ORDER_STATUS_CHANGED};
+use crate::services::job_queue;
+use crate::services::background_jobs::{JOB_ANALYTICS_BROADCAST, JOB_LOYALTY_CREDIT};
+use crate::services::notification_templates::{normalize_locale, OrderEvent};
+use crate::services::report_service::DEFAULT_TIMEZONE;
 
 // Generate unique order number
 fn generate_order_number() -> String {
@@ -24,6 +37,7 @@ fn generate_order_number() -> String {
 // Create order (Public)
 pub async fn create_order(
     State((pool, ws_state)): State<(PgPool, Arc<WsState>)>,
+    customer: Option<Extension<CustomerClaims>>,
     Json(order_data): Json<CreateOrder>,
 ) -> Result<Response, AppError> {
     tracing::info!("📦 Received order request from: {}", order_data.customer_name);
@@ -44,16 +58,19 @@ pub async fn create_order(
     let tax = BigDecimal::from_f64(0.0).unwrap(); // TODO: Calculate based on country
 
     // Verify all items exist and calculate subtotal
+    let mut combos = Vec::with_capacity(order_data.items.len());
     for item in &order_data.items {
         tracing::info!("🔍 Checking menu item ID: {}", item.menu_item_id);
         
+        // Scheduled price and availability, as shown on the menu
         let menu_item = sqlx::query!(
             r#"
-            SELECT price, is_available
-            FROM menu_items
+            SELECT price AS "price!", is_available
+            FROM menu_items_at(now(), $2)
             WHERE id = $1
             "#,
-            item.menu_item_id
+            item.menu_item_id,
+            DEFAULT_TIMEZONE
         )
         .fetch_optional(&mut *tx)
         .await?;
@@ -70,9 +87,15 @@ pub async fn create_order(
             )));
         }
 
-        let item_price = &menu_item.price;
+        // Combo components are checked against the slots and add their surcharges
+        let combo = combo_service::price_combo(&mut tx, item.menu_item_id, &item.components).await?;
+        let item_price = match &combo {
+            Some(combo) => &menu_item.price + &combo.surcharge,
+            None => menu_item.price,
+        };
         let item_total = item_price * BigDecimal::from_i32(item.quantity).unwrap();
         subtotal += item_total;
+        combos.push(combo);
     }
 
     let total = &subtotal + &delivery_fee + &tax;
@@ -87,9 +110,9 @@ pub async fn create_order(
             delivery_street, delivery_building, delivery_apartment, delivery_floor,
             delivery_entrance, delivery_intercom, delivery_city, delivery_postal_code,
             delivery_country, subtotal, delivery_fee, tax, total, payment_method,
-            special_instructions, status
+            special_instructions, customer_id, locale, status
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, 'pending')
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, 'pending')
         RETURNING id, order_number, customer_name, customer_phone, customer_email,
                   delivery_street, delivery_building, delivery_apartment, delivery_floor,
                   delivery_entrance, delivery_intercom, delivery_city, delivery_postal_code,
@@ -115,21 +138,24 @@ pub async fn create_order(
         tax,
         total,
         order_data.payment_method,
-        order_data.special_instructions
+        order_data.special_instructions,
+        customer.map(|Extension(claims)| claims.sub),
+        normalize_locale(order_data.locale.as_deref())
     )
     .fetch_one(&mut *tx)
     .await?;
 
     // Create order items
     let mut items = Vec::new();
-    for item_data in order_data.items {
+    for (item_data, combo) in order_data.items.into_iter().zip(combos) {
         let menu_item = sqlx::query!(
             r#"
-            SELECT name, price
-            FROM menu_items
+            SELECT name AS "name!", price AS "price!", allergens, spice_level AS "spice_level!"
+            FROM menu_items_at(now(), $2)
             WHERE id = $1
             "#,
-            item_data.menu_item_id
+            item_data.menu_item_id,
+            DEFAULT_TIMEZONE
         )
         .fetch_optional(&mut *tx)
         .await?;
@@ -145,25 +171,71 @@ pub async fn create_order(
             r#"
             INSERT INTO order_items (
                 order_id, menu_item_id, menu_item_name, menu_item_price, 
-                quantity, special_instructions
+                quantity, special_instructions, allergens, spice_level
             )
-            VALUES ($1, $2, $3, $4, $5, $6)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             RETURNING id, order_id, menu_item_id, menu_item_name, menu_item_price,
-                      quantity, special_instructions, created_at
+                      quantity, special_instructions, parent_item_id, combo_slot, created_at
             "#,
             order.id,
             item_data.menu_item_id,
             menu_item.name,
-            menu_item.price,
+            combo.as_ref().map_or(menu_item.price.clone(), |combo| &menu_item.price + &combo.surcharge),
             item_data.quantity,
-            item_data.special_instructions
+            item_data.special_instructions,
+            &menu_item.allergens.unwrap_or_default(),
+            menu_item.spice_level
         )
         .fetch_one(&mut *tx)
         .await?;
 
+        let parent_id = order_item.id;
         items.push(order_item);
+
+        // Components are snapshotted under the combo line so the kitchen sees what to make
+        for component in combo.map(|combo| combo.components).unwrap_or_default() {
+            let choice = component.choice;
+            let quantity = component.quantity.checked_mul(item_data.quantity).ok_or_else(|| {
+                AppError::BadRequest(format!("Quantity of combo {} is too large", item_data.menu_item_id))
+            })?;
+            let component_item = sqlx::query_as!(
+                OrderItem,
+                r#"
+                INSERT INTO order_items (
+                    order_id, menu_item_id, menu_item_name, menu_item_price,
+                    quantity, allergens, spice_level, parent_item_id, combo_slot
+                )
+                VALUES ($1, $2, $3, 0, $4, $5, $6, $7, $8)
+                RETURNING id, order_id, menu_item_id, menu_item_name, menu_item_price,
+                          quantity, special_instructions, parent_item_id, combo_slot, created_at
+                "#,
+                order.id,
+                choice.menu_item_id,
+                choice.name,
+                quantity,
+                &choice.allergens,
+                choice.spice_level,
+                parent_id,
+                component.slot_name
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+
+            items.push(component_item);
+        }
     }
 
+    let order_with_items = OrderWithItems {
+        order,
+        items,
+    };
+    let order = &order_with_items.order;
+
+    // Queue customer notification, webhooks and dashboard analytics refresh
+    notification_service::enqueue_order_event(&mut tx, order, OrderEvent::Created).await?;
+    webhook_service::dispatch(&mut tx, EVENT_ORDER_CREATED, serde_json::json!(&order_with_items)).await?;
+    job_queue::enqueue(&mut *tx, JOB_ANALYTICS_BROADCAST, serde_json::json!({}), None).await?;
+
     // Commit transaction
     tx.commit().await?;
 
@@ -175,19 +247,6 @@ pub async fn create_order(
         customer_name: order_data.customer_name.clone(),
         total: total.to_string(),
     });
-    
-    // Send updated analytics
-    tokio::spawn({
-        let ws_state = ws_state.clone();
-        async move {
-            ws_state.send_analytics_update().await;
-        }
-    });
-
-    let order_with_items = OrderWithItems {
-        order,
-        items,
-    };
 
     Ok((StatusCode::CREATED, Json(order_with_items)).into_response())
 }
@@ -218,7 +277,7 @@ pub async fn get_order(
         OrderItem,
         r#"
         SELECT id, order_id, menu_item_id, menu_item_name, menu_item_price,
-               quantity, special_instructions, created_at
+               quantity, special_instructions, parent_item_id, combo_slot, created_at
         FROM order_items
         WHERE order_id = $1
         "#,
@@ -230,47 +289,94 @@ pub async fn get_order(
     Ok(Json(OrderWithItems { order, items }))
 }
 
-// Get all orders - Admin only
+const ORDER_COLUMNS: &str = r#"id, order_number, customer_name, customer_phone, customer_email,
+    delivery_street, delivery_building, delivery_apartment, delivery_floor,
+    delivery_entrance, delivery_intercom, delivery_city, delivery_postal_code,
+    delivery_country, delivery_lat, delivery_lng,
+    subtotal, delivery_fee, tax, total, payment_method, status,
+    special_instructions, delivery_time, created_at, updated_at, completed_at"#;
+
+const ORDER_SORT_FIELDS: &[SortField] = &[
+    ("id", "id"),
+    ("order_number", "order_number"),
+    ("created_at", "created_at"),
+    ("updated_at", "updated_at"),
+    ("total", "total"),
+    ("status", "status"),
+];
+
+#[derive(Debug, Deserialize)]
+pub struct OrderFilters {
+    // Comma-separated, e.g. "pending,confirmed"
+    pub status: Option<String>,
+    pub payment_method: Option<String>,
+    // Order number, customer name or phone (partial match)
+    pub q: Option<String>,
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+    pub min_total: Option<BigDecimal>,
+    pub max_total: Option<BigDecimal>,
+}
+
+// Get all orders (?status=pending,confirmed&q=+4850&sort=-created_at&page=1) - Admin only
 pub async fn get_all_orders(
     State(pool): State<PgPool>,
-) -> Result<Json<Vec<OrderWithItems>>, AppError> {
-    // Fetch all orders
-    let orders = sqlx::query_as!(
-        Order,
+    Query(filters): Query<OrderFilters>,
+    Query(params): Query<ListParams>,
+) -> Result<Page<OrderWithItems>, AppError> {
+    let mut list = ListQuery::new(ORDER_COLUMNS, "orders");
+    list.filter_opt("payment_method = ", filters.payment_method)
+        .filter_opt("created_at >= ", filters.created_from)
+        .filter_opt("created_at < ", filters.created_to)
+        .filter_opt("total >= ", filters.min_total)
+        .filter_opt("total <= ", filters.max_total);
+
+    if let Some(statuses) = split_list(filters.status.as_deref()) {
+        list.filter(Sql::new("status = ANY(").bind(statuses).sql(")"));
+    }
+    if let Some(q) = filters.q.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+        let pattern = format!("%{}%", q.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"));
+        list.filter(
+            Sql::new("order_number ILIKE ")
+                .bind(pattern.as_str())
+                .sql(" OR customer_name ILIKE ")
+                .bind(pattern.as_str())
+                .sql(" OR customer_phone ILIKE ")
+                .bind(pattern.as_str()),
+        );
+    }
+
+    list.sort(&params, ORDER_SORT_FIELDS, "created_at DESC")?.paginate(&params)?;
+
+    let page = list.fetch_page::<Order>(&pool).await?;
+
+    // Items for the whole page in one query
+    let order_ids: Vec<i32> = page.items.iter().map(|order| order.id).collect();
+    let items = sqlx::query_as!(
+        OrderItem,
         r#"
-        SELECT id, order_number, customer_name, customer_phone, customer_email,
-               delivery_street, delivery_building, delivery_apartment, delivery_floor,
-               delivery_entrance, delivery_intercom, delivery_city, delivery_postal_code,
-               delivery_country, delivery_lat, delivery_lng,
-               subtotal, delivery_fee, tax, total, payment_method, status,
-               special_instructions, delivery_time, created_at, updated_at, completed_at
-        FROM orders
-        ORDER BY created_at DESC
-        "#
+        SELECT id, order_id, menu_item_id, menu_item_name, menu_item_price,
+               quantity, special_instructions, parent_item_id, combo_slot, created_at
+        FROM order_items
+        WHERE order_id = ANY($1)
+        ORDER BY id ASC
+        "#,
+        &order_ids
     )
     .fetch_all(&pool)
     .await?;
 
-    // Fetch items for each order
-    let mut orders_with_items = Vec::new();
-    for order in orders {
-        let items = sqlx::query_as!(
-            OrderItem,
-            r#"
-            SELECT id, order_id, menu_item_id, menu_item_name, menu_item_price,
-                   quantity, special_instructions, created_at
-            FROM order_items
-            WHERE order_id = $1
-            "#,
-            order.id
-        )
-        .fetch_all(&pool)
-        .await?;
-
-        orders_with_items.push(OrderWithItems { order, items });
+    let mut items_by_order: HashMap<i32, Vec<OrderItem>> = HashMap::new();
+    for item in items {
+        if let Some(order_id) = item.order_id {
+            items_by_order.entry(order_id).or_default().push(item);
+        }
     }
 
-    Ok(Json(orders_with_items))
+    Ok(page.map(|order| OrderWithItems {
+        items: items_by_order.remove(&order.id).unwrap_or_default(),
+        order,
+    }))
 }
 
 // Get order by ID with items - Admin only
@@ -299,7 +405,7 @@ pub async fn get_order_by_id(
         OrderItem,
         r#"
         SELECT id, order_id, menu_item_id, menu_item_name, menu_item_price,
-               quantity, special_instructions, created_at
+               quantity, special_instructions, parent_item_id, combo_slot, created_at
         FROM order_items
         WHERE order_id = $1
         "#,
@@ -311,9 +417,114 @@ pub async fn get_order_by_id(
     Ok(Json(OrderWithItems { order, items }))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct TicketParams {
+    // Language of allergen names: en (default), ru, pl
+    pub lang: Option<String>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct TicketLine {
+    quantity: i32,
+    menu_item_name: String,
+    special_instructions: Option<String>,
+    spice_level: i16,
+    allergens: Vec<String>,
+    parent_item_id: Option<i32>,
+    combo_slot: Option<String>,
+}
+
+// Kitchen ticket as plain text, with allergens and spice level per item - Admin only
+pub async fn get_kitchen_ticket(
+    State(pool): State<PgPool>,
+    Path(id): Path<i32>,
+    Query(params): Query<TicketParams>,
+) -> Result<Response, AppError> {
+    let lang = normalize_locale(params.lang.as_deref());
+
+    let (order_number, ordered_at, special_instructions): (String, String, Option<String>) = sqlx::query_as(
+        r#"
+        SELECT order_number, to_char(created_at AT TIME ZONE $2, 'YYYY-MM-DD HH24:MI'), special_instructions
+        FROM orders
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(DEFAULT_TIMEZONE)
+    .fetch_optional(&pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Order with ID {} not found", id)))?;
+
+    // Allergens were snapshotted when the order was placed
+    let lines = sqlx::query_as::<_, TicketLine>(
+        r#"
+        SELECT oi.quantity, oi.menu_item_name, oi.special_instructions, oi.spice_level,
+               oi.parent_item_id, oi.combo_slot,
+               COALESCE(
+                   array_agg(
+                       CASE $2 WHEN 'ru' THEN a.name_ru WHEN 'pl' THEN a.name_pl ELSE a.name END
+                       ORDER BY a.sort_order
+                   ) FILTER (WHERE a.code IS NOT NULL),
+                   '{}'
+               ) AS allergens
+        FROM order_items oi
+        LEFT JOIN allergens a ON a.code = ANY(oi.allergens)
+        WHERE oi.order_id = $1
+        GROUP BY oi.id
+        ORDER BY COALESCE(oi.parent_item_id, oi.id), oi.id
+        "#,
+    )
+    .bind(id)
+    .bind(lang)
+    .fetch_all(&pool)
+    .await?;
+
+    let rule = "=".repeat(40);
+    let mut ticket = format!("{}\nKITCHEN TICKET {}\n{}\n{}\n", rule, order_number, ordered_at, rule);
+    let mut order_allergens: Vec<&String> = Vec::new();
+
+    // Combo components follow their combo line, indented
+    for line in &lines {
+        let indent = if line.parent_item_id.is_some() { "    " } else { "" };
+        match &line.combo_slot {
+            Some(slot) if line.parent_item_id.is_some() => ticket.push_str(&format!(
+                "{}> {}: {} x {}\n",
+                indent, slot, line.quantity, line.menu_item_name
+            )),
+            _ => ticket.push_str(&format!("{}{} x {}\n", indent, line.quantity, line.menu_item_name)),
+        }
+        if !line.allergens.is_empty() {
+            ticket.push_str(&format!("{}    !! ALLERGENS: {}\n", indent, line.allergens.join(", ")));
+        }
+        if line.spice_level > 0 {
+            ticket.push_str(&format!("{}    SPICE: {}/{}\n", indent, line.spice_level, MAX_SPICE_LEVEL));
+        }
+        if let Some(note) = line.special_instructions.as_deref().filter(|n| !n.trim().is_empty()) {
+            ticket.push_str(&format!("{}    NOTE: {}\n", indent, note.trim()));
+        }
+        for allergen in &line.allergens {
+            if !order_allergens.contains(&allergen) {
+                order_allergens.push(allergen);
+            }
+        }
+    }
+
+    ticket.push_str(&format!("{}\n", "-".repeat(40)));
+    if let Some(note) = special_instructions.as_deref().filter(|n| !n.trim().is_empty()) {
+        ticket.push_str(&format!("ORDER NOTE: {}\n", note.trim()));
+    }
+    if !order_allergens.is_empty() {
+        let names: Vec<&str> = order_allergens.iter().map(|a| a.as_str()).collect();
+        ticket.push_str(&format!("ALLERGENS IN ORDER: {}\n", names.join(", ")));
+    }
+    ticket.push_str(&format!("{}\n", rule));
+
+    Ok(([(header::CONTENT_TYPE, "text/plain; charset=utf-8")], ticket).into_response())
+}
+
 // Update order status - Admin only
 pub async fn update_order_status(
-    State(pool): State<PgPool>,
+    State((pool, ws_state)): State<(PgPool, Arc<WsState>)>,
     Path(id): Path<i32>,
     Json(status_data): Json<UpdateOrderStatus>,
 ) -> Result<Json<Order>, AppError> {
@@ -332,6 +543,8 @@ pub async fn update_order_status(
         None
     };
 
+    let mut tx = pool.begin().await?;
+
     let order = sqlx::query_as!(
         Order,
         r#"
@@ -349,17 +562,41 @@ pub async fn update_order_status(
         status_data.status,
         completed_at
     )
-    .fetch_one(&pool)
+    .fetch_one(&mut *tx)
     .await?;
 
+    let event = if order.status == "cancelled" {
+        OrderEvent::Cancelled
+    } else {
+        OrderEvent::StatusChanged
+    };
+    notification_service::enqueue_order_event(&mut tx, &order, event).await?;
+    webhook_service::dispatch(&mut tx, EVENT_ORDER_STATUS_CHANGED, serde_json::json!(&order)).await?;
+
+    if order.status == "delivered" {
+        job_queue::enqueue(
+            &mut *tx,
+            JOB_LOYALTY_CREDIT,
+            serde_json::json!({ "order_id": order.id }),
+            None,
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    broadcast_status_update(&ws_state, &order);
+
     Ok(Json(order))
 }
 
 // Cancel order - Admin only
 pub async fn cancel_order(
-    State(pool): State<PgPool>,
+    State((pool, ws_state)): State<(PgPool, Arc<WsState>)>,
     Path(id): Path<i32>,
 ) -> Result<Json<Order>, AppError> {
+    let mut tx = pool.begin().await?;
+
     let order = sqlx::query_as!(
         Order,
         r#"
@@ -375,8 +612,23 @@ pub async fn cancel_order(
         "#,
         id
     )
-    .fetch_one(&pool)
+    .fetch_one(&mut *tx)
     .await?;
 
+    notification_service::enqueue_order_event(&mut tx, &order, OrderEvent::Cancelled).await?;
+    webhook_service::dispatch(&mut tx, EVENT_ORDER_STATUS_CHANGED, serde_json::json!(&order)).await?;
+
+    tx.commit().await?;
+
+    broadcast_status_update(&ws_state, &order);
+
     Ok(Json(order))
 }
+
+fn broadcast_status_update(ws_state: &WsState, order: &Order) {
+    ws_state.broadcast(WsMessage::OrderStatusUpdate {
+        order_id: order.id,
+        order_number: order.order_number.clone(),
+        status: order.status.clone(),
+    });
+}
@@ -0,0 +1,80 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use std::sync::Arc;
+
+use crate::error::AppError;
+use crate::models::schedule::{AvailabilityWindow, PriceRule, SaveAvailabilityWindow, SavePriceRule, ScheduleFilters};
+use crate::services::schedule_service::ScheduleService;
+
+// Price rules (?menu_item_id=&category_id=) - Admin only
+pub async fn get_price_rules(
+    State(schedules): State<Arc<ScheduleService>>,
+    Query(filters): Query<ScheduleFilters>,
+) -> Result<Json<Vec<PriceRule>>, AppError> {
+    Ok(Json(schedules.price_rules(&filters).await?))
+}
+
+// Create price rule - Admin only
+pub async fn create_price_rule(
+    State(schedules): State<Arc<ScheduleService>>,
+    Json(input): Json<SavePriceRule>,
+) -> Result<Response, AppError> {
+    let rule = schedules.save_price_rule(None, input).await?;
+    Ok((StatusCode::CREATED, Json(rule)).into_response())
+}
+
+// Replace price rule - Admin only
+pub async fn update_price_rule(
+    State(schedules): State<Arc<ScheduleService>>,
+    Path(id): Path<i32>,
+    Json(input): Json<SavePriceRule>,
+) -> Result<Json<PriceRule>, AppError> {
+    Ok(Json(schedules.save_price_rule(Some(id), input).await?))
+}
+
+// Delete price rule - Admin only
+pub async fn delete_price_rule(
+    State(schedules): State<Arc<ScheduleService>>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, AppError> {
+    schedules.delete_price_rule(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Availability windows (?menu_item_id=&category_id=) - Admin only
+pub async fn get_availability_windows(
+    State(schedules): State<Arc<ScheduleService>>,
+    Query(filters): Query<ScheduleFilters>,
+) -> Result<Json<Vec<AvailabilityWindow>>, AppError> {
+    Ok(Json(schedules.availability_windows(&filters).await?))
+}
+
+// Create availability window - Admin only
+pub async fn create_availability_window(
+    State(schedules): State<Arc<ScheduleService>>,
+    Json(input): Json<SaveAvailabilityWindow>,
+) -> Result<Response, AppError> {
+    let window = schedules.save_availability_window(None, input).await?;
+    Ok((StatusCode::CREATED, Json(window)).into_response())
+}
+
+// Replace availability window - Admin only
+pub async fn update_availability_window(
+    State(schedules): State<Arc<ScheduleService>>,
+    Path(id): Path<i32>,
+    Json(input): Json<SaveAvailabilityWindow>,
+) -> Result<Json<AvailabilityWindow>, AppError> {
+    Ok(Json(schedules.save_availability_window(Some(id), input).await?))
+}
+
+// Delete availability window - Admin only
+pub async fn delete_availability_window(
+    State(schedules): State<Arc<ScheduleService>>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, AppError> {
+    schedules.delete_availability_window(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
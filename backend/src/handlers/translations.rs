@@ -0,0 +1,51 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use std::sync::Arc;
+
+use crate::error::AppError;
+use crate::locale::LocaleConfig;
+use crate::services::translation_service::{
+    EntityTranslations, MissingFilters, MissingTranslation, TranslationService, TranslationUpdate,
+};
+
+// Configured content locales and fallbacks - Admin only
+pub async fn get_locales(
+    State(translations): State<Arc<TranslationService>>,
+) -> Json<LocaleConfig> {
+    Json(translations.config().clone())
+}
+
+// Texts not yet translated (?locale=uk&entity=menu_item) - Admin only
+pub async fn get_missing(
+    State(translations): State<Arc<TranslationService>>,
+    Query(filters): Query<MissingFilters>,
+) -> Result<Json<Vec<MissingTranslation>>, AppError> {
+    Ok(Json(translations.missing(filters).await?))
+}
+
+// All translations of one entity - Admin only
+pub async fn get_translations(
+    State(translations): State<Arc<TranslationService>>,
+    Path((entity, id)): Path<(String, i32)>,
+) -> Result<Json<EntityTranslations>, AppError> {
+    Ok(Json(translations.get(&entity, id).await?))
+}
+
+// Set translations ({"uk": {"name": "..."}}) - Admin only
+pub async fn update_translations(
+    State(translations): State<Arc<TranslationService>>,
+    Path((entity, id)): Path<(String, i32)>,
+    Json(update): Json<TranslationUpdate>,
+) -> Result<Json<EntityTranslations>, AppError> {
+    Ok(Json(translations.update(&entity, id, update).await?))
+}
+
+// Remove one locale from an entity - Admin only
+pub async fn delete_locale(
+    State(translations): State<Arc<TranslationService>>,
+    Path((entity, id, locale)): Path<(String, i32, String)>,
+) -> Result<Json<EntityTranslations>, AppError> {
+    Ok(Json(translations.delete_locale(&entity, id, &locale).await?))
+}
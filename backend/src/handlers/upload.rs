@@ -1,18 +1,39 @@
 use shuttle_axum::axum::{
     extract::{State, Multipart},
+    Extension,
     Json,
 };
+use base64::Engine;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use crate::services::cloudinary_service::{CloudinaryService, CloudinaryResponse};
+use crate::models::media::MediaAsset;
+use crate::models_single::Claims;
+use crate::services::media_service::MediaService;
 use crate::error::AppError;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UploadResponse {
+    // Media library entry
+    pub id: i32,
     pub url: String,
     pub public_id: String,
     pub width: u32,
     pub height: u32,
+    // Scaled-down copies keyed by name: thumbnail, card, hero
+    pub derivatives: serde_json::Value,
+}
+
+impl From<MediaAsset> for UploadResponse {
+    fn from(asset: MediaAsset) -> Self {
+        Self {
+            id: asset.id,
+            url: asset.url,
+            public_id: asset.public_id,
+            width: asset.width as u32,
+            height: asset.height as u32,
+            derivatives: asset.derivatives,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -23,7 +44,8 @@ pub struct Base64UploadRequest {
 
 /// Upload image from multipart form
 pub async fn upload_image(
-    State(cloudinary): State<Arc<CloudinaryService>>,
+    State(media): State<Arc<MediaService>>,
+    Extension(claims): Extension<Claims>,
     mut multipart: Multipart,
 ) -> Result<Json<UploadResponse>, AppError> {
     let mut file_data: Option<Vec<u8>> = None;
@@ -62,43 +84,26 @@ pub async fn upload_image(
 
     tracing::info!("Uploading image: {} ({} bytes)", filename, file_data.len());
 
-    let result = cloudinary
-        .upload_image(file_data, filename, folder)
-        .await
-        .map_err(|e| {
-            tracing::error!("Cloudinary upload error: {}", e);
-            AppError::InternalError
-        })?;
-
-    Ok(Json(UploadResponse {
-        url: result.secure_url,
-        public_id: result.public_id,
-        width: result.width,
-        height: result.height,
-    }))
+    let asset = media.upload(file_data, folder.as_deref(), &claims.sub).await?;
+    Ok(Json(asset.into()))
 }
 
 /// Upload image from base64
 pub async fn upload_base64(
-    State(cloudinary): State<Arc<CloudinaryService>>,
+    State(media): State<Arc<MediaService>>,
+    Extension(claims): Extension<Claims>,
     Json(payload): Json<Base64UploadRequest>,
 ) -> Result<Json<UploadResponse>, AppError> {
     tracing::info!("Uploading base64 image to folder: {:?}", payload.folder);
 
-    let result = cloudinary
-        .upload_base64(payload.image, payload.folder)
-        .await
-        .map_err(|e| {
-            tracing::error!("Cloudinary base64 upload error: {}", e);
-            AppError::InternalError
-        })?;
-
-    Ok(Json(UploadResponse {
-        url: result.secure_url,
-        public_id: result.public_id,
-        width: result.width,
-        height: result.height,
-    }))
+    // Plain base64 or a `data:image/...;base64,` URL
+    let encoded = payload.image.split_once(";base64,").map_or(payload.image.as_str(), |(_, encoded)| encoded);
+    let data = base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .map_err(|e| AppError::BadRequest(format!("Invalid base64 image: {}", e)))?;
+
+    let asset = media.upload(data, payload.folder.as_deref(), &claims.sub).await?;
+    Ok(Json(asset.into()))
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -106,20 +111,14 @@ pub struct DeleteRequest {
     pub public_id: String,
 }
 
-/// Delete image from Cloudinary
+/// Delete image from the configured storage (refused while it is in use)
 pub async fn delete_image(
-    State(cloudinary): State<Arc<CloudinaryService>>,
+    State(media): State<Arc<MediaService>>,
     Json(payload): Json<DeleteRequest>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     tracing::info!("Deleting image: {}", payload.public_id);
 
-    cloudinary
-        .delete_image(payload.public_id)
-        .await
-        .map_err(|e| {
-            tracing::error!("Cloudinary delete error: {}", e);
-            AppError::InternalError
-        })?;
+    media.delete_by_public_id(&payload.public_id).await?;
 
     Ok(Json(serde_json::json!({
         "success": true,
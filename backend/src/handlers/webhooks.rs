@@ -0,0 +1,79 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use std::sync::Arc;
+
+use crate::error::AppError;
+use crate::models::webhook::{
+    CreateWebhookEndpoint, UpdateWebhookEndpoint, WebhookDelivery, WebhookDeliveryFilters,
+    WebhookEndpoint,
+};
+use crate::services::webhook_service::WebhookService;
+
+// List webhook endpoints - Admin only
+pub async fn get_endpoints(
+    State(webhooks): State<Arc<WebhookService>>,
+) -> Result<Json<Vec<WebhookEndpoint>>, AppError> {
+    Ok(Json(webhooks.list_endpoints().await?))
+}
+
+// Get webhook endpoint by ID - Admin only
+pub async fn get_endpoint(
+    State(webhooks): State<Arc<WebhookService>>,
+    Path(id): Path<i32>,
+) -> Result<Json<WebhookEndpoint>, AppError> {
+    Ok(Json(webhooks.get_endpoint(id).await?))
+}
+
+// Register webhook endpoint - Admin only
+pub async fn create_endpoint(
+    State(webhooks): State<Arc<WebhookService>>,
+    Json(data): Json<CreateWebhookEndpoint>,
+) -> Result<Response, AppError> {
+    let endpoint = webhooks.create_endpoint(data).await?;
+    Ok((StatusCode::CREATED, Json(endpoint)).into_response())
+}
+
+// Update webhook endpoint - Admin only
+pub async fn update_endpoint(
+    State(webhooks): State<Arc<WebhookService>>,
+    Path(id): Path<i32>,
+    Json(data): Json<UpdateWebhookEndpoint>,
+) -> Result<Json<WebhookEndpoint>, AppError> {
+    Ok(Json(webhooks.update_endpoint(id, data).await?))
+}
+
+// Delete webhook endpoint - Admin only
+pub async fn delete_endpoint(
+    State(webhooks): State<Arc<WebhookService>>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, AppError> {
+    webhooks.delete_endpoint(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Delivery log (?endpoint_id=1&status=failed&limit=50) - Admin only
+pub async fn get_deliveries(
+    State(webhooks): State<Arc<WebhookService>>,
+    Query(filters): Query<WebhookDeliveryFilters>,
+) -> Result<Json<Vec<WebhookDelivery>>, AppError> {
+    Ok(Json(webhooks.list_deliveries(filters).await?))
+}
+
+// Get delivery by ID - Admin only
+pub async fn get_delivery(
+    State(webhooks): State<Arc<WebhookService>>,
+    Path(id): Path<i64>,
+) -> Result<Json<WebhookDelivery>, AppError> {
+    Ok(Json(webhooks.get_delivery(id).await?))
+}
+
+// Send a delivery again - Admin only
+pub async fn redeliver(
+    State(webhooks): State<Arc<WebhookService>>,
+    Path(id): Path<i64>,
+) -> Result<Json<WebhookDelivery>, AppError> {
+    Ok(Json(webhooks.redeliver(id).await?))
+}
@@ -0,0 +1,563 @@
+pub mod models_single;
+pub mod models;
+pub mod error;
+pub mod config;
+pub mod db_single;
+mod listing;
+mod locale;
+mod websocket;
+mod repositories {
+    pub mod customer_repository;
+    pub mod portfolio_repository_single;
+}
+pub mod services {
+    pub mod auth_service_single;
+    pub mod background_jobs;
+    pub mod blog_service;
+    pub mod cloudinary_service;
+    pub mod combo_service;
+    pub mod customer_service;
+    pub mod data_maintenance_service;
+    pub mod image_processing;
+    pub mod image_storage;
+    pub mod job_queue;
+    pub mod media_service;
+    pub mod menu_bulk_service;
+    pub mod menu_history_service;
+    pub mod menu_transfer_service;
+    pub mod notification_service;
+    pub mod notification_templates;
+    pub mod notifier;
+    pub mod portfolio_schema;
+    pub mod report_service;
+    pub mod restaurant_config_service;
+    pub mod schedule_service;
+    pub mod sms_sender;
+    pub mod staff_service;
+    pub mod translation_service;
+    pub mod webhook_service;
+}
+mod handlers {
+    pub mod allergens;
+    pub mod auth_single;
+    pub mod blog;
+    pub mod combos;
+    pub mod customer;
+    pub mod jobs;
+    pub mod media;
+    pub mod menu_bulk;
+    pub mod menu_history;
+    pub mod menu_transfer;
+    pub mod portfolio_single;
+    pub mod reports;
+    pub mod upload;
+    pub mod restaurant_categories;
+    pub mod restaurant_menu;
+    pub mod restaurant_orders;
+    pub mod restaurant_info;
+    pub mod schedules;
+    pub mod translations;
+    pub mod webhooks;
+}
+mod middleware_single;
+
+// Load .env file for sqlx compile-time verification
+#[cfg(not(target_env = "shuttle"))]
+fn load_env() {
+    dotenvy::dotenv().ok();
+}
+
+#[cfg(target_env = "shuttle")]
+fn load_env() {
+    // Shuttle sets DATABASE_URL automatically
+}
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use shuttle_axum::axum::{
+    routing::{get, post, put, delete},
+    Router,
+    http::Method,
+    middleware as axum_middleware,
+    extract::DefaultBodyLimit,
+    Extension,
+};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+use tower_http::services::ServeDir;
+use services::auth_service_single::AuthService;
+use services::cloudinary_service::CloudinaryService;
+use services::image_storage::{ImageStorage, LocalImageStorage, S3ImageStorage};
+use services::customer_service::CustomerService;
+use services::sms_sender::{SmsSender, ConsoleSmsSender, HttpSmsSender};
+use services::notifier::{Notifier, SmtpNotifier, SmsNotifier, LogNotifier, CHANNEL_EMAIL, CHANNEL_SMS};
+use services::notification_service::NotificationService;
+use services::job_queue::JobQueue;
+use services::media_service::MediaService;
+use services::background_jobs::{AnalyticsBroadcastJob, DailyReportJob, ImageDeleteJob, LoyaltyCreditJob};
+use services::webhook_service::WebhookService;
+use services::report_service::ReportService;
+use services::menu_bulk_service::MenuBulkService;
+use services::menu_history_service::MenuHistoryService;
+use services::menu_transfer_service::MenuTransferService;
+use services::translation_service::TranslationService;
+use services::combo_service::ComboService;
+use services::blog_service::BlogService;
+use services::schedule_service::ScheduleService;
+use repositories::customer_repository::CustomerRepository;
+use repositories::portfolio_repository_single::PortfolioRepository;
+use config::{AppConfig, ImageStorageConfig};
+use anyhow::Context;
+use sqlx::PgPool;
+
+/// The whole API; shared by the Shuttle entrypoint and the standalone server.
+/// Fails when the database cannot be migrated or an integration cannot start.
+pub async fn create_app(pool: PgPool, config: AppConfig) -> anyhow::Result<Router> {
+    let (image_storage, local_media) = configured_image_storage(&config);
+    build_app(pool, config, image_storage, local_media).await
+}
+
+/// `create_app` with the given image storage instead of the IMAGE_STORAGE one (tests)
+pub async fn create_app_with_storage(
+    pool: PgPool,
+    config: AppConfig,
+    image_storage: Arc<dyn ImageStorage>,
+) -> anyhow::Result<Router> {
+    build_app(pool, config, image_storage, None).await
+}
+
+// The storage and, for local files, the directory to serve and its path
+fn configured_image_storage(config: &AppConfig) -> (Arc<dyn ImageStorage>, Option<(PathBuf, String)>) {
+    match &config.image_storage {
+        ImageStorageConfig::Cloudinary(cloudinary) => (Arc::new(CloudinaryService::new(cloudinary.clone())), None),
+        ImageStorageConfig::Local { dir, base_url, mount_path } => (
+            Arc::new(LocalImageStorage::new(dir.clone(), base_url.clone())),
+            Some((dir.clone(), mount_path.clone())),
+        ),
+        ImageStorageConfig::S3(s3) => (Arc::new(S3ImageStorage::new(s3.clone())), None),
+    }
+}
+
+async fn build_app(
+    pool: PgPool,
+    config: AppConfig,
+    image_storage: Arc<dyn ImageStorage>,
+    local_media: Option<(PathBuf, String)>,
+) -> anyhow::Result<Router> {
+    // Load .env for local development
+    load_env();
+    
+    // Create WebSocket state
+    let ws_state = Arc::new(websocket::WsState::new(pool.clone()));
+    
+    let jwt_secret = config.jwt_secret;
+    let admin_config = config.admin;
+
+    // Run migrations
+    db_single::run_migrations(&pool, config.migrations_baseline).await?;
+
+    // Dev seed data: always in development, elsewhere only with SEED_DATA=true
+    if config.environment.is_development() || config.seed_data {
+        db_single::run_seeds(&pool, false).await?;
+    }
+
+    // SMS gateway (optional, falls back to console output)
+    let sms_sender: Option<Arc<dyn SmsSender>> = match config.sms_gateway {
+        Some(gateway) => {
+            tracing::info!("📱 SMS gateway enabled");
+            Some(Arc::new(HttpSmsSender::new(gateway)))
+        }
+        None => {
+            tracing::warn!("⚠️  SMS gateway not configured, SMS will be logged");
+            None
+        }
+    };
+
+    // Initialize services
+    let auth_service = Arc::new(AuthService::new(pool.clone(), admin_config, jwt_secret.clone()));
+    let customer_service = Arc::new(CustomerService::new(
+        CustomerRepository::new(pool.clone()),
+        sms_sender.clone().unwrap_or_else(|| Arc::new(ConsoleSmsSender)),
+        jwt_secret.clone(),
+    ));
+
+    // Customer notifications: real channels when configured, otherwise
+    // written to NOTIFICATIONS_LOG_FILE (or the log) as a stand-in
+    let notifications_log = config.notifications_log;
+
+    let email_notifier: Arc<dyn Notifier> = match config.smtp {
+        Some(smtp) => Arc::new(SmtpNotifier::new(smtp).context("Invalid SMTP configuration")?),
+        None => {
+            tracing::warn!("⚠️  SMTP not configured, emails will be logged");
+            Arc::new(LogNotifier::new(CHANNEL_EMAIL, notifications_log.clone()))
+        }
+    };
+
+    let sms_notifier: Arc<dyn Notifier> = match sms_sender {
+        Some(sender) => Arc::new(SmsNotifier::new(sender)),
+        None => Arc::new(LogNotifier::new(CHANNEL_SMS, notifications_log)),
+    };
+
+    let notification_service = Arc::new(NotificationService::new(
+        pool.clone(),
+        vec![email_notifier, sms_notifier],
+    ));
+
+    tracing::info!("🖼️  Image storage: {}", image_storage.name());
+
+    // Uploads nothing uses are deleted after MEDIA_ORPHAN_GRACE_HOURS (default 24)
+    let media_service = Arc::new(MediaService::new(
+        pool.clone(),
+        image_storage.clone(),
+        config.media_orphan_grace,
+    ));
+
+    let webhook_service = Arc::new(WebhookService::new(pool.clone()));
+
+    // Content locales, default first (e.g. CONTENT_LOCALES=en,ru,pl,uk and LOCALE_FALLBACKS=uk:ru)
+    let locale_config = Arc::new(config.locales);
+
+    let report_service = Arc::new(ReportService::new(pool.clone()));
+
+    // Background jobs (durable, stored in the `jobs` table)
+    let job_queue = JobQueue::new(pool.clone())
+        .register(notification_service)
+        .register(webhook_service.clone())
+        .register(Arc::new(AnalyticsBroadcastJob::new(ws_state.clone())))
+        .register(Arc::new(LoyaltyCreditJob::new(pool.clone())))
+        .register(Arc::new(ImageDeleteJob::new(image_storage.clone())))
+        .register(Arc::new(DailyReportJob::new(pool.clone(), report_service.clone())))
+        .register(media_service.clone());
+    let job_queue = Arc::new(job_queue);
+    if config.job_worker {
+        job_queue.clone().spawn_worker();
+    } else {
+        tracing::info!("⏸️ Job worker disabled (JOB_WORKER=false)");
+    }
+    if let Err(e) = media_service.schedule_sweep().await {
+        tracing::error!("Failed to schedule the media sweep: {:?}", e);
+    }
+    if let Err(e) = report_service.schedule_daily().await {
+        tracing::error!("Failed to schedule the daily report: {:?}", e);
+    }
+
+    // CORS configuration (CORS_ORIGINS, any origin when not set)
+    let allowed_origins = if config.cors_origins.is_empty() {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(config.cors_origins)
+    };
+    let cors = CorsLayer::new()
+        .allow_origin(allowed_origins)
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
+        .allow_headers(Any);
+
+    // Public routes (без auth)
+    let public_routes = Router::new()
+        .route("/health", get(|| async { "OK" }))
+        .route("/api/auth/login", post(handlers::auth_single::login))
+        .with_state(auth_service.clone());
+    
+    // WebSocket route (отдельно, с собственным state)
+    let ws_routes = Router::new()
+        .route("/api/ws", get(websocket::ws_handler))
+        .with_state(ws_state.clone());
+
+    // Public restaurant routes (with pool)
+    let restaurant_public_pool = Router::new()
+        // Categories
+        .route("/api/restaurant/categories", get(handlers::restaurant_categories::get_categories))
+        .route("/api/restaurant/categories/{id}", get(handlers::restaurant_categories::get_category))
+        .route("/api/restaurant/categories/slug/{slug}", get(handlers::restaurant_categories::get_category_by_slug))
+        // Menu
+        .route("/api/restaurant/menu", get(handlers::restaurant_menu::get_menu_items))
+        .route("/api/restaurant/menu/search", get(handlers::restaurant_menu::search_menu_items))
+        .route("/api/restaurant/menu/{id}", get(handlers::restaurant_menu::get_menu_item))
+        .route("/api/restaurant/menu/category/{category_id}", get(handlers::restaurant_menu::get_menu_items_by_category))
+        .route("/api/restaurant/allergens", get(handlers::allergens::get_allergens))
+        // Orders (public read access for admin dashboard)
+        .route("/api/restaurant/orders/{order_number}", get(handlers::restaurant_orders::get_order))
+        .route("/api/restaurant/admin/orders", get(handlers::restaurant_orders::get_all_orders))
+        .route("/api/restaurant/admin/orders/{id}", get(handlers::restaurant_orders::get_order_by_id))
+        // Restaurant info
+        .route("/api/restaurant/info", get(handlers::restaurant_info::get_restaurant_info))
+        .layer(Extension(locale_config.clone()))
+        .with_state(pool.clone());
+    
+    // Order creation route (with ws_state for broadcasting)
+    // Guest checkout stays open; a customer token links the order to the account
+    let restaurant_orders = Router::new()
+        .route("/api/restaurant/orders", post(handlers::restaurant_orders::create_order))
+        .layer(axum_middleware::from_fn_with_state(
+            customer_service.clone(),
+            middleware_single::optional_customer_middleware,
+        ))
+        .with_state((pool.clone(), ws_state.clone()));
+
+    // Customer account routes
+    let customer_public = Router::new()
+        .route("/api/customer/register", post(handlers::customer::register))
+        .route("/api/customer/login", post(handlers::customer::login))
+        .route("/api/customer/otp/request", post(handlers::customer::request_otp))
+        .route("/api/customer/otp/verify", post(handlers::customer::verify_otp))
+        .with_state(customer_service.clone());
+
+    let customer_protected = Router::new()
+        .route("/api/customer/me", get(handlers::customer::me))
+        .route("/api/customer/orders", get(handlers::customer::get_orders))
+        .route("/api/customer/addresses", get(handlers::customer::get_addresses))
+        .route("/api/customer/addresses", post(handlers::customer::create_address))
+        .route("/api/customer/addresses/{id}", put(handlers::customer::update_address))
+        .route("/api/customer/addresses/{id}", delete(handlers::customer::delete_address))
+        .layer(axum_middleware::from_fn_with_state(
+            customer_service.clone(),
+            middleware_single::customer_auth_middleware,
+        ))
+        .with_state(customer_service.clone());
+
+    // Protected restaurant routes (admin only)
+    let restaurant_protected = Router::new()
+        // Categories
+        .route("/api/restaurant/admin/categories", get(handlers::restaurant_categories::get_all_categories))
+        .route("/api/restaurant/admin/categories", post(handlers::restaurant_categories::create_category))
+        .route("/api/restaurant/admin/categories/{id}", put(handlers::restaurant_categories::update_category))
+        .route("/api/restaurant/admin/categories/{id}", delete(handlers::restaurant_categories::delete_category))
+        .route("/api/restaurant/admin/categories/{id}/restore", post(handlers::restaurant_categories::restore_category))
+        // Menu
+        .route("/api/restaurant/admin/menu", get(handlers::restaurant_menu::get_all_menu_items))
+        .route("/api/restaurant/admin/menu", post(handlers::restaurant_menu::create_menu_item))
+        .route("/api/restaurant/admin/menu/{id}", put(handlers::restaurant_menu::update_menu_item))
+        .route("/api/restaurant/admin/menu/{id}", delete(handlers::restaurant_menu::delete_menu_item))
+        .route("/api/restaurant/admin/menu/{id}/restore", post(handlers::restaurant_menu::restore_menu_item))
+        // Kitchen
+        .route("/api/restaurant/admin/orders/{id}/ticket", get(handlers::restaurant_orders::get_kitchen_ticket))
+        // Restaurant info
+        .route("/api/restaurant/admin/info", put(handlers::restaurant_info::update_restaurant_info))
+        .layer(axum_middleware::from_fn_with_state(
+            auth_service.clone(),
+            middleware_single::auth_middleware,
+        ))
+        .with_state(pool.clone());
+
+    // Order status changes (admin only, with ws_state for broadcasting)
+    let restaurant_orders_protected = Router::new()
+        .route("/api/restaurant/admin/orders/{id}/status", put(handlers::restaurant_orders::update_order_status))
+        .route("/api/restaurant/admin/orders/{id}/cancel", put(handlers::restaurant_orders::cancel_order))
+        .layer(axum_middleware::from_fn_with_state(
+            auth_service.clone(),
+            middleware_single::auth_middleware,
+        ))
+        .with_state((pool.clone(), ws_state.clone()));
+
+    // Background jobs (admin only)
+    let jobs_protected = Router::new()
+        .route("/api/restaurant/admin/jobs", get(handlers::jobs::get_jobs))
+        .route("/api/restaurant/admin/jobs/stats", get(handlers::jobs::get_job_stats))
+        .route("/api/restaurant/admin/jobs/{id}", get(handlers::jobs::get_job))
+        .route("/api/restaurant/admin/jobs/{id}/retry", post(handlers::jobs::retry_job))
+        .layer(axum_middleware::from_fn_with_state(
+            auth_service.clone(),
+            middleware_single::auth_middleware,
+        ))
+        .with_state(job_queue.clone());
+
+    // Menu import/export (admin only)
+    let menu_transfer_protected = Router::new()
+        .route("/api/restaurant/admin/menu/export", get(handlers::menu_transfer::export_menu))
+        .route("/api/restaurant/admin/menu/import", post(handlers::menu_transfer::import_menu))
+        .layer(DefaultBodyLimit::max(10 * 1024 * 1024))
+        .layer(axum_middleware::from_fn_with_state(
+            auth_service.clone(),
+            middleware_single::auth_middleware,
+        ))
+        .with_state(Arc::new(MenuTransferService::new(pool.clone())));
+
+    // Menu item history (admin only)
+    let menu_history_protected = Router::new()
+        .route("/api/restaurant/admin/menu/{id}/versions", get(handlers::menu_history::get_versions))
+        .route("/api/restaurant/admin/menu/{id}/versions/{version}/rollback", post(handlers::menu_history::rollback))
+        .layer(axum_middleware::from_fn_with_state(
+            auth_service.clone(),
+            middleware_single::auth_middleware,
+        ))
+        .with_state(Arc::new(MenuHistoryService::new(pool.clone())));
+
+    // Bulk menu operations (admin only, with ws_state for broadcasting)
+    let menu_bulk_protected = Router::new()
+        .route("/api/restaurant/admin/menu/bulk", get(handlers::menu_bulk::get_bulk_operations))
+        .route("/api/restaurant/admin/menu/bulk", post(handlers::menu_bulk::bulk_update))
+        .layer(axum_middleware::from_fn_with_state(
+            auth_service.clone(),
+            middleware_single::auth_middleware,
+        ))
+        .with_state(Arc::new(MenuBulkService::new(pool.clone(), ws_state.clone())));
+
+    // Media library (admin only)
+    let media_protected = Router::new()
+        .route("/api/restaurant/admin/media", get(handlers::media::get_media))
+        .route("/api/restaurant/admin/media/{id}", get(handlers::media::get_media_asset))
+        .route("/api/restaurant/admin/media/{id}", delete(handlers::media::delete_media_asset))
+        .layer(axum_middleware::from_fn_with_state(
+            auth_service.clone(),
+            middleware_single::auth_middleware,
+        ))
+        .with_state(media_service.clone());
+
+    // Sales reports (admin only)
+    let reports_protected = Router::new()
+        .route("/api/restaurant/admin/reports/summary", get(handlers::reports::get_summary))
+        .route("/api/restaurant/admin/reports/revenue", get(handlers::reports::get_revenue))
+        .route("/api/restaurant/admin/reports/top-items", get(handlers::reports::get_top_items))
+        .route("/api/restaurant/admin/reports/top-categories", get(handlers::reports::get_top_categories))
+        .route("/api/restaurant/admin/reports/heatmap", get(handlers::reports::get_heatmap))
+        .route("/api/restaurant/admin/reports/payment-methods", get(handlers::reports::get_payment_methods))
+        .route("/api/restaurant/admin/reports/menu", get(handlers::reports::get_menu_snapshot))
+        .route("/api/restaurant/admin/reports/daily", get(handlers::reports::get_daily))
+        .layer(axum_middleware::from_fn_with_state(
+            auth_service.clone(),
+            middleware_single::auth_middleware,
+        ))
+        .with_state(report_service);
+
+    // Outgoing webhooks (admin only)
+    let webhooks_protected = Router::new()
+        .route("/api/restaurant/admin/webhooks", get(handlers::webhooks::get_endpoints))
+        .route("/api/restaurant/admin/webhooks", post(handlers::webhooks::create_endpoint))
+        .route("/api/restaurant/admin/webhooks/{id}", get(handlers::webhooks::get_endpoint))
+        .route("/api/restaurant/admin/webhooks/{id}", put(handlers::webhooks::update_endpoint))
+        .route("/api/restaurant/admin/webhooks/{id}", delete(handlers::webhooks::delete_endpoint))
+        .route("/api/restaurant/admin/webhooks/deliveries", get(handlers::webhooks::get_deliveries))
+        .route("/api/restaurant/admin/webhooks/deliveries/{id}", get(handlers::webhooks::get_delivery))
+        .route("/api/restaurant/admin/webhooks/deliveries/{id}/redeliver", post(handlers::webhooks::redeliver))
+        .layer(axum_middleware::from_fn_with_state(
+            auth_service.clone(),
+            middleware_single::auth_middleware,
+        ))
+        .with_state(webhook_service);
+
+    // Translations (admin only)
+    let translations_protected = Router::new()
+        .route("/api/restaurant/admin/locales", get(handlers::translations::get_locales))
+        .route("/api/restaurant/admin/translations/missing", get(handlers::translations::get_missing))
+        .route("/api/restaurant/admin/translations/{entity}/{id}", get(handlers::translations::get_translations))
+        .route("/api/restaurant/admin/translations/{entity}/{id}", put(handlers::translations::update_translations))
+        .route("/api/restaurant/admin/translations/{entity}/{id}/{locale}", delete(handlers::translations::delete_locale))
+        .layer(axum_middleware::from_fn_with_state(
+            auth_service.clone(),
+            middleware_single::auth_middleware,
+        ))
+        .with_state(Arc::new(TranslationService::new(pool.clone(), locale_config)));
+
+    // Combo meals: slots are public, defining them is admin only
+    let combo_service = Arc::new(ComboService::new(pool.clone()));
+    let combos_public = Router::new()
+        .route("/api/restaurant/menu/{id}/combo", get(handlers::combos::get_combo))
+        .with_state(combo_service.clone());
+
+    let combos_protected = Router::new()
+        .route("/api/restaurant/admin/menu/{id}/combo", put(handlers::combos::set_combo))
+        .route("/api/restaurant/admin/menu/{id}/combo", delete(handlers::combos::delete_combo))
+        .layer(axum_middleware::from_fn_with_state(
+            auth_service.clone(),
+            middleware_single::auth_middleware,
+        ))
+        .with_state(combo_service);
+
+    // Scheduled prices and availability windows (admin only)
+    let schedules_protected = Router::new()
+        .route("/api/restaurant/admin/price-rules", get(handlers::schedules::get_price_rules))
+        .route("/api/restaurant/admin/price-rules", post(handlers::schedules::create_price_rule))
+        .route("/api/restaurant/admin/price-rules/{id}", put(handlers::schedules::update_price_rule))
+        .route("/api/restaurant/admin/price-rules/{id}", delete(handlers::schedules::delete_price_rule))
+        .route("/api/restaurant/admin/availability-windows", get(handlers::schedules::get_availability_windows))
+        .route("/api/restaurant/admin/availability-windows", post(handlers::schedules::create_availability_window))
+        .route("/api/restaurant/admin/availability-windows/{id}", put(handlers::schedules::update_availability_window))
+        .route("/api/restaurant/admin/availability-windows/{id}", delete(handlers::schedules::delete_availability_window))
+        .layer(axum_middleware::from_fn_with_state(
+            auth_service.clone(),
+            middleware_single::auth_middleware,
+        ))
+        .with_state(Arc::new(ScheduleService::new(pool.clone())));
+
+    // Blog: published posts are public, drafts and editing are admin only
+    let blog_service = Arc::new(BlogService::new(pool.clone()));
+    let blog_public = Router::new()
+        .route("/api/blog", get(handlers::blog::get_posts))
+        .route("/api/blog/{slug}", get(handlers::blog::get_post))
+        .with_state(blog_service.clone());
+
+    let blog_protected = Router::new()
+        .route("/api/admin/blog", get(handlers::blog::get_all_posts))
+        .route("/api/admin/blog", post(handlers::blog::create_post))
+        .route("/api/admin/blog/{id}", get(handlers::blog::get_post_by_id))
+        .route("/api/admin/blog/{id}", put(handlers::blog::update_post))
+        .route("/api/admin/blog/{id}", delete(handlers::blog::delete_post))
+        .layer(axum_middleware::from_fn_with_state(
+            auth_service.clone(),
+            middleware_single::auth_middleware,
+        ))
+        .with_state(blog_service);
+
+    // Chef profile: public read, editing is admin only
+    let portfolio_repository = Arc::new(PortfolioRepository::new(pool.clone()));
+    let portfolio_public = Router::new()
+        .route("/api/portfolio", get(handlers::portfolio_single::get_portfolio))
+        .route("/api/portfolio/schema", get(handlers::portfolio_single::get_portfolio_schema))
+        .with_state(portfolio_repository.clone());
+
+    let portfolio_protected = Router::new()
+        .route("/api/portfolio", put(handlers::portfolio_single::update_portfolio))
+        .layer(axum_middleware::from_fn_with_state(
+            auth_service.clone(),
+            middleware_single::auth_middleware,
+        ))
+        .with_state(portfolio_repository);
+
+    // Protected routes (тільки для admin з JWT)
+    // Build app
+    let mut app = Router::new()
+        .merge(public_routes)
+        .merge(ws_routes)
+        .merge(restaurant_public_pool)
+        .merge(restaurant_orders)
+        .merge(restaurant_protected)
+        .merge(restaurant_orders_protected)
+        .merge(customer_public)
+        .merge(customer_protected)
+        .merge(jobs_protected)
+        .merge(webhooks_protected)
+        .merge(reports_protected)
+        .merge(menu_transfer_protected)
+        .merge(menu_history_protected)
+        .merge(menu_bulk_protected)
+        .merge(media_protected)
+        .merge(translations_protected)
+        .merge(combos_public)
+        .merge(combos_protected)
+        .merge(schedules_protected)
+        .merge(blog_public)
+        .merge(blog_protected)
+        .merge(portfolio_public)
+        .merge(portfolio_protected);
+
+    let protected_upload = Router::new()
+        .route("/api/upload", post(handlers::upload::upload_image))
+        .route("/api/upload/base64", post(handlers::upload::upload_base64))
+        .route("/api/upload", delete(handlers::upload::delete_image))
+        .layer(DefaultBodyLimit::max(10 * 1024 * 1024)) // 10MB limit for file uploads
+        .layer(axum_middleware::from_fn_with_state(
+            auth_service.clone(),
+            middleware_single::auth_middleware,
+        ))
+        .with_state(media_service);
+
+    app = app.merge(protected_upload);
+
+    // Files of the local image storage
+    if let Some((dir, mount_path)) = local_media {
+        app = app.nest_service(&mount_path, ServeDir::new(dir));
+    }
+
+    app = app.layer(cors);
+
+    Ok(app)
+}
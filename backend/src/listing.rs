@@ -0,0 +1,378 @@
+use axum::{
+    http::{header::HeaderName, HeaderValue},
+    response::{IntoResponse, Json, Response},
+};
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{postgres::PgRow, FromRow, PgPool, Postgres, QueryBuilder};
+
+use crate::error::{AppError, Result};
+
+// Listing endpoints return the same JSON array as before; the total number
+// of matching rows (ignoring pagination) goes into this header
+pub const TOTAL_COUNT_HEADER: &str = "x-total-count";
+
+const DEFAULT_PER_PAGE: i64 = 50;
+const MAX_PER_PAGE: i64 = 200;
+
+/// A value bound as a query parameter, never spliced into the SQL text
+#[derive(Debug, Clone)]
+pub enum Value {
+    Bool(bool),
+    Int(i32),
+    BigInt(i64),
+    Text(String),
+    TextList(Vec<String>),
+    Decimal(BigDecimal),
+    Timestamp(DateTime<Utc>),
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<i32> for Value {
+    fn from(value: i32) -> Self {
+        Value::Int(value)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::BigInt(value)
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Text(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Text(value.to_string())
+    }
+}
+
+impl From<Vec<String>> for Value {
+    fn from(value: Vec<String>) -> Self {
+        Value::TextList(value)
+    }
+}
+
+impl From<BigDecimal> for Value {
+    fn from(value: BigDecimal) -> Self {
+        Value::Decimal(value)
+    }
+}
+
+impl From<DateTime<Utc>> for Value {
+    fn from(value: DateTime<Utc>) -> Self {
+        Value::Timestamp(value)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Part {
+    Sql(&'static str),
+    Bind(Value),
+}
+
+/// SQL fragment made of static text and bound values:
+/// `Sql::new("price <= ").bind(max_price)`
+#[derive(Debug, Clone, Default)]
+pub struct Sql {
+    parts: Vec<Part>,
+}
+
+impl Sql {
+    pub fn new(sql: &'static str) -> Self {
+        Self { parts: vec![Part::Sql(sql)] }
+    }
+
+    pub fn bind(mut self, value: impl Into<Value>) -> Self {
+        self.parts.push(Part::Bind(value.into()));
+        self
+    }
+
+    pub fn sql(mut self, sql: &'static str) -> Self {
+        self.parts.push(Part::Sql(sql));
+        self
+    }
+
+    fn push_to(&self, builder: &mut QueryBuilder<'_, Postgres>) {
+        for part in &self.parts {
+            match part {
+                Part::Sql(sql) => {
+                    builder.push(*sql);
+                }
+                Part::Bind(value) => {
+                    match value.clone() {
+                        Value::Bool(v) => builder.push_bind(v),
+                        Value::Int(v) => builder.push_bind(v),
+                        Value::BigInt(v) => builder.push_bind(v),
+                        Value::Text(v) => builder.push_bind(v),
+                        Value::TextList(v) => builder.push_bind(v),
+                        Value::Decimal(v) => builder.push_bind(v),
+                        Value::Timestamp(v) => builder.push_bind(v),
+                    };
+                }
+            }
+        }
+    }
+}
+
+/// `?sort=-price,name&page=2&per_page=20`
+#[derive(Debug, Default, Deserialize)]
+pub struct ListParams {
+    // Comma-separated fields, `-` prefix for descending
+    pub sort: Option<String>,
+    // 1-based; without page/per_page every row is returned
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+/// Sortable field exposed to clients and the column it maps to
+pub type SortField = (&'static str, &'static str);
+
+/// SELECT with filters, whitelisted sorting and pagination.
+/// Table and column names are `&'static str`, so request data can only reach
+/// the query as bound parameters.
+pub struct ListQuery {
+    columns: &'static str,
+    from: Sql,
+    filters: Vec<Sql>,
+    order: Vec<Sql>,
+    limit: Option<(i64, i64)>,
+}
+
+impl ListQuery {
+    pub fn new(columns: &'static str, from: &'static str) -> Self {
+        Self::from_sql(columns, Sql::new(from))
+    }
+
+    /// FROM with bound values, e.g. a set-returning function call
+    pub fn from_sql(columns: &'static str, from: Sql) -> Self {
+        Self {
+            columns,
+            from,
+            filters: Vec::new(),
+            order: Vec::new(),
+            limit: None,
+        }
+    }
+
+    /// Add a WHERE condition (joined with AND)
+    pub fn filter(&mut self, condition: Sql) -> &mut Self {
+        self.filters.push(condition);
+        self
+    }
+
+    /// `filter_opt("category_id = ", filters.category_id)` - skipped when None
+    pub fn filter_opt<V: Into<Value>>(&mut self, condition: &'static str, value: Option<V>) -> &mut Self {
+        if let Some(value) = value {
+            self.filters.push(Sql::new(condition).bind(value));
+        }
+        self
+    }
+
+    /// Add an ORDER BY term ahead of the client's sort
+    pub fn order_by(&mut self, term: Sql) -> &mut Self {
+        self.order.push(term);
+        self
+    }
+
+    /// Apply `?sort=`, falling back to `default` (e.g. "id ASC").
+    /// Unknown fields are rejected with the list of allowed ones.
+    pub fn sort(&mut self, params: &ListParams, allowed: &[SortField], default: &'static str) -> Result<&mut Self> {
+        let requested = params
+            .sort
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|field| !field.is_empty());
+
+        let mut sorted = false;
+        for field in requested {
+            let (name, descending) = match field.strip_prefix('-') {
+                Some(name) => (name, true),
+                None => (field.strip_prefix('+').unwrap_or(field), false),
+            };
+
+            let column = allowed
+                .iter()
+                .find(|(allowed_name, _)| *allowed_name == name)
+                .map(|(_, column)| *column)
+                .ok_or_else(|| {
+                    let names: Vec<&str> = allowed.iter().map(|(name, _)| *name).collect();
+                    AppError::Validation(format!(
+                        "Cannot sort by '{}'. Allowed: {}",
+                        name,
+                        names.join(", ")
+                    ))
+                })?;
+
+            self.order.push(Sql::new(column).sql(if descending { " DESC NULLS LAST" } else { " ASC NULLS LAST" }));
+            sorted = true;
+        }
+
+        if !sorted {
+            self.order.push(Sql::new(default));
+        }
+
+        Ok(self)
+    }
+
+    pub fn paginate(&mut self, params: &ListParams) -> Result<&mut Self> {
+        if params.page.is_none() && params.per_page.is_none() {
+            return Ok(self);
+        }
+
+        let page = params.page.unwrap_or(1);
+        let per_page = params.per_page.unwrap_or(DEFAULT_PER_PAGE);
+        if page < 1 {
+            return Err(AppError::Validation("page must be 1 or greater".to_string()));
+        }
+        if !(1..=MAX_PER_PAGE).contains(&per_page) {
+            return Err(AppError::Validation(format!("per_page must be between 1 and {}", MAX_PER_PAGE)));
+        }
+
+        let offset = (page - 1)
+            .checked_mul(per_page)
+            .ok_or_else(|| AppError::Validation("page is too large".to_string()))?;
+        self.limit = Some((per_page, offset));
+        Ok(self)
+    }
+
+    /// Cap the number of rows without an offset
+    pub fn limit(&mut self, limit: i64) -> &mut Self {
+        self.limit = Some((limit, 0));
+        self
+    }
+
+    fn push_where(&self, builder: &mut QueryBuilder<'_, Postgres>) {
+        for (i, filter) in self.filters.iter().enumerate() {
+            builder.push(if i == 0 { " WHERE " } else { " AND " });
+            builder.push("(");
+            filter.push_to(builder);
+            builder.push(")");
+        }
+    }
+
+    fn select(&self) -> QueryBuilder<'static, Postgres> {
+        let mut builder = QueryBuilder::new(format!("SELECT {} FROM ", self.columns));
+        self.from.push_to(&mut builder);
+        self.push_where(&mut builder);
+
+        for (i, term) in self.order.iter().enumerate() {
+            builder.push(if i == 0 { " ORDER BY " } else { ", " });
+            term.push_to(&mut builder);
+        }
+
+        if let Some((limit, offset)) = self.limit {
+            builder.push(" LIMIT ").push_bind(limit);
+            builder.push(" OFFSET ").push_bind(offset);
+        }
+
+        builder
+    }
+
+    pub async fn fetch_all<T>(&self, pool: &PgPool) -> Result<Vec<T>>
+    where
+        T: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+    {
+        Ok(self.select().build_query_as::<T>().fetch_all(pool).await?)
+    }
+
+    pub async fn count(&self, pool: &PgPool) -> Result<i64> {
+        let mut builder = QueryBuilder::new("SELECT COUNT(*) FROM ");
+        self.from.push_to(&mut builder);
+        self.push_where(&mut builder);
+
+        Ok(builder.build_query_scalar::<i64>().fetch_one(pool).await?)
+    }
+
+    /// Rows of the requested page plus the total for `X-Total-Count`
+    pub async fn fetch_page<T>(&self, pool: &PgPool) -> Result<Page<T>>
+    where
+        T: for<'r> FromRow<'r, PgRow> + Send + Unpin,
+    {
+        let items = self.fetch_all(pool).await?;
+        let total = match self.limit {
+            // Everything fit on the first page, no need to count
+            Some((limit, 0)) if (items.len() as i64) < limit => items.len() as i64,
+            None => items.len() as i64,
+            Some(_) => self.count(pool).await?,
+        };
+
+        Ok(Page { items, total })
+    }
+}
+
+/// One page of a listing, serialized as a plain JSON array
+#[derive(Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: i64,
+}
+
+impl<T> Page<T> {
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+        }
+    }
+}
+
+impl<T: Serialize> IntoResponse for Page<T> {
+    fn into_response(self) -> Response {
+        let mut response = Json(self.items).into_response();
+        response.headers_mut().insert(
+            HeaderName::from_static(TOTAL_COUNT_HEADER),
+            HeaderValue::from(self.total),
+        );
+        response
+    }
+}
+
+/// Comma-separated query value -> trimmed, non-empty entries
+pub fn split_list(value: Option<&str>) -> Option<Vec<String>> {
+    let values: Vec<String> = value?
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    (!values.is_empty()).then_some(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(page: i64, per_page: i64) -> ListParams {
+        ListParams { page: Some(page), per_page: Some(per_page), ..Default::default() }
+    }
+
+    #[test]
+    fn paginates_with_limit_and_offset() {
+        let mut list = ListQuery::new("id", "menu_items");
+        list.paginate(&page(3, 20)).unwrap();
+        assert_eq!(list.limit, Some((20, 40)));
+    }
+
+    #[test]
+    fn rejects_pages_out_of_range() {
+        let mut list = ListQuery::new("id", "menu_items");
+        for params in [page(0, 20), page(1, 0), page(1, MAX_PER_PAGE + 1), page(i64::MAX, 100)] {
+            assert!(matches!(list.paginate(&params), Err(AppError::Validation(_))), "{:?}", params);
+        }
+    }
+}
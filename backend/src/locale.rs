@@ -0,0 +1,173 @@
+use axum::{
+    extract::{FromRequestParts, Query},
+    http::{header, request::Parts},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+// Locales that still have `<field>_ru` / `<field>_pl` columns
+pub const LEGACY_LOCALES: [&str; 2] = ["ru", "pl"];
+
+/// Content locales the admin can translate into.
+/// The first one is the default and is stored in the base columns.
+#[derive(Debug, Clone, Serialize)]
+pub struct LocaleConfig {
+    pub default_locale: String,
+    pub locales: Vec<String>,
+    // locale -> next locale to try, e.g. uk -> ru
+    pub fallbacks: HashMap<String, String>,
+}
+
+impl Default for LocaleConfig {
+    fn default() -> Self {
+        Self::from_settings(None, None)
+    }
+}
+
+/// `pl-PL` -> `pl`
+pub fn normalize_tag(tag: &str) -> String {
+    tag.trim()
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+impl LocaleConfig {
+    /// `locales`: comma-separated, default first (`en,ru,pl,uk`).
+    /// `fallbacks`: comma-separated `locale:fallback` pairs (`uk:ru,be:ru`).
+    pub fn from_settings(locales: Option<&str>, fallbacks: Option<&str>) -> Self {
+        let mut parsed: Vec<String> = Vec::new();
+        for locale in locales.unwrap_or("en,ru,pl").split(',').map(normalize_tag) {
+            if !locale.is_empty() && !parsed.contains(&locale) {
+                parsed.push(locale);
+            }
+        }
+        if parsed.is_empty() {
+            parsed.push("en".to_string());
+        }
+
+        let fallbacks = fallbacks
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|pair| pair.split_once(':'))
+            .map(|(from, to)| (normalize_tag(from), normalize_tag(to)))
+            .filter(|(from, to)| parsed.contains(from) && parsed.contains(to))
+            .collect();
+
+        Self {
+            default_locale: parsed[0].clone(),
+            locales: parsed,
+            fallbacks,
+        }
+    }
+
+    pub fn is_supported(&self, locale: &str) -> bool {
+        self.locales.iter().any(|l| l == locale)
+    }
+
+    /// Locales to try in order: each preference followed by its fallbacks,
+    /// always ending with the default locale
+    pub fn chain(&self, preferred: &[String]) -> Vec<String> {
+        let mut chain: Vec<String> = Vec::new();
+
+        for locale in preferred {
+            let mut current = Some(locale.clone());
+            while let Some(locale) = current {
+                if !self.is_supported(&locale) || chain.contains(&locale) {
+                    break;
+                }
+                current = self.fallbacks.get(&locale).cloned();
+                chain.push(locale);
+            }
+        }
+
+        if !chain.contains(&self.default_locale) {
+            chain.push(self.default_locale.clone());
+        }
+
+        chain
+    }
+}
+
+/// Language tags from an `Accept-Language` header, most preferred first
+fn parse_accept_language(value: &str) -> Vec<String> {
+    let mut tags: Vec<(String, f32)> = value
+        .split(',')
+        .filter_map(|part| {
+            let mut pieces = part.split(';');
+            let tag = normalize_tag(pieces.next()?);
+            let quality = pieces
+                .find_map(|p| p.trim().strip_prefix("q="))
+                .and_then(|q| q.parse().ok())
+                .unwrap_or(1.0);
+            (!tag.is_empty() && tag != "*" && quality > 0.0).then_some((tag, quality))
+        })
+        .collect();
+
+    // Stable sort keeps header order for equal weights
+    tags.sort_by(|a, b| b.1.total_cmp(&a.1));
+    tags.into_iter().map(|(tag, _)| tag).collect()
+}
+
+#[derive(Debug, Deserialize)]
+struct LangQuery {
+    lang: Option<String>,
+}
+
+/// Requested content language.
+///
+/// Public menu endpoints return the stored fields unchanged unless `?lang=` is
+/// given. `?lang=uk` resolves strings for Ukrainian (then its fallbacks,
+/// then `Accept-Language`, then the default locale); `?lang=auto` uses only
+/// `Accept-Language`.
+#[derive(Debug, Clone)]
+pub struct ContentLocale {
+    // None when the client did not ask for resolved strings
+    pub chain: Option<Vec<String>>,
+    // Stored in the base columns, so resolution stops there
+    pub default_locale: String,
+}
+
+impl<S> FromRequestParts<S> for ContentLocale
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let lang = Query::<LangQuery>::try_from_uri(&parts.uri)
+            .ok()
+            .and_then(|Query(query)| query.lang)
+            .filter(|lang| !lang.trim().is_empty());
+
+        let config = parts
+            .extensions
+            .get::<Arc<LocaleConfig>>()
+            .cloned()
+            .unwrap_or_default();
+
+        let Some(lang) = lang else {
+            return Ok(Self { chain: None, default_locale: config.default_locale.clone() });
+        };
+
+        let mut preferred = Vec::new();
+        if lang != "auto" {
+            preferred.push(normalize_tag(&lang));
+        }
+        if let Some(accept) = parts
+            .headers
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+        {
+            preferred.extend(parse_accept_language(accept));
+        }
+
+        Ok(Self {
+            chain: Some(config.chain(&preferred)),
+            default_locale: config.default_locale.clone(),
+        })
+    }
+}
@@ -6,7 +6,9 @@ use shuttle_axum::axum::{
 };
 
 use crate::error::AppError;
+use crate::models_single::ROLE_STAFF;
 use crate::services::auth_service_single::AuthService;
+use crate::services::customer_service::CustomerService;
 use std::sync::Arc;
 
 pub async fn auth_middleware(
@@ -27,10 +29,12 @@ pub async fn auth_middleware(
     let token = &auth_header[7..];
     
     // Валідація токена (просто перевірка, що він дійсний)
-    let claims = auth_service.validate_token(token)?;
+    let claims = auth_service
+        .validate_token(token)
+        .map_err(|_| AppError::InvalidToken)?;
 
-    // Перевірка, що це admin
-    if claims.sub != "admin" {
+    // Перевірка, що це admin (staff акаунти мають ті ж права)
+    if claims.sub != "admin" && claims.role != ROLE_STAFF {
         return Err(AppError::Forbidden("Not an admin".to_string()));
     }
 
@@ -39,3 +43,40 @@ pub async fn auth_middleware(
 
     Ok(next.run(request).await)
 }
+
+// Customer JWT (окремо від admin токена)
+pub async fn customer_auth_middleware(
+    State(customer_service): State<Arc<CustomerService>>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let token = bearer_token(&request).ok_or(AppError::InvalidToken)?;
+    let claims = customer_service.validate_token(token)?;
+
+    request.extensions_mut().insert(claims);
+
+    Ok(next.run(request).await)
+}
+
+// Для гостьових маршрутів: якщо є валідний customer токен — додаємо claims, інакше пропускаємо
+pub async fn optional_customer_middleware(
+    State(customer_service): State<Arc<CustomerService>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let claims = bearer_token(&request).and_then(|token| customer_service.validate_token(token).ok());
+
+    if let Some(claims) = claims {
+        request.extensions_mut().insert(claims);
+    }
+
+    next.run(request).await
+}
+
+fn bearer_token(request: &Request) -> Option<&str> {
+    request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+}
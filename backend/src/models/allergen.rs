@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+
+// ===== ALLERGEN & DIETARY MODELS =====
+
+// Codes of the 14 allergens declared in the EU (Regulation 1169/2011, Annex II),
+// in label order. Localized names and icons live in the `allergens` table.
+pub const EU_ALLERGENS: [&str; 14] = [
+    "gluten",
+    "crustaceans",
+    "eggs",
+    "fish",
+    "peanuts",
+    "soybeans",
+    "milk",
+    "nuts",
+    "celery",
+    "mustard",
+    "sesame",
+    "sulphites",
+    "lupin",
+    "molluscs",
+];
+
+// 0 = not spicy, 1 = mild, 2 = medium, 3 = hot
+pub const MAX_SPICE_LEVEL: i16 = 3;
+
+// Allergens that rule out a dietary flag
+const NOT_VEGETARIAN: [&str; 3] = ["fish", "crustaceans", "molluscs"];
+const NOT_VEGAN: [&str; 5] = ["fish", "crustaceans", "molluscs", "milk", "eggs"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct Allergen {
+    pub code: String,
+    pub name: String,
+    pub name_ru: String,
+    pub name_pl: String,
+    pub icon: String,
+    pub sort_order: i32,
+}
+
+/// Common spellings -> allergen code
+fn allergen_code(value: &str) -> String {
+    let value = value.trim().to_lowercase();
+    match value.as_str() {
+        "wheat" => "gluten",
+        "egg" => "eggs",
+        "peanut" => "peanuts",
+        "soy" | "soya" => "soybeans",
+        "dairy" | "lactose" => "milk",
+        "tree nuts" => "nuts",
+        "sulfites" => "sulphites",
+        other => other,
+    }
+    .to_string()
+}
+
+/// Map to allergen codes, drop duplicates and sort in label order.
+/// Returns the values that are not EU allergens as the error.
+pub fn normalize_allergens(values: &[String]) -> Result<Vec<String>, Vec<String>> {
+    let mut codes = Vec::new();
+    let mut unknown = Vec::new();
+
+    for value in values.iter().filter(|v| !v.trim().is_empty()) {
+        let code = allergen_code(value);
+        if !EU_ALLERGENS.contains(&code.as_str()) {
+            unknown.push(value.clone());
+        } else if !codes.contains(&code) {
+            codes.push(code);
+        }
+    }
+
+    if !unknown.is_empty() {
+        return Err(unknown);
+    }
+
+    codes.sort_by_key(|code| EU_ALLERGENS.iter().position(|a| a == code));
+    Ok(codes)
+}
+
+/// Dietary fields of a menu item, checked together on create, update and import
+#[derive(Debug, Clone, Default)]
+pub struct DietaryInfo {
+    pub allergens: Vec<String>,
+    pub is_vegetarian: bool,
+    pub is_vegan: bool,
+    pub is_gluten_free: bool,
+    pub spice_level: i16,
+}
+
+impl DietaryInfo {
+    /// Normalizes allergens in place (and marks vegan items vegetarian),
+    /// returns every problem found
+    pub fn validate(&mut self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        if self.is_vegan {
+            self.is_vegetarian = true;
+        }
+
+        match normalize_allergens(&self.allergens) {
+            Ok(codes) => self.allergens = codes,
+            Err(unknown) => errors.push(format!(
+                "Unknown allergens: {}. Must be one of: {}",
+                unknown.join(", "),
+                EU_ALLERGENS.join(", ")
+            )),
+        }
+
+        if !(0..=MAX_SPICE_LEVEL).contains(&self.spice_level) {
+            errors.push(format!("spice_level must be between 0 and {}", MAX_SPICE_LEVEL));
+        }
+
+        let contains = |rules: &[&str]| -> Vec<String> {
+            self.allergens.iter().filter(|a| rules.contains(&a.as_str())).cloned().collect()
+        };
+        if self.is_vegan {
+            let found = contains(&NOT_VEGAN);
+            if !found.is_empty() {
+                errors.push(format!("A vegan item cannot contain {}", found.join(", ")));
+            }
+        } else if self.is_vegetarian {
+            let found = contains(&NOT_VEGETARIAN);
+            if !found.is_empty() {
+                errors.push(format!("A vegetarian item cannot contain {}", found.join(", ")));
+            }
+        }
+        if self.is_gluten_free && self.allergens.iter().any(|a| a == "gluten") {
+            errors.push("A gluten-free item cannot contain gluten".to_string());
+        }
+
+        errors
+    }
+}
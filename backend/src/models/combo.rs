@@ -0,0 +1,90 @@
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+
+// ===== COMBO MODELS =====
+// A combo is a menu item with slots; its price is the base price and each
+// chosen component may add a surcharge.
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Combo {
+    pub menu_item_id: i32,
+    pub slots: Vec<ComboSlot>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct ComboSlot {
+    pub id: i32,
+    pub name: String,
+    pub name_ru: String,
+    pub name_pl: String,
+    pub min_choices: i32,
+    pub max_choices: i32,
+    pub sort_order: i32,
+    #[sqlx(skip)]
+    pub options: Vec<ComboSlotOption>,
+    // Available items that fit the slot, resolved from the options
+    #[sqlx(skip)]
+    pub choices: Vec<ComboChoice>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct ComboSlotOption {
+    pub id: i32,
+    #[serde(skip)]
+    pub slot_id: i32,
+    pub category_id: Option<i32>,
+    pub menu_item_id: Option<i32>,
+    pub surcharge: BigDecimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct ComboChoice {
+    #[serde(skip)]
+    pub slot_id: i32,
+    pub menu_item_id: i32,
+    pub name: String,
+    pub name_ru: String,
+    pub name_pl: String,
+    pub image: String,
+    pub surcharge: BigDecimal,
+    pub allergens: Vec<String>,
+    pub spice_level: i16,
+}
+
+// Replaces the whole combo definition
+#[derive(Debug, Deserialize)]
+pub struct SaveCombo {
+    pub slots: Vec<ComboSlotInput>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ComboSlotInput {
+    pub name: String,
+    pub name_ru: Option<String>,
+    pub name_pl: Option<String>,
+    // Defaults: exactly 1
+    pub min_choices: Option<i32>,
+    pub max_choices: Option<i32>,
+    pub options: Vec<ComboOptionInput>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ComboOptionInput {
+    // Exactly one of category_id / menu_item_id
+    pub category_id: Option<i32>,
+    pub menu_item_id: Option<i32>,
+    pub surcharge: Option<BigDecimal>,
+}
+
+fn default_quantity() -> i32 {
+    1
+}
+
+// Component picked by the customer when ordering a combo
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ComboSelection {
+    pub slot_id: i32,
+    pub menu_item_id: i32,
+    #[serde(default = "default_quantity")]
+    pub quantity: i32,
+}
@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+
+// ===== CUSTOMER MODELS =====
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct Customer {
+    pub id: i32,
+    pub name: String,
+    pub email: Option<String>,
+    pub phone: String,
+    #[serde(skip_serializing)]
+    pub password_hash: Option<String>,
+    pub phone_verified: bool,
+    pub loyalty_points: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RegisterCustomer {
+    pub name: String,
+    pub email: String,
+    pub phone: String,
+    pub password: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CustomerLogin {
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RequestOtp {
+    pub phone: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VerifyOtp {
+    pub phone: String,
+    pub code: String,
+    // Required only when the phone has no account yet (sign-up by phone)
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CustomerAuthResponse {
+    pub token: String,
+    pub customer: Customer,
+}
+
+// ===== ADDRESS MODELS =====
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct CustomerAddress {
+    pub id: i32,
+    pub customer_id: i32,
+    pub label: Option<String>,
+    pub street: String,
+    pub building: String,
+    pub apartment: Option<String>,
+    pub floor: Option<String>,
+    pub entrance: Option<String>,
+    pub intercom: Option<String>,
+    pub city: String,
+    pub postal_code: String,
+    pub country: String,
+    pub is_default: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateCustomerAddress {
+    pub label: Option<String>,
+    pub street: String,
+    pub building: String,
+    pub apartment: Option<String>,
+    pub floor: Option<String>,
+    pub entrance: Option<String>,
+    pub intercom: Option<String>,
+    pub city: String,
+    pub postal_code: String,
+    pub country: Option<String>,
+    pub is_default: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateCustomerAddress {
+    pub label: Option<String>,
+    pub street: Option<String>,
+    pub building: Option<String>,
+    pub apartment: Option<String>,
+    pub floor: Option<String>,
+    pub entrance: Option<String>,
+    pub intercom: Option<String>,
+    pub city: Option<String>,
+    pub postal_code: Option<String>,
+    pub country: Option<String>,
+    pub is_default: Option<bool>,
+}
+
+// JWT Claims for customers (staff tokens use models_single::Claims)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomerClaims {
+    pub sub: i32, // customer id
+    pub role: String, // always "customer"
+    pub exp: usize,
+}
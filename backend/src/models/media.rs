@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+
+// ===== MEDIA LIBRARY MODELS =====
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct MediaAsset {
+    pub id: i32,
+    // Backend holding the files: cloudinary, local or s3
+    pub storage: String,
+    pub public_id: String,
+    pub url: String,
+    pub format: String,
+    pub width: i32,
+    pub height: i32,
+    pub bytes: i32,
+    pub folder: Option<String>,
+    // {"card": {"url", "public_id", "width", "height"}, ...}
+    pub derivatives: serde_json::Value,
+    pub uploaded_by: String,
+    pub created_at: DateTime<Utc>,
+    // Last time a reference to the image was removed
+    pub released_at: Option<DateTime<Utc>>,
+    // Menu items, categories, restaurant info fields, blog posts and the profile using the image
+    pub usage_count: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct MediaReference {
+    // menu_item, category, restaurant_info, blog_post or portfolio
+    pub entity: String,
+    // Numeric for menu rows, a UUID for blog posts and the profile
+    pub entity_id: String,
+    pub field: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MediaAssetDetails {
+    #[serde(flatten)]
+    pub asset: MediaAsset,
+    pub references: Vec<MediaReference>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MediaFilters {
+    // true: only images nothing uses, false: only images in use
+    pub unused: Option<bool>,
+    pub folder: Option<String>,
+}
@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use bigdecimal::BigDecimal;
+use super::menu_transfer::FieldChange;
+
+// ===== BULK MENU OPERATION MODELS =====
+
+pub const PRICE_ROUNDING_MODES: &[&str] = &["nearest", "up", "down"];
+
+// Conditions are combined with AND; at least one is required
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BulkMenuFilter {
+    pub ids: Option<Vec<i32>>,
+    pub category_id: Option<i32>,
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BulkMenuOperation {
+    SetAvailability {
+        is_available: bool,
+    },
+    SetFlags {
+        is_popular: Option<bool>,
+        is_new: Option<bool>,
+    },
+    // Exactly one of percent (+5 raises by 5%, -10 lowers by 10%) or amount.
+    // The result is rounded to a multiple of round_to (default 0.01).
+    AdjustPrice {
+        percent: Option<BigDecimal>,
+        amount: Option<BigDecimal>,
+        round_to: Option<BigDecimal>,
+        // nearest (default), up or down
+        rounding: Option<String>,
+    },
+    MoveCategory {
+        category_id: i32,
+    },
+    AddTags {
+        tags: Vec<String>,
+    },
+    RemoveTags {
+        tags: Vec<String>,
+    },
+}
+
+impl BulkMenuOperation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SetAvailability { .. } => "set_availability",
+            Self::SetFlags { .. } => "set_flags",
+            Self::AdjustPrice { .. } => "adjust_price",
+            Self::MoveCategory { .. } => "move_category",
+            Self::AddTags { .. } => "add_tags",
+            Self::RemoveTags { .. } => "remove_tags",
+        }
+    }
+
+    /// Menu item fields the operation can change
+    pub fn fields(&self) -> &'static [&'static str] {
+        match self {
+            Self::SetAvailability { .. } => &["is_available"],
+            Self::SetFlags { .. } => &["is_popular", "is_new"],
+            Self::AdjustPrice { .. } => &["price"],
+            Self::MoveCategory { .. } => &["category_id"],
+            Self::AddTags { .. } | Self::RemoveTags { .. } => &["tags"],
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BulkMenuRequest {
+    pub filter: BulkMenuFilter,
+    pub operation: BulkMenuOperation,
+}
+
+impl BulkMenuRequest {
+    /// Trims tags and fills in defaults; returns the problems found
+    pub fn validate(&mut self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        if let Some(tag) = self.filter.tag.as_mut() {
+            *tag = tag.trim().to_string();
+        }
+        let filter = &self.filter;
+        if filter.ids.as_ref().is_none_or(|ids| ids.is_empty())
+            && filter.category_id.is_none()
+            && filter.tag.as_deref().is_none_or(str::is_empty)
+        {
+            errors.push("filter needs at least one of ids, category_id or tag".to_string());
+        }
+
+        match &mut self.operation {
+            BulkMenuOperation::SetFlags { is_popular, is_new } => {
+                if is_popular.is_none() && is_new.is_none() {
+                    errors.push("set_flags needs is_popular and/or is_new".to_string());
+                }
+            }
+            BulkMenuOperation::AdjustPrice { percent, amount, round_to, rounding } => {
+                match (percent.as_ref(), amount.as_ref()) {
+                    (Some(percent), None) if percent <= &BigDecimal::from(-100) => {
+                        errors.push("percent must be greater than -100".to_string())
+                    }
+                    (Some(_), None) | (None, Some(_)) => {}
+                    _ => errors.push("adjust_price needs exactly one of percent or amount".to_string()),
+                }
+                let step = round_to.get_or_insert_with(|| BigDecimal::new(1.into(), 2));
+                if *step < BigDecimal::new(1.into(), 2) || !(&*step * BigDecimal::from(100)).is_integer() {
+                    errors.push("round_to must be a positive multiple of 0.01".to_string());
+                }
+                let mode = rounding.get_or_insert_with(|| "nearest".to_string());
+                if !PRICE_ROUNDING_MODES.contains(&mode.as_str()) {
+                    errors.push(format!("rounding must be one of: {}", PRICE_ROUNDING_MODES.join(", ")));
+                }
+            }
+            BulkMenuOperation::AddTags { tags } | BulkMenuOperation::RemoveTags { tags } => {
+                let mut cleaned: Vec<String> = Vec::new();
+                for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+                    if !cleaned.iter().any(|c| c == tag) {
+                        cleaned.push(tag.to_string());
+                    }
+                }
+                if cleaned.is_empty() {
+                    errors.push("tags must contain at least one non-empty tag".to_string());
+                }
+                *tags = cleaned;
+            }
+            BulkMenuOperation::SetAvailability { .. } | BulkMenuOperation::MoveCategory { .. } => {}
+        }
+
+        errors
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BulkMenuOptions {
+    // Report what would change without saving
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkItemChange {
+    pub id: i32,
+    pub name: String,
+    pub changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkMenuReport {
+    // Audit log entry; None on a dry run
+    pub id: Option<i32>,
+    pub dry_run: bool,
+    pub operation: &'static str,
+    pub matched: usize,
+    pub changed: usize,
+    pub items: Vec<BulkItemChange>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct MenuBulkOperation {
+    pub id: i32,
+    pub operation: String,
+    pub request: serde_json::Value,
+    pub matched: i32,
+    pub changed: i32,
+    pub items: serde_json::Value,
+    pub changed_by: String,
+    pub created_at: DateTime<Utc>,
+}
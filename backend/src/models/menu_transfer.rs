@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use bigdecimal::BigDecimal;
+
+// ===== MENU IMPORT / EXPORT MODELS =====
+// Categories are referenced by slug so a file can move between databases.
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CategoryRecord {
+    pub slug: String,
+    pub name: String,
+    pub name_ru: String,
+    pub name_pl: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub image: Option<String>,
+    #[serde(default)]
+    pub order: i32,
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MenuItemRecord {
+    // Matched first by id, then by category + name; new items get a fresh id
+    #[serde(default)]
+    pub id: Option<i32>,
+    pub category_slug: String,
+    pub name: String,
+    #[serde(default)]
+    pub name_ru: String,
+    #[serde(default)]
+    pub name_pl: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub description_ru: String,
+    #[serde(default)]
+    pub description_pl: String,
+    pub price: BigDecimal,
+    #[serde(default)]
+    pub original_price: Option<BigDecimal>,
+    #[serde(default)]
+    pub image: String,
+    #[serde(default)]
+    pub images: Vec<String>,
+    #[serde(default = "default_true")]
+    pub is_available: bool,
+    #[serde(default)]
+    pub is_popular: bool,
+    #[serde(default)]
+    pub is_new: bool,
+    #[serde(default)]
+    pub is_vegetarian: bool,
+    #[serde(default)]
+    pub is_spicy: bool,
+    #[serde(default)]
+    pub is_vegan: bool,
+    #[serde(default)]
+    pub is_gluten_free: bool,
+    #[serde(default)]
+    pub is_halal: bool,
+    #[serde(default)]
+    pub is_lactose_free: bool,
+    // 0-3; when 0, `is_spicy: true` means mild (1)
+    #[serde(default)]
+    pub spice_level: i16,
+    // EU allergen codes
+    #[serde(default)]
+    pub allergens: Vec<String>,
+    #[serde(default)]
+    pub weight: Option<String>,
+    #[serde(default)]
+    pub calories: Option<i32>,
+    #[serde(default)]
+    pub cooking_time: Option<i32>,
+    #[serde(default)]
+    pub ingredients: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+// JSON format: the whole menu, including categories without items
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MenuExport {
+    pub categories: Vec<CategoryRecord>,
+    pub items: Vec<MenuItemRecord>,
+}
+
+// CSV format: one row per menu item. Category columns create or update the
+// category; leave them empty to reference an existing one. Lists use `|`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MenuCsvRow {
+    pub id: Option<i32>,
+    pub category_slug: String,
+    pub category_name: Option<String>,
+    pub category_name_ru: Option<String>,
+    pub category_name_pl: Option<String>,
+    pub name: String,
+    pub name_ru: Option<String>,
+    pub name_pl: Option<String>,
+    pub description: Option<String>,
+    pub description_ru: Option<String>,
+    pub description_pl: Option<String>,
+    pub price: BigDecimal,
+    pub original_price: Option<BigDecimal>,
+    pub image: Option<String>,
+    pub images: Option<String>,
+    pub is_available: Option<bool>,
+    pub is_popular: Option<bool>,
+    pub is_new: Option<bool>,
+    pub is_vegetarian: Option<bool>,
+    pub is_spicy: Option<bool>,
+    pub is_vegan: Option<bool>,
+    pub is_gluten_free: Option<bool>,
+    pub is_halal: Option<bool>,
+    pub is_lactose_free: Option<bool>,
+    pub spice_level: Option<i16>,
+    pub allergens: Option<String>,
+    pub weight: Option<String>,
+    pub calories: Option<i32>,
+    pub cooking_time: Option<i32>,
+    pub ingredients: Option<String>,
+    pub tags: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MenuTransferOptions {
+    // json (default) or csv
+    pub format: Option<String>,
+    // Validate and report the diff without saving
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub from: serde_json::Value,
+    pub to: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportRowResult {
+    // 1-based position in the file (CSV: line number, header is line 1)
+    pub row: usize,
+    pub entity: &'static str, // category, menu_item
+    pub key: String,
+    pub action: &'static str, // create, update, unchanged, error
+    pub id: Option<i32>,
+    pub changes: Vec<FieldChange>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ImportSummary {
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub errors: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    // False when the import was a dry run or was rolled back because of errors
+    pub committed: bool,
+    pub summary: ImportSummary,
+    pub rows: Vec<ImportRowResult>,
+}
@@ -0,0 +1,27 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// ===== MENU ITEM HISTORY MODELS =====
+
+// A version is recorded when name, name_ru, name_pl, price, original_price,
+// is_available or category_id changes (see migration 017)
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct MenuItemVersion {
+    pub id: i32,
+    pub menu_item_id: i32,
+    pub version: i32,
+    // created, updated or deleted
+    pub action: String,
+    pub changed_fields: Vec<String>,
+    pub category_id: Option<i32>,
+    pub name: String,
+    pub name_ru: String,
+    pub name_pl: String,
+    pub price: BigDecimal,
+    pub original_price: Option<BigDecimal>,
+    pub is_available: Option<bool>,
+    pub changed_by: String,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
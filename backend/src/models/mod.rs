@@ -1,3 +1,12 @@
 pub mod restaurant;
+pub mod customer;
+pub mod webhook;
+pub mod menu_transfer;
+pub mod menu_version;
+pub mod allergen;
+pub mod combo;
+pub mod schedule;
+pub mod menu_bulk;
+pub mod media;
 
 pub use restaurant::*;
@@ -1,10 +1,11 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use bigdecimal::BigDecimal;
+use super::combo::ComboSelection;
 
 // ===== CATEGORY MODELS =====
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
 pub struct Category {
     pub id: i32,
     pub name: String,
@@ -17,6 +18,8 @@ pub struct Category {
     pub is_active: Option<bool>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    // Set when soft-deleted (see /restore)
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -63,6 +66,13 @@ pub struct MenuItem {
     pub is_new: Option<bool>,
     pub is_vegetarian: Option<bool>,
     pub is_spicy: Option<bool>,
+    pub is_vegan: bool,
+    pub is_gluten_free: bool,
+    pub is_halal: bool,
+    pub is_lactose_free: bool,
+    // 0 = not spicy ... 3 = hot
+    pub spice_level: i16,
+    // EU allergen codes (see models::allergen)
     pub allergens: Option<Vec<String>>,
     pub weight: Option<String>,
     pub calories: Option<i32>,
@@ -71,6 +81,8 @@ pub struct MenuItem {
     pub tags: Option<Vec<String>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    // Set when soft-deleted (see /restore)
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -88,6 +100,11 @@ pub struct CreateMenuItem {
     pub images: Option<Vec<String>>,
     pub is_vegetarian: Option<bool>,
     pub is_spicy: Option<bool>,
+    pub is_vegan: Option<bool>,
+    pub is_gluten_free: Option<bool>,
+    pub is_halal: Option<bool>,
+    pub is_lactose_free: Option<bool>,
+    pub spice_level: Option<i16>,
     pub allergens: Option<Vec<String>>,
     pub weight: Option<String>,
     pub calories: Option<i32>,
@@ -114,6 +131,11 @@ pub struct UpdateMenuItem {
     pub is_new: Option<bool>,
     pub is_vegetarian: Option<bool>,
     pub is_spicy: Option<bool>,
+    pub is_vegan: Option<bool>,
+    pub is_gluten_free: Option<bool>,
+    pub is_halal: Option<bool>,
+    pub is_lactose_free: Option<bool>,
+    pub spice_level: Option<i16>,
     pub allergens: Option<Vec<String>>,
     pub weight: Option<String>,
     pub calories: Option<i32>,
@@ -124,7 +146,7 @@ pub struct UpdateMenuItem {
 
 // ===== ORDER MODELS =====
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
 pub struct Order {
     pub id: i32,
     pub order_number: String,
@@ -162,7 +184,7 @@ pub struct OrderWithItems {
     pub items: Vec<OrderItem>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
 pub struct OrderItem {
     pub id: i32,
     pub order_id: Option<i32>,
@@ -171,6 +193,9 @@ pub struct OrderItem {
     pub menu_item_price: BigDecimal,
     pub quantity: i32,
     pub special_instructions: Option<String>,
+    // Set on combo components: the combo line and the slot they fill
+    pub parent_item_id: Option<i32>,
+    pub combo_slot: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -179,6 +204,9 @@ pub struct CreateOrderItem {
     pub menu_item_id: i32,
     pub quantity: i32,
     pub special_instructions: Option<String>,
+    // Chosen components when the item is a combo
+    #[serde(default)]
+    pub components: Vec<ComboSelection>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -197,6 +225,8 @@ pub struct CreateOrder {
     pub delivery_country: Option<String>,
     pub payment_method: String,
     pub special_instructions: Option<String>,
+    // Language for customer notifications: en, ru, pl
+    pub locale: Option<String>,
     pub items: Vec<CreateOrderItem>,
 }
 
@@ -274,3 +304,19 @@ pub struct UpdateRestaurantInfo {
     pub featured_dish_description: Option<String>,
     pub featured_dish_price: Option<String>,
 }
+
+// Restaurant settings with their translations (fodi-admin config dump/restore)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RestaurantConfig {
+    // id and updated_at are ignored on restore
+    pub info: RestaurantInfo,
+    #[serde(default)]
+    pub translations: Vec<RestaurantTranslation>,
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RestaurantTranslation {
+    pub field: String,
+    pub locale: String,
+    pub text: String,
+}
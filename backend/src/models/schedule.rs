@@ -0,0 +1,114 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// ===== SCHEDULE MODELS =====
+// Price rules and availability windows target one menu item or a category and
+// are evaluated in the restaurant's local time (see migration 016).
+
+pub const PRICE_RULE_KINDS: [&str; 3] = ["percent", "amount_off", "fixed_price"];
+
+/// When a rule or window applies; every part is optional
+#[derive(Debug, Serialize, Deserialize, Clone, Default, sqlx::FromRow)]
+pub struct Schedule {
+    // ISO weekdays: 1 = Monday ... 7 = Sunday
+    pub days_of_week: Option<Vec<i16>>,
+    // "14:00" - "16:00"; end before start runs past midnight
+    pub start_time: Option<NaiveTime>,
+    pub end_time: Option<NaiveTime>,
+    // Inclusive
+    pub starts_on: Option<NaiveDate>,
+    pub ends_on: Option<NaiveDate>,
+}
+
+impl Schedule {
+    /// Sorts and deduplicates weekdays, returns every problem found
+    pub fn validate(&mut self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        if let Some(days) = &mut self.days_of_week {
+            if days.is_empty() || days.iter().any(|day| !(1..=7).contains(day)) {
+                errors.push("days_of_week must list ISO weekdays from 1 (Monday) to 7 (Sunday)".to_string());
+            }
+            days.sort_unstable();
+            days.dedup();
+        }
+
+        match (self.start_time, self.end_time) {
+            (Some(start), Some(end)) if start == end => {
+                errors.push("start_time and end_time must differ (omit both for all day)".to_string())
+            }
+            (Some(_), None) | (None, Some(_)) => {
+                errors.push("start_time and end_time must be given together".to_string())
+            }
+            _ => {}
+        }
+
+        if let (Some(starts_on), Some(ends_on)) = (self.starts_on, self.ends_on) {
+            if starts_on > ends_on {
+                errors.push("starts_on must not be after ends_on".to_string());
+            }
+        }
+
+        errors
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct PriceRule {
+    pub id: i32,
+    pub name: String,
+    pub menu_item_id: Option<i32>,
+    pub category_id: Option<i32>,
+    pub kind: String,
+    pub value: BigDecimal,
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub schedule: Schedule,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Create or replace a price rule
+#[derive(Debug, Deserialize)]
+pub struct SavePriceRule {
+    pub name: String,
+    // Exactly one of menu_item_id / category_id
+    pub menu_item_id: Option<i32>,
+    pub category_id: Option<i32>,
+    // percent: value % off, amount_off: value off, fixed_price: price = value
+    pub kind: String,
+    pub value: BigDecimal,
+    #[serde(flatten)]
+    pub schedule: Schedule,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct AvailabilityWindow {
+    pub id: i32,
+    pub menu_item_id: Option<i32>,
+    pub category_id: Option<i32>,
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub schedule: Schedule,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Create or replace an availability window
+#[derive(Debug, Deserialize)]
+pub struct SaveAvailabilityWindow {
+    // Exactly one of menu_item_id / category_id
+    pub menu_item_id: Option<i32>,
+    pub category_id: Option<i32>,
+    #[serde(flatten)]
+    pub schedule: Schedule,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ScheduleFilters {
+    pub menu_item_id: Option<i32>,
+    pub category_id: Option<i32>,
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+
+// ===== WEBHOOK MODELS =====
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct WebhookEndpoint {
+    pub id: i32,
+    pub url: String,
+    pub secret: String,
+    pub events: Vec<String>,
+    pub description: Option<String>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookEndpoint {
+    pub url: String,
+    // Generated when omitted
+    pub secret: Option<String>,
+    pub events: Vec<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateWebhookEndpoint {
+    pub url: Option<String>,
+    pub secret: Option<String>,
+    pub events: Option<Vec<String>>,
+    pub description: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub endpoint_id: i32,
+    pub event: String,
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub attempts: i32,
+    pub response_status: Option<i32>,
+    pub response_body: Option<String>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WebhookDeliveryFilters {
+    pub endpoint_id: Option<i32>,
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+}
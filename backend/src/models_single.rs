@@ -20,9 +20,21 @@ pub struct AdminConfig {
     pub password_hash: String,
 }
 
+// Staff account from `fodi-admin staff create`; same access as the admin
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct StaffUser {
+    pub id: i32,
+    pub username: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
 // DTOs
 #[derive(Debug, Deserialize)]
 pub struct LoginRequest {
+    // Staff accounts only; without it the password is checked against ADMIN_PASSWORD_HASH
+    #[serde(default)]
+    pub username: Option<String>,
     pub password: String,
 }
 
@@ -40,10 +52,15 @@ pub struct UpdatePortfolioRequest {
 // JWT Claims
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
-    pub sub: String, // "admin"
+    pub sub: String, // "admin" or the staff username
+    #[serde(default)]
+    pub role: String, // admin, staff
     pub exp: usize,
 }
 
+pub const ROLE_ADMIN: &str = "admin";
+pub const ROLE_STAFF: &str = "staff";
+
 // Blog Post Models
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
 pub struct BlogPost {
@@ -58,10 +75,27 @@ pub struct BlogPost {
     pub image: String,
     pub category: String,
     pub read_time: String,
+    // draft or published
+    pub status: String,
+    // Published posts are public from this moment on (scheduled when in the future)
+    pub published_at: Option<DateTime<Utc>>,
+    // `content` rendered from Markdown, without raw HTML or unsafe links
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub content_html: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+pub const BLOG_POST_STATUSES: &[&str] = &["draft", "published"];
+
+#[derive(Debug, Deserialize)]
+pub struct BlogPostFilters {
+    pub category: Option<String>,
+    // Admin list only
+    pub status: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CreateBlogPost {
     pub slug: String,
@@ -74,6 +108,9 @@ pub struct CreateBlogPost {
     pub image: String,
     pub category: String,
     pub read_time: String,
+    // Defaults to draft
+    pub status: Option<String>,
+    pub published_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -88,6 +125,8 @@ pub struct UpdateBlogPost {
     pub image: Option<String>,
     pub category: Option<String>,
     pub read_time: Option<String>,
+    pub status: Option<String>,
+    pub published_at: Option<DateTime<Utc>>,
 }
 
 // Custom serialization for NaiveDate
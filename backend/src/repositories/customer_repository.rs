@@ -0,0 +1,387 @@
+use crate::error::{AppError, Result};
+use crate::models::customer::{Customer, CustomerAddress, CreateCustomerAddress};
+use crate::models::restaurant::{Order, OrderItem, OrderWithItems};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+const CUSTOMER_COLUMNS: &str =
+    "id, name, email, phone, password_hash, phone_verified, loyalty_points, created_at, updated_at";
+
+const ADDRESS_COLUMNS: &str = r#"id, customer_id, label, street, building, apartment, floor,
+    entrance, intercom, city, postal_code, country, is_default, created_at, updated_at"#;
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct OtpCode {
+    pub id: i32,
+    pub code_hash: String,
+    pub attempts: i32,
+    pub expires_at: DateTime<Utc>,
+}
+
+pub struct CustomerRepository {
+    pool: PgPool,
+}
+
+impl CustomerRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(
+        &self,
+        name: &str,
+        email: Option<&str>,
+        phone: &str,
+        password_hash: Option<&str>,
+        phone_verified: bool,
+    ) -> Result<Customer> {
+        let customer = sqlx::query_as::<_, Customer>(&format!(
+            r#"
+            INSERT INTO customers (name, email, phone, password_hash, phone_verified)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING {}
+            "#,
+            CUSTOMER_COLUMNS
+        ))
+        .bind(name)
+        .bind(email)
+        .bind(phone)
+        .bind(password_hash)
+        .bind(phone_verified)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            // Email or phone taken by a concurrent sign-up
+            sqlx::Error::Database(db) if db.is_unique_violation() => AppError::UserAlreadyExists,
+            e => AppError::Database(e),
+        })?;
+
+        Ok(customer)
+    }
+
+    pub async fn get_by_id(&self, id: i32) -> Result<Customer> {
+        sqlx::query_as::<_, Customer>(&format!(
+            "SELECT {} FROM customers WHERE id = $1",
+            CUSTOMER_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(AppError::UserNotFound)
+    }
+
+    pub async fn find_by_email(&self, email: &str) -> Result<Option<Customer>> {
+        let customer = sqlx::query_as::<_, Customer>(&format!(
+            "SELECT {} FROM customers WHERE LOWER(email) = LOWER($1)",
+            CUSTOMER_COLUMNS
+        ))
+        .bind(email)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(customer)
+    }
+
+    pub async fn find_by_phone(&self, phone: &str) -> Result<Option<Customer>> {
+        let customer = sqlx::query_as::<_, Customer>(&format!(
+            "SELECT {} FROM customers WHERE phone = $1",
+            CUSTOMER_COLUMNS
+        ))
+        .bind(phone)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(customer)
+    }
+
+    pub async fn mark_phone_verified(&self, id: i32) -> Result<Customer> {
+        let customer = sqlx::query_as::<_, Customer>(&format!(
+            "UPDATE customers SET phone_verified = true WHERE id = $1 RETURNING {}",
+            CUSTOMER_COLUMNS
+        ))
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(customer)
+    }
+
+    /// Attach earlier guest orders placed with this phone number to the account.
+    /// Phones on orders are stored as typed, so they are brought to E.164 the
+    /// same way as `customers.phone` before comparing.
+    pub async fn link_guest_orders(&self, customer_id: i32, phone: &str) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE orders
+            SET customer_id = $1
+            WHERE customer_id IS NULL
+              AND e164_phone(customer_phone) = $2
+            "#,
+        )
+        .bind(customer_id)
+        .bind(phone)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Order history, newest first
+    pub async fn orders(&self, customer_id: i32) -> Result<Vec<OrderWithItems>> {
+        let orders = sqlx::query_as::<_, Order>(
+            r#"
+            SELECT id, order_number, customer_name, customer_phone, customer_email,
+                   delivery_street, delivery_building, delivery_apartment, delivery_floor,
+                   delivery_entrance, delivery_intercom, delivery_city, delivery_postal_code,
+                   delivery_country, delivery_lat, delivery_lng,
+                   subtotal, delivery_fee, tax, total, payment_method, status,
+                   special_instructions, delivery_time, created_at, updated_at, completed_at
+            FROM orders
+            WHERE customer_id = $1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(customer_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let order_ids: Vec<i32> = orders.iter().map(|order| order.id).collect();
+        let items = sqlx::query_as::<_, OrderItem>(
+            r#"
+            SELECT id, order_id, menu_item_id, menu_item_name, menu_item_price,
+                   quantity, special_instructions, parent_item_id, combo_slot, created_at
+            FROM order_items
+            WHERE order_id = ANY($1)
+            ORDER BY id ASC
+            "#,
+        )
+        .bind(&order_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let orders_with_items = orders
+            .into_iter()
+            .map(|order| {
+                let order_items = items
+                    .iter()
+                    .filter(|item| item.order_id == Some(order.id))
+                    .cloned()
+                    .collect();
+                OrderWithItems { order, items: order_items }
+            })
+            .collect();
+
+        Ok(orders_with_items)
+    }
+
+    // ===== OTP CODES =====
+
+    pub async fn create_otp(&self, phone: &str, code_hash: &str, expires_at: DateTime<Utc>) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO customer_otp_codes (phone, code_hash, expires_at)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(phone)
+        .bind(code_hash)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Number of codes requested for a phone since `since` (used for rate limiting)
+    pub async fn count_recent_otps(&self, phone: &str, since: DateTime<Utc>) -> Result<i64> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM customer_otp_codes WHERE phone = $1 AND created_at > $2",
+        )
+        .bind(phone)
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Latest unconsumed code for a phone
+    pub async fn latest_otp(&self, phone: &str) -> Result<Option<OtpCode>> {
+        let otp = sqlx::query_as::<_, OtpCode>(
+            r#"
+            SELECT id, code_hash, attempts, expires_at
+            FROM customer_otp_codes
+            WHERE phone = $1 AND consumed_at IS NULL
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(phone)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(otp)
+    }
+
+    pub async fn increment_otp_attempts(&self, id: i32) -> Result<()> {
+        sqlx::query("UPDATE customer_otp_codes SET attempts = attempts + 1 WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn consume_otp(&self, id: i32) -> Result<()> {
+        sqlx::query("UPDATE customer_otp_codes SET consumed_at = NOW() WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    // ===== ADDRESSES =====
+
+    pub async fn list_addresses(&self, customer_id: i32) -> Result<Vec<CustomerAddress>> {
+        let addresses = sqlx::query_as::<_, CustomerAddress>(&format!(
+            r#"
+            SELECT {}
+            FROM customer_addresses
+            WHERE customer_id = $1
+            ORDER BY is_default DESC, id ASC
+            "#,
+            ADDRESS_COLUMNS
+        ))
+        .bind(customer_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(addresses)
+    }
+
+    pub async fn get_address(&self, customer_id: i32, id: i32) -> Result<CustomerAddress> {
+        sqlx::query_as::<_, CustomerAddress>(&format!(
+            "SELECT {} FROM customer_addresses WHERE id = $1 AND customer_id = $2",
+            ADDRESS_COLUMNS
+        ))
+        .bind(id)
+        .bind(customer_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Address with ID {} not found", id)))
+    }
+
+    pub async fn create_address(
+        &self,
+        customer_id: i32,
+        data: CreateCustomerAddress,
+    ) -> Result<CustomerAddress> {
+        let mut tx = self.pool.begin().await?;
+
+        // First address is always the default one
+        let has_addresses: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM customer_addresses WHERE customer_id = $1)",
+        )
+        .bind(customer_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        let is_default = data.is_default.unwrap_or(false) || !has_addresses;
+
+        if is_default {
+            Self::clear_default(&mut tx, customer_id).await?;
+        }
+
+        let address = sqlx::query_as::<_, CustomerAddress>(&format!(
+            r#"
+            INSERT INTO customer_addresses (
+                customer_id, label, street, building, apartment, floor,
+                entrance, intercom, city, postal_code, country, is_default
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            RETURNING {}
+            "#,
+            ADDRESS_COLUMNS
+        ))
+        .bind(customer_id)
+        .bind(data.label)
+        .bind(data.street)
+        .bind(data.building)
+        .bind(data.apartment)
+        .bind(data.floor)
+        .bind(data.entrance)
+        .bind(data.intercom)
+        .bind(data.city)
+        .bind(data.postal_code)
+        .bind(data.country.unwrap_or_else(|| "Poland".to_string()))
+        .bind(is_default)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(address)
+    }
+
+    pub async fn save_address(&self, address: &CustomerAddress) -> Result<CustomerAddress> {
+        let mut tx = self.pool.begin().await?;
+
+        if address.is_default {
+            Self::clear_default(&mut tx, address.customer_id).await?;
+        }
+
+        let address = sqlx::query_as::<_, CustomerAddress>(&format!(
+            r#"
+            UPDATE customer_addresses
+            SET label = $3, street = $4, building = $5, apartment = $6, floor = $7,
+                entrance = $8, intercom = $9, city = $10, postal_code = $11,
+                country = $12, is_default = $13
+            WHERE id = $1 AND customer_id = $2
+            RETURNING {}
+            "#,
+            ADDRESS_COLUMNS
+        ))
+        .bind(address.id)
+        .bind(address.customer_id)
+        .bind(&address.label)
+        .bind(&address.street)
+        .bind(&address.building)
+        .bind(&address.apartment)
+        .bind(&address.floor)
+        .bind(&address.entrance)
+        .bind(&address.intercom)
+        .bind(&address.city)
+        .bind(&address.postal_code)
+        .bind(&address.country)
+        .bind(address.is_default)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(address)
+    }
+
+    pub async fn delete_address(&self, customer_id: i32, id: i32) -> Result<()> {
+        let result = sqlx::query("DELETE FROM customer_addresses WHERE id = $1 AND customer_id = $2")
+            .bind(id)
+            .bind(customer_id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Address with ID {} not found", id)));
+        }
+
+        Ok(())
+    }
+
+    async fn clear_default(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>, customer_id: i32) -> Result<()> {
+        sqlx::query("UPDATE customer_addresses SET is_default = false WHERE customer_id = $1 AND is_default")
+            .bind(customer_id)
+            .execute(&mut **tx)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -1,6 +1,5 @@
 use sqlx::PgPool;
-use uuid::Uuid;
-use anyhow::Result;
+use crate::error::Result;
 use crate::models_single::{Portfolio, UpdatePortfolioRequest};
 
 pub struct PortfolioRepository {
@@ -1,45 +1,65 @@
 use anyhow::{Result, anyhow};
 use jsonwebtoken::{encode, decode, Header, Validation, EncodingKey, DecodingKey};
 use chrono::Utc;
-use crate::models_single::{LoginRequest, AuthResponse, Claims, AdminConfig};
+use sqlx::PgPool;
+use crate::models_single::{LoginRequest, AuthResponse, Claims, AdminConfig, ROLE_ADMIN, ROLE_STAFF};
+use crate::services::staff_service::StaffService;
 
 pub struct AuthService {
     admin_config: AdminConfig,
     jwt_secret: String,
+    staff: StaffService,
 }
 
 impl AuthService {
-    pub fn new(admin_config: AdminConfig, jwt_secret: String) -> Self {
+    pub fn new(pool: PgPool, admin_config: AdminConfig, jwt_secret: String) -> Self {
         Self {
             admin_config,
             jwt_secret,
+            staff: StaffService::new(pool),
         }
     }
 
-    // Логін для admin (один пароль)
+    // Логін для admin (один пароль) або staff (username + пароль)
     pub async fn login(&self, req: LoginRequest) -> Result<AuthResponse> {
-        // Перевірка пароля
-        let password_valid = bcrypt::verify(&req.password, &self.admin_config.password_hash)?;
-        
-        if !password_valid {
-            return Err(anyhow!("Invalid password"));
-        }
+        let username = req.username.as_deref().map(str::trim).filter(|u| !u.is_empty());
+
+        let (sub, role) = match username {
+            Some(username) if username != self.admin_config.username => {
+                let user = self
+                    .staff
+                    .authenticate(username, &req.password)
+                    .await?
+                    .ok_or_else(|| anyhow!("Invalid username or password"))?;
+                (user.username, ROLE_STAFF)
+            }
+            _ => {
+                // Перевірка пароля
+                let password_valid = bcrypt::verify(&req.password, &self.admin_config.password_hash)?;
+
+                if !password_valid {
+                    return Err(anyhow!("Invalid password"));
+                }
+                ("admin".to_string(), ROLE_ADMIN)
+            }
+        };
 
         // Генерація JWT токена
-        let token = self.generate_token()?;
+        let token = self.generate_token(sub, role)?;
 
         Ok(AuthResponse { token })
     }
 
     // Генерація JWT токена
-    fn generate_token(&self) -> Result<String> {
+    fn generate_token(&self, sub: String, role: &str) -> Result<String> {
         let expiration = Utc::now()
             .checked_add_signed(chrono::Duration::hours(24))
             .ok_or_else(|| anyhow!("Failed to add expiration time"))?
             .timestamp() as usize;
 
         let claims = Claims {
-            sub: "admin".to_string(),
+            sub,
+            role: role.to_string(),
             exp: expiration,
         };
 
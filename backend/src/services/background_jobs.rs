@@ -0,0 +1,173 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+use crate::services::image_storage::ImageStorage;
+use crate::services::job_queue::{self, Job, JobHandler};
+use crate::services::report_service::ReportService;
+use crate::websocket::WsState;
+
+pub const JOB_ANALYTICS_BROADCAST: &str = "analytics.broadcast";
+pub const JOB_DAILY_REPORT: &str = "report.daily";
+pub const JOB_IMAGE_DELETE: &str = "image.delete";
+pub const JOB_LOYALTY_CREDIT: &str = "loyalty.credit";
+
+/// Push fresh dashboard analytics to WebSocket clients
+pub struct AnalyticsBroadcastJob {
+    ws_state: Arc<WsState>,
+}
+
+impl AnalyticsBroadcastJob {
+    pub fn new(ws_state: Arc<WsState>) -> Self {
+        Self { ws_state }
+    }
+}
+
+#[async_trait]
+impl JobHandler for AnalyticsBroadcastJob {
+    fn kind(&self) -> &'static str {
+        JOB_ANALYTICS_BROADCAST
+    }
+
+    async fn run(&self, _job: &Job) -> anyhow::Result<()> {
+        self.ws_state.send_analytics_update().await;
+        Ok(())
+    }
+}
+
+/// Remove an image from the configured storage: payload `{ "public_id": "..." }`
+pub struct ImageDeleteJob {
+    storage: Arc<dyn ImageStorage>,
+}
+
+impl ImageDeleteJob {
+    pub fn new(storage: Arc<dyn ImageStorage>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl JobHandler for ImageDeleteJob {
+    fn kind(&self) -> &'static str {
+        JOB_IMAGE_DELETE
+    }
+
+    async fn run(&self, job: &Job) -> anyhow::Result<()> {
+        let public_id = job.payload["public_id"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing public_id"))?;
+
+        self.storage.delete(public_id).await
+    }
+}
+
+/// Credit loyalty points for a delivered order: payload `{ "order_id": 1 }`.
+/// One point per full złoty of the order total; credited at most once per order.
+pub struct LoyaltyCreditJob {
+    pool: PgPool,
+}
+
+impl LoyaltyCreditJob {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl JobHandler for LoyaltyCreditJob {
+    fn kind(&self) -> &'static str {
+        JOB_LOYALTY_CREDIT
+    }
+
+    async fn run(&self, job: &Job) -> anyhow::Result<()> {
+        let order_id = job.payload["order_id"]
+            .as_i64()
+            .ok_or_else(|| anyhow::anyhow!("Missing order_id"))? as i32;
+
+        let mut tx = self.pool.begin().await?;
+
+        // Guest orders have no account to credit
+        let credited: Option<(i32, i32)> = sqlx::query_as(
+            r#"
+            INSERT INTO loyalty_transactions (customer_id, order_id, points)
+            SELECT customer_id, id, FLOOR(total)::INTEGER
+            FROM orders
+            WHERE id = $1 AND customer_id IS NOT NULL AND status = 'delivered'
+            ON CONFLICT (order_id) DO NOTHING
+            RETURNING customer_id, points
+            "#,
+        )
+        .bind(order_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some((customer_id, points)) = credited {
+            sqlx::query("UPDATE customers SET loyalty_points = loyalty_points + $2 WHERE id = $1")
+                .bind(customer_id)
+                .bind(points)
+                .execute(&mut *tx)
+                .await?;
+
+            tracing::info!("⭐ Credited {} points to customer {} for order {}", points, customer_id, order_id);
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
+
+/// Store the sales summary of a closed day in `daily_reports`: payload
+/// `{ "date": "2024-05-01" }`, the day that ended last when omitted.
+/// Queues the run for the next day first, so a failing day does not stop
+/// the schedule.
+pub struct DailyReportJob {
+    pool: PgPool,
+    reports: Arc<ReportService>,
+}
+
+impl DailyReportJob {
+    pub fn new(pool: PgPool, reports: Arc<ReportService>) -> Self {
+        Self { pool, reports }
+    }
+}
+
+#[async_trait]
+impl JobHandler for DailyReportJob {
+    fn kind(&self) -> &'static str {
+        JOB_DAILY_REPORT
+    }
+
+    async fn run(&self, job: &Job) -> anyhow::Result<()> {
+        let (last_closed, next_run) = self.reports.last_closed_day().await?;
+        let date = match job.payload.get("date") {
+            Some(date) => serde_json::from_value(date.clone())?,
+            None => last_closed,
+        };
+
+        // A backfill for a given date does not continue the schedule. Retries
+        // of this run find the next one already queued.
+        if job.payload.get("date").is_none() {
+            let queued: bool = sqlx::query_scalar(
+                r#"
+                SELECT EXISTS(
+                    SELECT 1 FROM jobs
+                    WHERE kind = $1 AND status = 'queued' AND id <> $2 AND NOT payload ? 'date'
+                )
+                "#,
+            )
+            .bind(JOB_DAILY_REPORT)
+            .bind(job.id)
+            .fetch_one(&self.pool)
+            .await?;
+            if !queued {
+                job_queue::enqueue(&self.pool, JOB_DAILY_REPORT, serde_json::json!({}), Some(next_run)).await?;
+            }
+        }
+
+        // Upserts, so running a day again is harmless
+        let report = self.reports.close_day(date).await?;
+        tracing::info!("📊 Daily report for {}: {} orders, {} revenue", date, report.orders, report.revenue);
+        Ok(())
+    }
+}
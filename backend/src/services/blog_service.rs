@@ -0,0 +1,278 @@
+use chrono::Utc;
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+use sqlx::PgPool;
+
+use crate::error::{AppError, Result};
+use crate::listing::{ListParams, ListQuery, Page, Sql};
+use crate::models_single::{BlogPost, BlogPostFilters, CreateBlogPost, UpdateBlogPost, BLOG_POST_STATUSES};
+
+const POST_COLUMNS: &str = r#"id, slug, title, excerpt, content, date, author, image, category, read_time,
+    status, published_at, created_at, updated_at"#;
+
+// Published and not scheduled for later
+const VISIBLE: &str = "status = 'published' AND published_at <= NOW()";
+
+const SORT_FIELDS: &[(&str, &str)] = &[("date", "date"), ("title", "title"), ("published_at", "published_at")];
+
+// URL schemes allowed in links and images; relative URLs are always allowed
+const SAFE_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+pub struct BlogService {
+    pool: PgPool,
+}
+
+impl BlogService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list_published(&self, filters: BlogPostFilters, params: &ListParams) -> Result<Page<BlogPost>> {
+        let mut list = ListQuery::new(POST_COLUMNS, "blog_posts");
+        list.filter(Sql::new(VISIBLE))
+            .filter_opt("category = ", filters.category.filter(|c| !c.is_empty()))
+            .sort(params, SORT_FIELDS, "date DESC, published_at DESC")?
+            .paginate(params)?;
+
+        Ok(list.fetch_page::<BlogPost>(&self.pool).await?.map(render))
+    }
+
+    pub async fn get_published(&self, slug: &str) -> Result<BlogPost> {
+        let post = sqlx::query_as::<_, BlogPost>(&format!(
+            "SELECT {} FROM blog_posts WHERE slug = $1 AND {}",
+            POST_COLUMNS, VISIBLE
+        ))
+        .bind(slug)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Blog post '{}' not found", slug)))?;
+
+        Ok(render(post))
+    }
+
+    /// Drafts and scheduled posts included
+    pub async fn list_all(&self, filters: BlogPostFilters, params: &ListParams) -> Result<Page<BlogPost>> {
+        let mut list = ListQuery::new(POST_COLUMNS, "blog_posts");
+        list.filter_opt("category = ", filters.category.filter(|c| !c.is_empty()))
+            .filter_opt("status = ", filters.status.filter(|s| !s.is_empty()))
+            .sort(params, SORT_FIELDS, "date DESC, created_at DESC")?
+            .paginate(params)?;
+
+        Ok(list.fetch_page::<BlogPost>(&self.pool).await?.map(render))
+    }
+
+    pub async fn get(&self, id: &str) -> Result<BlogPost> {
+        let post = sqlx::query_as::<_, BlogPost>(&format!("SELECT {} FROM blog_posts WHERE id = $1", POST_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Blog post with ID {} not found", id)))?;
+
+        Ok(render(post))
+    }
+
+    pub async fn create(&self, payload: CreateBlogPost) -> Result<BlogPost> {
+        validate_slug(&payload.slug)?;
+        validate_title(&payload.title)?;
+        let status = payload.status.unwrap_or_else(|| "draft".to_string());
+        validate_status(&status)?;
+        self.check_slug(&payload.slug, None).await?;
+
+        // Publishing without a date means publishing now
+        let published_at = match (status.as_str(), payload.published_at) {
+            ("published", None) => Some(Utc::now()),
+            (_, published_at) => published_at,
+        };
+
+        let post = sqlx::query_as::<_, BlogPost>(&format!(
+            r#"
+            INSERT INTO blog_posts (id, slug, title, excerpt, content, date, author, image, category,
+                                    read_time, status, published_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            RETURNING {}
+            "#,
+            POST_COLUMNS
+        ))
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(&payload.slug)
+        .bind(payload.title.trim())
+        .bind(&payload.excerpt)
+        .bind(&payload.content)
+        .bind(payload.date)
+        .bind(&payload.author)
+        .bind(&payload.image)
+        .bind(&payload.category)
+        .bind(&payload.read_time)
+        .bind(&status)
+        .bind(published_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(render(post))
+    }
+
+    pub async fn update(&self, id: &str, payload: UpdateBlogPost) -> Result<BlogPost> {
+        let current = self.get(id).await?;
+
+        if let Some(slug) = &payload.slug {
+            validate_slug(slug)?;
+            self.check_slug(slug, Some(id)).await?;
+        }
+        if let Some(title) = &payload.title {
+            validate_title(title)?;
+        }
+        if let Some(status) = &payload.status {
+            validate_status(status)?;
+        }
+
+        let status = payload.status.unwrap_or(current.status);
+        let published_at = match (status.as_str(), payload.published_at.or(current.published_at)) {
+            ("published", None) => Some(Utc::now()),
+            (_, published_at) => published_at,
+        };
+
+        let post = sqlx::query_as::<_, BlogPost>(&format!(
+            r#"
+            UPDATE blog_posts
+            SET slug = COALESCE($2, slug),
+                title = COALESCE($3, title),
+                excerpt = COALESCE($4, excerpt),
+                content = COALESCE($5, content),
+                date = COALESCE($6, date),
+                author = COALESCE($7, author),
+                image = COALESCE($8, image),
+                category = COALESCE($9, category),
+                read_time = COALESCE($10, read_time),
+                status = $11,
+                published_at = $12,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            POST_COLUMNS
+        ))
+        .bind(id)
+        .bind(&payload.slug)
+        .bind(payload.title.as_deref().map(str::trim))
+        .bind(&payload.excerpt)
+        .bind(&payload.content)
+        .bind(payload.date)
+        .bind(&payload.author)
+        .bind(&payload.image)
+        .bind(&payload.category)
+        .bind(&payload.read_time)
+        .bind(&status)
+        .bind(published_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(render(post))
+    }
+
+    pub async fn delete(&self, id: &str) -> Result<()> {
+        let result = sqlx::query("DELETE FROM blog_posts WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Blog post with ID {} not found", id)));
+        }
+        Ok(())
+    }
+
+    async fn check_slug(&self, slug: &str, except_id: Option<&str>) -> Result<()> {
+        let taken: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM blog_posts WHERE slug = $1 AND id IS DISTINCT FROM $2)",
+        )
+        .bind(slug)
+        .bind(except_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        if taken {
+            return Err(AppError::Validation("Slug already exists".to_string()));
+        }
+        Ok(())
+    }
+}
+
+fn validate_slug(slug: &str) -> Result<()> {
+    let valid = !slug.is_empty()
+        && slug.len() <= 255
+        && slug.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !slug.starts_with('-')
+        && !slug.ends_with('-');
+
+    if !valid {
+        return Err(AppError::Validation(
+            "Slug must contain only lowercase letters, digits and dashes".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_title(title: &str) -> Result<()> {
+    if title.trim().is_empty() || title.len() > 500 {
+        return Err(AppError::Validation("Title must be 1-500 characters".to_string()));
+    }
+    Ok(())
+}
+
+fn validate_status(status: &str) -> Result<()> {
+    if !BLOG_POST_STATUSES.contains(&status) {
+        return Err(AppError::Validation(format!(
+            "Invalid status '{}', expected one of: {}",
+            status,
+            BLOG_POST_STATUSES.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+fn render(mut post: BlogPost) -> BlogPost {
+    post.content_html = render_markdown(&post.content);
+    post
+}
+
+/// Markdown to HTML safe to embed as is: raw HTML in the source is
+/// escaped and links or images with scripting URLs are neutralized
+pub fn render_markdown(markdown: &str) -> String {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+
+    let events = Parser::new_ext(markdown, options).map(|event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        Event::Start(Tag::Link { link_type, dest_url, title, id }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image { link_type, dest_url, title, id }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        }),
+        event => event,
+    });
+
+    let mut out = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut out, events);
+    out
+}
+
+fn safe_url(url: CowStr<'_>) -> CowStr<'_> {
+    // Browsers ignore whitespace and control characters inside the scheme
+    let cleaned: String = url
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect::<String>()
+        .to_ascii_lowercase();
+
+    match cleaned.split_once(':') {
+        Some((scheme, _)) if !scheme.contains(['/', '?', '#']) && !SAFE_SCHEMES.contains(&scheme) => {
+            CowStr::Borrowed("#")
+        }
+        _ => url,
+    }
+}
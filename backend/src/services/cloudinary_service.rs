@@ -1,10 +1,13 @@
 use anyhow::{Result, anyhow};
+use async_trait::async_trait;
 use reqwest::multipart::{Form, Part};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use sha1::{Sha1, Digest};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::services::image_storage::{ImageStorage, StoredImage};
+
 #[derive(Debug, Clone)]
 pub struct CloudinaryConfig {
     pub cloud_name: String,
@@ -44,6 +47,46 @@ impl CloudinaryService {
         }
     }
 
+    /// Form with `api_key`, `timestamp` and `signature` for a signed API call
+    fn signed_form(&self, mut params: Vec<(&'static str, String)>) -> Form {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            .to_string();
+        params.push(("timestamp", timestamp));
+
+        let signature = sign_params(&params, &self.config.api_secret);
+
+        params
+            .into_iter()
+            .fold(Form::new(), |form, (name, value)| form.text(name, value))
+            .text("api_key", self.config.api_key.clone())
+            .text("signature", signature)
+    }
+
+    /// POST to `image/{action}` of the Upload API
+    async fn call(&self, action: &str, form: Form) -> Result<reqwest::Response> {
+        let url = format!(
+            "https://api.cloudinary.com/v1_1/{}/image/{}",
+            self.config.cloud_name, action
+        );
+
+        let response = self.client
+            .post(&url)
+            .multipart(form)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            tracing::error!("❌ Cloudinary {} failed: {}", action, error_text);
+            return Err(anyhow!("Cloudinary {} failed: {}", action, error_text));
+        }
+
+        Ok(response)
+    }
+
     /// Upload image to Cloudinary using SIGNED upload
     pub async fn upload_image(
         &self,
@@ -56,57 +99,21 @@ impl CloudinaryService {
         
         tracing::info!("📤 Starting Cloudinary upload: {} ({:.2} KB)", filename, file_size_kb);
 
-        let upload_url = format!(
-            "https://api.cloudinary.com/v1_1/{}/image/upload",
-            self.config.cloud_name
-        );
-
         let folder_name = folder.unwrap_or_else(|| "portfolio".to_string());
         let filename_clone = filename.clone();
-        
-        // Generate timestamp for signature
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs()
-            .to_string();
-        
-        // Create signature string (alphabetically ordered params + api_secret)
-        let sig_string = format!(
-            "folder={}&timestamp={}{}",
-            folder_name, timestamp, self.config.api_secret
-        );
-        
-        // Generate SHA1 signature
-        let mut hasher = Sha1::new();
-        hasher.update(sig_string.as_bytes());
-        let signature = format!("{:x}", hasher.finalize());
-        
-        tracing::info!("🔐 Generated signature for timestamp: {}", timestamp);
 
-        // Use signed upload
-        let form = Form::new()
-            .part("file", Part::bytes(file_data).file_name(filename))
-            .text("folder", folder_name)
-            .text("timestamp", timestamp)
-            .text("api_key", self.config.api_key.clone())
-            .text("signature", signature);
+        let form = self
+            .signed_form(vec![
+                ("folder", folder_name),
+                ("upload_preset", self.config.upload_preset.clone()),
+            ])
+            .part("file", Part::bytes(file_data).file_name(filename));
 
-        let response = self.client
-            .post(&upload_url)
-            .multipart(form)
-            .send()
-            .await?;
+        let response = self.call("upload", form).await?;
 
         let elapsed = start_time.elapsed();
         tracing::info!("⏱️ Cloudinary response received in {:.2}s", elapsed.as_secs_f64());
 
-        if !response.status().is_success() {
-            let error_text = response.text().await?;
-            tracing::error!("❌ Cloudinary upload failed: {}", error_text);
-            return Err(anyhow!("Cloudinary upload failed: {}", error_text));
-        }
-
         let cloudinary_response: CloudinaryResponse = response.json().await?;
         
         tracing::info!(
@@ -119,64 +126,169 @@ impl CloudinaryService {
         Ok(cloudinary_response)
     }
 
-    /// Upload image from base64
+    /// Upload image from base64 (signed, with the configured upload preset).
+    /// Skips image_processing: the upload handlers decode and clean images first.
     pub async fn upload_base64(
         &self,
         base64_data: String,
         folder: Option<String>,
     ) -> Result<CloudinaryResponse> {
-        let upload_url = format!(
-            "https://api.cloudinary.com/v1_1/{}/image/upload",
-            self.config.cloud_name
-        );
-
         let folder_name = folder.unwrap_or_else(|| "portfolio".to_string());
 
-        // Use unsigned upload with preset
-        let form = Form::new()
-            .text("file", base64_data)
-            .text("upload_preset", self.config.upload_preset.clone())
-            .text("folder", folder_name);
-
-        let response = self.client
-            .post(&upload_url)
-            .multipart(form)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(anyhow!("Cloudinary upload failed: {}", error_text));
-        }
+        // `file` is not part of the signature
+        let form = self
+            .signed_form(vec![
+                ("folder", folder_name),
+                ("upload_preset", self.config.upload_preset.clone()),
+            ])
+            .text("file", base64_data);
 
+        let response = self.call("upload", form).await?;
         let cloudinary_response: CloudinaryResponse = response.json().await?;
         Ok(cloudinary_response)
     }
 
-    /// Delete image from Cloudinary (requires admin API)
+    /// Delete image from Cloudinary; a missing image is not an error
     pub async fn delete_image(&self, public_id: String) -> Result<()> {
-        let delete_url = format!(
-            "https://api.cloudinary.com/v1_1/{}/image/destroy",
-            self.config.cloud_name
-        );
+        let form = self.signed_form(vec![
+            ("public_id", public_id.clone()),
+            ("invalidate", "true".to_string()),
+        ]);
 
-        // For delete, we still need to use signed request
-        // This is a simplified version - in production you'd want proper signature
-        let form = Form::new()
-            .text("public_id", public_id)
-            .text("api_key", self.config.api_key.clone());
+        let response = self.call("destroy", form).await?;
 
-        let response = self.client
-            .post(&delete_url)
-            .multipart(form)
-            .send()
-            .await?;
+        // HTTP 200 with {"result": "ok"} or {"result": "not found"}
+        let body: serde_json::Value = response.json().await?;
+        match body["result"].as_str() {
+            Some("ok") | Some("not found") => Ok(()),
+            _ => Err(anyhow!("Cloudinary destroy of {} failed: {}", public_id, body)),
+        }
+    }
 
-        if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(anyhow!("Cloudinary delete failed: {}", error_text));
+    /// Move an image to a new public_id
+    #[allow(dead_code)] // not exposed by the API yet
+    pub async fn rename_image(
+        &self,
+        from_public_id: String,
+        to_public_id: String,
+        overwrite: bool,
+    ) -> Result<CloudinaryResponse> {
+        let form = self.signed_form(vec![
+            ("from_public_id", from_public_id),
+            ("to_public_id", to_public_id),
+            ("overwrite", overwrite.to_string()),
+        ]);
+
+        let response = self.call("rename", form).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Apply actions to an uploaded image, e.g. `("eager", "w_400,h_300,c_fill")`
+    #[allow(dead_code)] // not exposed by the API yet
+    pub async fn explicit_image(
+        &self,
+        public_id: String,
+        mut params: Vec<(&'static str, String)>,
+    ) -> Result<CloudinaryResponse> {
+        params.push(("public_id", public_id));
+        params.push(("type", "upload".to_string()));
+
+        let response = self.call("explicit", self.signed_form(params)).await?;
+        Ok(response.json().await?)
+    }
+}
+
+/// Cloudinary API signature: the parameters sorted by name as `name=value`
+/// joined with `&`, followed by the API secret, SHA-1 hex encoded.
+/// `file`, `cloud_name`, `resource_type` and `api_key` are not signed, and
+/// neither are empty values.
+pub fn sign_params(params: &[(&str, String)], api_secret: &str) -> String {
+    let mut signed: Vec<&(&str, String)> = params
+        .iter()
+        .filter(|(name, value)| {
+            !value.is_empty() && !["file", "cloud_name", "resource_type", "api_key", "signature"].contains(name)
+        })
+        .collect();
+    signed.sort_by_key(|(name, _)| *name);
+
+    let to_sign = signed
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    let mut hasher = Sha1::new();
+    hasher.update(to_sign.as_bytes());
+    hasher.update(api_secret.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+impl From<CloudinaryResponse> for StoredImage {
+    fn from(response: CloudinaryResponse) -> Self {
+        Self {
+            url: response.secure_url,
+            public_id: response.public_id,
+            width: response.width,
+            height: response.height,
         }
+    }
+}
+
+#[async_trait]
+impl ImageStorage for CloudinaryService {
+    fn name(&self) -> &'static str {
+        "cloudinary"
+    }
+
+    async fn upload(&self, data: Vec<u8>, filename: &str, folder: Option<&str>) -> Result<StoredImage> {
+        Ok(self.upload_image(data, filename.to_string(), folder.map(str::to_string)).await?.into())
+    }
+
+    async fn delete(&self, public_id: &str) -> Result<()> {
+        self.delete_image(public_id.to_string()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sign_params;
+
+    #[test]
+    fn matches_cloudinary_documentation_example() {
+        let params = [
+            ("timestamp", "1315060510".to_string()),
+            ("public_id", "sample_image".to_string()),
+            ("eager", "w_400,h_300,c_pad|w_260,h_200,c_crop".to_string()),
+        ];
+
+        assert_eq!(sign_params(&params, "abcd"), "bfd09f95f331f558cbd1320e67aa8d488770583e");
+    }
+
+    #[test]
+    fn skips_unsigned_and_empty_params() {
+        let params = [
+            ("timestamp", "1315060510".to_string()),
+            ("public_id", "sample_image".to_string()),
+            ("eager", "w_400,h_300,c_pad|w_260,h_200,c_crop".to_string()),
+            ("file", "data:image/png;base64,AAAA".to_string()),
+            ("api_key", "1234".to_string()),
+            ("resource_type", "image".to_string()),
+            ("cloud_name", "demo".to_string()),
+            ("folder", String::new()),
+        ];
+
+        assert_eq!(sign_params(&params, "abcd"), "bfd09f95f331f558cbd1320e67aa8d488770583e");
+    }
+
+    #[test]
+    fn signs_destroy_request() {
+        // sha1("invalidate=true&public_id=menu/rolls/abc&timestamp=1700000000secret")
+        let params = [
+            ("public_id", "menu/rolls/abc".to_string()),
+            ("invalidate", "true".to_string()),
+            ("timestamp", "1700000000".to_string()),
+        ];
 
-        Ok(())
+        assert_eq!(sign_params(&params, "secret"), "861d4ea3130c5b60ea7e9968ff3591ce0943d2fa");
     }
 }
@@ -0,0 +1,360 @@
+use bigdecimal::BigDecimal;
+use sqlx::{PgConnection, PgPool};
+
+use crate::error::{AppError, Result};
+use crate::models::combo::{Combo, ComboChoice, ComboSelection, ComboSlot, ComboSlotOption, SaveCombo};
+use crate::services::report_service::DEFAULT_TIMEZONE;
+use crate::services::webhook_service;
+
+const SLOT_COLUMNS: &str = "id, name, name_ru, name_pl, min_choices, max_choices, sort_order";
+
+/// One chosen component of a combo, ready to be stored as a child order item
+pub struct ComboComponent {
+    pub slot_name: String,
+    pub choice: ComboChoice,
+    pub quantity: i32,
+}
+
+/// Validated selection for one combo
+pub struct PricedCombo {
+    // Sum of component surcharges for one combo
+    pub surcharge: BigDecimal,
+    // In slot order
+    pub components: Vec<ComboComponent>,
+}
+
+async fn load_slots(conn: &mut PgConnection, combo_item_id: i32) -> Result<Vec<ComboSlot>> {
+    let sql = format!(
+        "SELECT {} FROM combo_slots WHERE combo_item_id = $1 ORDER BY sort_order, id",
+        SLOT_COLUMNS
+    );
+    Ok(sqlx::query_as::<_, ComboSlot>(&sql)
+        .bind(combo_item_id)
+        .fetch_all(&mut *conn)
+        .await?)
+}
+
+/// Items that fit each slot of a combo and are available right now. Combos cannot
+/// be components, and an item-specific option wins over the option of its category.
+async fn load_choices(
+    conn: &mut PgConnection,
+    combo_item_id: i32,
+    only_items: Option<&[i32]>,
+) -> Result<Vec<ComboChoice>> {
+    Ok(sqlx::query_as::<_, ComboChoice>(
+        r#"
+        SELECT DISTINCT ON (s.sort_order, s.id, m.name, m.id)
+               s.id AS slot_id, m.id AS menu_item_id, m.name, m.name_ru, m.name_pl, m.image,
+               o.surcharge, COALESCE(m.allergens, '{}') AS allergens, m.spice_level
+        FROM combo_slots s
+        JOIN combo_slot_options o ON o.slot_id = s.id
+        JOIN menu_items_at(now(), $3) m ON m.id = o.menu_item_id OR m.category_id = o.category_id
+        WHERE s.combo_item_id = $1
+          AND m.id <> $1
+          AND COALESCE(m.is_available, false)
+          AND NOT EXISTS (SELECT 1 FROM combo_slots cs WHERE cs.combo_item_id = m.id)
+          AND ($2::INT[] IS NULL OR m.id = ANY($2))
+        ORDER BY s.sort_order, s.id, m.name, m.id, (o.menu_item_id IS NULL)
+        "#,
+    )
+    .bind(combo_item_id)
+    .bind(only_items)
+    .bind(DEFAULT_TIMEZONE)
+    .fetch_all(&mut *conn)
+    .await?)
+}
+
+/// Checks the components chosen for a combo and prices them.
+/// Returns `None` for regular menu items (which must not have components).
+pub async fn price_combo(
+    conn: &mut PgConnection,
+    combo_item_id: i32,
+    selections: &[ComboSelection],
+) -> Result<Option<PricedCombo>> {
+    let slots = load_slots(conn, combo_item_id).await?;
+    if slots.is_empty() {
+        if !selections.is_empty() {
+            return Err(AppError::Validation(format!(
+                "Menu item {} is not a combo and cannot have components",
+                combo_item_id
+            )));
+        }
+        return Ok(None);
+    }
+
+    let ids: Vec<i32> = selections.iter().map(|s| s.menu_item_id).collect();
+    let choices = load_choices(conn, combo_item_id, Some(&ids)).await?;
+
+    let mut surcharge = BigDecimal::from(0);
+    let mut components = Vec::new();
+
+    for selection in selections {
+        let slot = slots.iter().find(|slot| slot.id == selection.slot_id).ok_or_else(|| {
+            AppError::Validation(format!(
+                "Slot {} does not belong to combo {}",
+                selection.slot_id, combo_item_id
+            ))
+        })?;
+
+        if selection.quantity < 1 {
+            return Err(AppError::Validation(format!(
+                "Quantity for '{}' must be at least 1",
+                slot.name
+            )));
+        }
+
+        let choice = choices
+            .iter()
+            .find(|c| c.slot_id == slot.id && c.menu_item_id == selection.menu_item_id)
+            .ok_or_else(|| {
+                AppError::Validation(format!(
+                    "Menu item {} cannot be chosen for '{}' or is not available",
+                    selection.menu_item_id, slot.name
+                ))
+            })?;
+
+        surcharge += &choice.surcharge * BigDecimal::from(selection.quantity);
+        components.push(ComboComponent {
+            slot_name: slot.name.clone(),
+            choice: choice.clone(),
+            quantity: selection.quantity,
+        });
+    }
+
+    for slot in &slots {
+        // Summed as i64: quantities come from the client and could overflow i32
+        let picked: i64 = selections
+            .iter()
+            .filter(|s| s.slot_id == slot.id)
+            .map(|s| i64::from(s.quantity))
+            .sum();
+        if picked < i64::from(slot.min_choices) || picked > i64::from(slot.max_choices) {
+            let expected = if slot.min_choices == slot.max_choices {
+                slot.min_choices.to_string()
+            } else {
+                format!("{}-{}", slot.min_choices, slot.max_choices)
+            };
+            return Err(AppError::Validation(format!(
+                "'{}' needs {} choice(s), got {}",
+                slot.name, expected, picked
+            )));
+        }
+    }
+
+    components.sort_by_key(|c| slots.iter().position(|slot| slot.id == c.choice.slot_id));
+
+    Ok(Some(PricedCombo { surcharge, components }))
+}
+
+/// Combo definitions (slots and their options) of menu items
+pub struct ComboService {
+    pool: PgPool,
+}
+
+impl ComboService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get(&self, combo_item_id: i32) -> Result<Combo> {
+        let mut conn = self.pool.acquire().await?;
+
+        let exists: bool =
+            sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM menu_items WHERE id = $1 AND deleted_at IS NULL)")
+                .bind(combo_item_id)
+                .fetch_one(&mut *conn)
+                .await?;
+        if !exists {
+            return Err(AppError::NotFound(format!("Menu item with ID {} not found", combo_item_id)));
+        }
+
+        let mut slots = load_slots(&mut conn, combo_item_id).await?;
+        if slots.is_empty() {
+            return Err(AppError::NotFound(format!("Menu item {} is not a combo", combo_item_id)));
+        }
+
+        let slot_ids: Vec<i32> = slots.iter().map(|slot| slot.id).collect();
+        let options = sqlx::query_as::<_, ComboSlotOption>(
+            r#"
+            SELECT id, slot_id, category_id, menu_item_id, surcharge
+            FROM combo_slot_options
+            WHERE slot_id = ANY($1)
+            ORDER BY id
+            "#,
+        )
+        .bind(&slot_ids)
+        .fetch_all(&mut *conn)
+        .await?;
+        let choices = load_choices(&mut conn, combo_item_id, None).await?;
+
+        for slot in &mut slots {
+            slot.options = options.iter().filter(|o| o.slot_id == slot.id).cloned().collect();
+            slot.choices = choices.iter().filter(|c| c.slot_id == slot.id).cloned().collect();
+        }
+
+        Ok(Combo { menu_item_id: combo_item_id, slots })
+    }
+
+    /// Replaces all slots of the combo; turns a regular item into a combo
+    pub async fn set(&self, combo_item_id: i32, input: SaveCombo) -> Result<Combo> {
+        let mut tx = self.pool.begin().await?;
+
+        let exists: bool =
+            sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM menu_items WHERE id = $1 AND deleted_at IS NULL)")
+                .bind(combo_item_id)
+                .fetch_one(&mut *tx)
+                .await?;
+        if !exists {
+            return Err(AppError::NotFound(format!("Menu item with ID {} not found", combo_item_id)));
+        }
+
+        let mut errors = Vec::new();
+
+        let is_component: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM combo_slot_options WHERE menu_item_id = $1)",
+        )
+        .bind(combo_item_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if is_component {
+            errors.push(format!(
+                "Menu item {} is a component of another combo and cannot be a combo itself",
+                combo_item_id
+            ));
+        }
+
+        if input.slots.is_empty() {
+            errors.push("A combo needs at least one slot".to_string());
+        }
+
+        let mut item_ids = Vec::new();
+        let mut category_ids = Vec::new();
+        for (index, slot) in input.slots.iter().enumerate() {
+            let label = format!("slots[{}]", index);
+            if slot.name.trim().is_empty() {
+                errors.push(format!("{}: name is required", label));
+            }
+            let min = slot.min_choices.unwrap_or(1);
+            let max = slot.max_choices.unwrap_or_else(|| min.max(1));
+            if min < 0 || max < 1 || max < min {
+                errors.push(format!(
+                    "{}: min_choices must be >= 0 and max_choices >= max(1, min_choices)",
+                    label
+                ));
+            }
+            if slot.options.is_empty() {
+                errors.push(format!("{}: at least one option is required", label));
+            }
+            for option in &slot.options {
+                match (option.category_id, option.menu_item_id) {
+                    (Some(category_id), None) => category_ids.push(category_id),
+                    (None, Some(menu_item_id)) if menu_item_id == combo_item_id => {
+                        errors.push(format!("{}: a combo cannot contain itself", label))
+                    }
+                    (None, Some(menu_item_id)) => item_ids.push(menu_item_id),
+                    _ => errors.push(format!(
+                        "{}: each option needs exactly one of category_id or menu_item_id",
+                        label
+                    )),
+                }
+                if option.surcharge.as_ref().is_some_and(|s| s < &BigDecimal::from(0)) {
+                    errors.push(format!("{}: surcharge cannot be negative", label));
+                }
+            }
+        }
+
+        let valid_items: Vec<i32> = sqlx::query_scalar(
+            r#"
+            SELECT id FROM menu_items m
+            WHERE id = ANY($1) AND deleted_at IS NULL
+              AND NOT EXISTS (SELECT 1 FROM combo_slots cs WHERE cs.combo_item_id = m.id)
+            "#,
+        )
+        .bind(&item_ids)
+        .fetch_all(&mut *tx)
+        .await?;
+        for id in item_ids.iter().filter(|id| !valid_items.contains(id)) {
+            errors.push(format!("Menu item {} does not exist or is a combo", id));
+        }
+
+        let valid_categories: Vec<i32> =
+            sqlx::query_scalar("SELECT id FROM categories WHERE id = ANY($1) AND deleted_at IS NULL")
+                .bind(&category_ids)
+                .fetch_all(&mut *tx)
+                .await?;
+        for id in category_ids.iter().filter(|id| !valid_categories.contains(id)) {
+            errors.push(format!("Category with ID {} not found", id));
+        }
+
+        if !errors.is_empty() {
+            return Err(AppError::Validation(errors.join("; ")));
+        }
+
+        sqlx::query("DELETE FROM combo_slots WHERE combo_item_id = $1")
+            .bind(combo_item_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for (index, slot) in input.slots.iter().enumerate() {
+            let min = slot.min_choices.unwrap_or(1);
+            let max = slot.max_choices.unwrap_or_else(|| min.max(1));
+            let slot_id: i32 = sqlx::query_scalar(
+                r#"
+                INSERT INTO combo_slots (combo_item_id, name, name_ru, name_pl, min_choices, max_choices, sort_order)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                RETURNING id
+                "#,
+            )
+            .bind(combo_item_id)
+            .bind(slot.name.trim())
+            .bind(slot.name_ru.as_deref().map(str::trim).unwrap_or_default())
+            .bind(slot.name_pl.as_deref().map(str::trim).unwrap_or_default())
+            .bind(min)
+            .bind(max)
+            .bind(index as i32)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            for option in &slot.options {
+                sqlx::query(
+                    r#"
+                    INSERT INTO combo_slot_options (slot_id, category_id, menu_item_id, surcharge)
+                    VALUES ($1, $2, $3, $4)
+                    "#,
+                )
+                .bind(slot_id)
+                .bind(option.category_id)
+                .bind(option.menu_item_id)
+                .bind(option.surcharge.clone().unwrap_or_else(|| BigDecimal::from(0)))
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        webhook_service::dispatch_menu_change(&mut tx, "menu_item", "combo_updated", combo_item_id, None)
+            .await?;
+
+        tx.commit().await?;
+
+        self.get(combo_item_id).await
+    }
+
+    /// Turns a combo back into a regular menu item
+    pub async fn delete(&self, combo_item_id: i32) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query("DELETE FROM combo_slots WHERE combo_item_id = $1")
+            .bind(combo_item_id)
+            .execute(&mut *tx)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Menu item {} is not a combo", combo_item_id)));
+        }
+
+        webhook_service::dispatch_menu_change(&mut tx, "menu_item", "combo_deleted", combo_item_id, None)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
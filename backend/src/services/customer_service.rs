@@ -0,0 +1,332 @@
+use crate::error::{AppError, Result};
+use crate::models::customer::{
+    Customer, CustomerAddress, CustomerAuthResponse, CustomerClaims, CustomerLogin,
+    CreateCustomerAddress, RegisterCustomer, UpdateCustomerAddress, VerifyOtp,
+};
+use crate::models::restaurant::OrderWithItems;
+use crate::repositories::customer_repository::CustomerRepository;
+use crate::services::sms_sender::SmsSender;
+use bcrypt::{hash, verify, DEFAULT_COST};
+use chrono::{Duration, Utc};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use std::sync::Arc;
+
+const CUSTOMER_ROLE: &str = "customer";
+const OTP_TTL_MINUTES: i64 = 10;
+const OTP_MAX_ATTEMPTS: i32 = 5;
+const OTP_MAX_PER_HOUR: i64 = 5;
+// Calling code for numbers given without one
+const DEFAULT_COUNTRY_CODE: &str = "48";
+
+/// Customer accounts: email/password and phone OTP login, saved addresses, order history.
+/// Tokens issued here carry `CustomerClaims` and are rejected by the staff middleware.
+pub struct CustomerService {
+    repo: CustomerRepository,
+    sms: Arc<dyn SmsSender>,
+    jwt_secret: String,
+}
+
+impl CustomerService {
+    pub fn new(repo: CustomerRepository, sms: Arc<dyn SmsSender>, jwt_secret: String) -> Self {
+        Self {
+            repo,
+            sms,
+            jwt_secret,
+        }
+    }
+
+    pub async fn register(&self, req: RegisterCustomer) -> Result<CustomerAuthResponse> {
+        let name = req.name.trim();
+        let email = req.email.trim().to_lowercase();
+        let phone = normalize_phone(&req.phone)?;
+
+        if name.is_empty() {
+            return Err(AppError::Validation("Name is required".to_string()));
+        }
+        if !email.contains('@') {
+            return Err(AppError::Validation("Invalid email".to_string()));
+        }
+        if req.password.len() < 8 {
+            return Err(AppError::Validation("Password must be at least 8 characters".to_string()));
+        }
+
+        // Concurrent sign-ups that pass this check are stopped by the unique
+        // constraints in `create`
+        if self.repo.find_by_email(&email).await?.is_some()
+            || self.repo.find_by_phone(&phone).await?.is_some()
+        {
+            return Err(AppError::UserAlreadyExists);
+        }
+
+        let password_hash = hash(&req.password, DEFAULT_COST)
+            .map_err(|_| AppError::InternalError)?;
+
+        // Phone is not verified yet, so guest orders are linked only after OTP verification
+        let customer = self
+            .repo
+            .create(name, Some(&email), &phone, Some(&password_hash), false)
+            .await?;
+
+        self.auth_response(customer)
+    }
+
+    pub async fn login(&self, req: CustomerLogin) -> Result<CustomerAuthResponse> {
+        let customer = self
+            .repo
+            .find_by_email(req.email.trim())
+            .await?
+            .ok_or(AppError::InvalidCredentials)?;
+
+        let password_hash = customer
+            .password_hash
+            .as_deref()
+            .ok_or(AppError::InvalidCredentials)?;
+
+        let valid = verify(&req.password, password_hash)
+            .map_err(|_| AppError::InvalidCredentials)?;
+
+        if !valid {
+            return Err(AppError::InvalidCredentials);
+        }
+
+        self.auth_response(customer)
+    }
+
+    /// Send a one-time login code to the phone
+    pub async fn request_otp(&self, phone: &str) -> Result<()> {
+        let phone = normalize_phone(phone)?;
+
+        let recent = self
+            .repo
+            .count_recent_otps(&phone, Utc::now() - Duration::hours(1))
+            .await?;
+        if recent >= OTP_MAX_PER_HOUR {
+            return Err(AppError::TooManyRequests(
+                "Too many codes requested, try again later".to_string(),
+            ));
+        }
+
+        let code = {
+            use rand::Rng;
+            format!("{:06}", rand::thread_rng().gen_range(0..1_000_000))
+        };
+        let code_hash = hash(&code, DEFAULT_COST).map_err(|_| AppError::InternalError)?;
+
+        self.repo
+            .create_otp(&phone, &code_hash, Utc::now() + Duration::minutes(OTP_TTL_MINUTES))
+            .await?;
+
+        self.sms
+            .send(&phone, &format!("FodiFood: your login code is {}", code))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Check a login code. Signs the customer up when the phone has no account yet
+    /// and links earlier guest orders placed with this phone.
+    pub async fn verify_otp(&self, req: VerifyOtp) -> Result<CustomerAuthResponse> {
+        let phone = normalize_phone(&req.phone)?;
+
+        let otp = self
+            .repo
+            .latest_otp(&phone)
+            .await?
+            .ok_or(AppError::InvalidCredentials)?;
+
+        if otp.expires_at < Utc::now() || otp.attempts >= OTP_MAX_ATTEMPTS {
+            return Err(AppError::InvalidCredentials);
+        }
+
+        let valid = verify(req.code.trim(), &otp.code_hash)
+            .map_err(|_| AppError::InvalidCredentials)?;
+        if !valid {
+            self.repo.increment_otp_attempts(otp.id).await?;
+            return Err(AppError::InvalidCredentials);
+        }
+
+        // Resolve the account before consuming the code, so a missing name can be retried
+        let customer = match self.repo.find_by_phone(&phone).await? {
+            Some(customer) => {
+                self.repo.consume_otp(otp.id).await?;
+                self.repo.mark_phone_verified(customer.id).await?
+            }
+            None => {
+                let name = req
+                    .name
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .ok_or_else(|| {
+                        AppError::Validation("Name is required to create an account".to_string())
+                    })?;
+                self.repo.consume_otp(otp.id).await?;
+                self.repo.create(name, None, &phone, None, true).await?
+            }
+        };
+
+        let linked = self.repo.link_guest_orders(customer.id, &phone).await?;
+        if linked > 0 {
+            tracing::info!("🔗 Linked {} guest orders to customer {}", linked, customer.id);
+        }
+
+        self.auth_response(customer)
+    }
+
+    pub async fn profile(&self, customer_id: i32) -> Result<Customer> {
+        self.repo.get_by_id(customer_id).await
+    }
+
+    pub async fn orders(&self, customer_id: i32) -> Result<Vec<OrderWithItems>> {
+        self.repo.orders(customer_id).await
+    }
+
+    // ===== ADDRESSES =====
+
+    pub async fn addresses(&self, customer_id: i32) -> Result<Vec<CustomerAddress>> {
+        self.repo.list_addresses(customer_id).await
+    }
+
+    pub async fn create_address(
+        &self,
+        customer_id: i32,
+        data: CreateCustomerAddress,
+    ) -> Result<CustomerAddress> {
+        self.repo.create_address(customer_id, data).await
+    }
+
+    pub async fn update_address(
+        &self,
+        customer_id: i32,
+        id: i32,
+        data: UpdateCustomerAddress,
+    ) -> Result<CustomerAddress> {
+        let mut address = self.repo.get_address(customer_id, id).await?;
+
+        // Update fields if provided
+        if let Some(label) = data.label {
+            address.label = Some(label);
+        }
+        if let Some(street) = data.street {
+            address.street = street;
+        }
+        if let Some(building) = data.building {
+            address.building = building;
+        }
+        if let Some(apartment) = data.apartment {
+            address.apartment = Some(apartment);
+        }
+        if let Some(floor) = data.floor {
+            address.floor = Some(floor);
+        }
+        if let Some(entrance) = data.entrance {
+            address.entrance = Some(entrance);
+        }
+        if let Some(intercom) = data.intercom {
+            address.intercom = Some(intercom);
+        }
+        if let Some(city) = data.city {
+            address.city = city;
+        }
+        if let Some(postal_code) = data.postal_code {
+            address.postal_code = postal_code;
+        }
+        if let Some(country) = data.country {
+            address.country = country;
+        }
+        if let Some(is_default) = data.is_default {
+            address.is_default = is_default;
+        }
+
+        self.repo.save_address(&address).await
+    }
+
+    pub async fn delete_address(&self, customer_id: i32, id: i32) -> Result<()> {
+        self.repo.delete_address(customer_id, id).await
+    }
+
+    // ===== TOKENS =====
+
+    fn auth_response(&self, customer: Customer) -> Result<CustomerAuthResponse> {
+        let token = self.generate_token(customer.id)?;
+        Ok(CustomerAuthResponse { token, customer })
+    }
+
+    fn generate_token(&self, customer_id: i32) -> Result<String> {
+        let expiration = (Utc::now() + Duration::days(30)).timestamp() as usize;
+
+        let claims = CustomerClaims {
+            sub: customer_id,
+            role: CUSTOMER_ROLE.to_string(),
+            exp: expiration,
+        };
+
+        encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(self.jwt_secret.as_bytes()),
+        )
+        .map_err(|_| AppError::InternalError)
+    }
+
+    pub fn validate_token(&self, token: &str) -> Result<CustomerClaims> {
+        let token_data = decode::<CustomerClaims>(
+            token,
+            &DecodingKey::from_secret(self.jwt_secret.as_bytes()),
+            &Validation::default(),
+        )
+        .map_err(|_| AppError::InvalidToken)?;
+
+        if token_data.claims.role != CUSTOMER_ROLE {
+            return Err(AppError::InvalidToken);
+        }
+
+        Ok(token_data.claims)
+    }
+}
+
+/// Normalize a phone number to E.164, Poland (+48) when no country code is given
+/// ("600 100 200", "0048 600-100-200", "+48 600 100 200" -> "+48600100200").
+/// `e164_phone()` in the database does the same for phones stored as typed.
+pub fn normalize_phone(raw: &str) -> Result<String> {
+    let raw = raw.trim();
+    let digits: String = raw.chars().filter(|c| c.is_ascii_digit()).collect();
+
+    let number = if raw.starts_with('+') {
+        digits
+    } else if let Some(international) = digits.strip_prefix("00") {
+        international.to_string()
+    } else if digits.len() == 9 {
+        format!("{}{}", DEFAULT_COUNTRY_CODE, digits)
+    } else {
+        // Country code typed without the +
+        digits
+    };
+
+    if number.len() < 10 || number.len() > 15 {
+        return Err(AppError::Validation("Invalid phone number".to_string()));
+    }
+
+    Ok(format!("+{}", number))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn phones_are_normalized_to_e164() {
+        for raw in ["600100200", "600 100 200", "+48 600-100-200", "0048600100200", "48 600 100 200"] {
+            assert_eq!(normalize_phone(raw).unwrap(), "+48600100200", "{}", raw);
+        }
+        assert_eq!(normalize_phone("+380 44 123 4567").unwrap(), "+380441234567");
+        assert_eq!(normalize_phone("00 49 30 1234567").unwrap(), "+49301234567");
+    }
+
+    #[test]
+    fn invalid_phones_are_rejected() {
+        for raw in ["", "12345", "+48 600", "+1234567890123456", "phone"] {
+            assert!(normalize_phone(raw).is_err(), "{}", raw);
+        }
+    }
+}
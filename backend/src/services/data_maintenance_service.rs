@@ -0,0 +1,179 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+
+use crate::error::Result;
+use crate::services::webhook_service::{EVENT_ORDER_CREATED, EVENT_ORDER_STATUS_CHANGED};
+
+// Placeholder for names removed by anonymization
+const ANONYMIZED_NAME: &str = "Anonymized";
+
+#[derive(Debug, Serialize)]
+pub struct LoyaltyRecompute {
+    // Delivered orders that had no loyalty credit yet
+    pub orders_credited: u64,
+    // Customers whose balance differed from their transactions
+    pub balances_corrected: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AnonymizeReport {
+    pub dry_run: bool,
+    pub customers: u64,
+    pub orders: u64,
+}
+
+/// Offline data upkeep run from `fodi-admin`, never from request handlers
+pub struct DataMaintenanceService {
+    pool: PgPool,
+}
+
+impl DataMaintenanceService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Reports are computed on read; loyalty balances are the stored
+    /// aggregate. Credits delivered orders missed by the loyalty job, then
+    /// sets every balance to the sum of its transactions.
+    pub async fn recompute_loyalty(&self) -> Result<LoyaltyRecompute> {
+        let mut tx = self.pool.begin().await?;
+
+        // Same rule as LoyaltyCreditJob: one point per full złoty
+        let orders_credited = sqlx::query(
+            r#"
+            INSERT INTO loyalty_transactions (customer_id, order_id, points)
+            SELECT customer_id, id, FLOOR(total)::INTEGER
+            FROM orders
+            WHERE customer_id IS NOT NULL AND status = 'delivered'
+            ON CONFLICT (order_id) DO NOTHING
+            "#,
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        let balances_corrected = sqlx::query(
+            r#"
+            UPDATE customers c
+            SET loyalty_points = totals.points
+            FROM (
+                SELECT c2.id, COALESCE(SUM(lt.points), 0)::INTEGER AS points
+                FROM customers c2
+                LEFT JOIN loyalty_transactions lt ON lt.customer_id = c2.id
+                GROUP BY c2.id
+            ) totals
+            WHERE totals.id = c.id AND c.loyalty_points <> totals.points
+            "#,
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        tx.commit().await?;
+
+        Ok(LoyaltyRecompute {
+            orders_credited,
+            balances_corrected,
+        })
+    }
+
+    /// Removes personal data older than `before`: contact and street-level
+    /// address fields of finished orders and of their webhook payloads, and
+    /// accounts with no activity since (addresses deleted, login disabled).
+    /// City, postal code and totals stay for reports. `dry_run` counts the
+    /// rows and rolls back.
+    pub async fn anonymize_customers(&self, before: DateTime<Utc>, dry_run: bool) -> Result<AnonymizeReport> {
+        let mut tx = self.pool.begin().await?;
+
+        let orders: Vec<i32> = sqlx::query_scalar(
+            r#"
+            UPDATE orders
+            SET customer_name = $2, customer_phone = '', customer_email = NULL,
+                delivery_street = '', delivery_building = '', delivery_apartment = NULL,
+                delivery_floor = NULL, delivery_entrance = NULL, delivery_intercom = NULL,
+                delivery_lat = NULL, delivery_lng = NULL, special_instructions = NULL,
+                anonymized_at = NOW()
+            WHERE created_at < $1
+              AND status IN ('delivered', 'cancelled')
+              AND anonymized_at IS NULL
+            RETURNING id
+            "#,
+        )
+        .bind(before)
+        .bind(ANONYMIZED_NAME)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        // Rendered emails and SMS repeat the customer's name and address
+        sqlx::query("DELETE FROM notification_outbox WHERE order_id = ANY($1)")
+            .bind(&orders)
+            .execute(&mut *tx)
+            .await?;
+
+        // Webhook payloads hold a serialized copy of the order; the delivery
+        // log itself stays, with the same fields scrubbed
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET payload = jsonb_set(payload, '{data}', (payload -> 'data') || jsonb_build_object(
+                    'customer_name', $2::TEXT, 'customer_phone', '', 'customer_email', NULL,
+                    'delivery_street', '', 'delivery_building', '', 'delivery_apartment', NULL,
+                    'delivery_floor', NULL, 'delivery_entrance', NULL, 'delivery_intercom', NULL,
+                    'delivery_lat', NULL, 'delivery_lng', NULL, 'special_instructions', NULL
+                )),
+                response_body = NULL
+            WHERE event = ANY($3)
+              AND (payload -> 'data' ->> 'id')::INTEGER = ANY($1)
+            "#,
+        )
+        .bind(&orders)
+        .bind(ANONYMIZED_NAME)
+        .bind(&[EVENT_ORDER_CREATED, EVENT_ORDER_STATUS_CHANGED][..])
+        .execute(&mut *tx)
+        .await?;
+
+        // No login history is kept, so activity means orders; updated_at also
+        // moves on loyalty credits. Phone is unique and required, so it
+        // becomes a per-account placeholder.
+        let customers: Vec<i32> = sqlx::query_scalar(
+            r#"
+            UPDATE customers c
+            SET name = $2, email = NULL, phone = 'anonymized-' || c.id,
+                password_hash = NULL, phone_verified = false, anonymized_at = NOW()
+            WHERE c.created_at < $1
+              AND c.anonymized_at IS NULL
+              AND NOT EXISTS (
+                  SELECT 1 FROM orders o WHERE o.customer_id = c.id AND o.created_at >= $1
+              )
+            RETURNING id
+            "#,
+        )
+        .bind(before)
+        .bind(ANONYMIZED_NAME)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM customer_addresses WHERE customer_id = ANY($1)")
+            .bind(&customers)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query("DELETE FROM customer_otp_codes WHERE created_at < $1")
+            .bind(before)
+            .execute(&mut *tx)
+            .await?;
+
+        if dry_run {
+            tx.rollback().await?;
+        } else {
+            tx.commit().await?;
+        }
+
+        Ok(AnonymizeReport {
+            dry_run,
+            customers: customers.len() as u64,
+            orders: orders.len() as u64,
+        })
+    }
+}
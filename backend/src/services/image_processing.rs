@@ -0,0 +1,367 @@
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::FilterType;
+use image::metadata::Orientation;
+use image::{DynamicImage, ImageDecoder, ImageReader, ImageResult, Limits};
+use img_parts::{Bytes, DynImage, ImageEXIF};
+use std::io::Cursor;
+
+use crate::error::{AppError, Result};
+
+pub const MIN_IMAGE_DIMENSION: u32 = 16;
+pub const MAX_IMAGE_DIMENSION: u32 = 8000;
+pub const MAX_IMAGE_PIXELS: u64 = 40_000_000;
+
+/// Responsive variants generated for every upload: name and the box the
+/// image is scaled down to fit (never up)
+pub const DERIVATIVES: &[(&str, u32, u32)] = &[
+    ("thumbnail", 240, 240),
+    ("card", 800, 600),
+    ("hero", 1920, 1080),
+];
+
+const ORIGINAL_JPEG_QUALITY: u8 = 90;
+const DERIVATIVE_JPEG_QUALITY: u8 = 82;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageFormat {
+    Jpeg,
+    Png,
+    Webp,
+    Avif,
+}
+
+impl ImageFormat {
+    /// Sniffs the magic bytes; the client's filename and content type are ignored
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        match data {
+            [0xFF, 0xD8, 0xFF, ..] => Some(Self::Jpeg),
+            [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, ..] => Some(Self::Png),
+            [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some(Self::Webp),
+            _ if is_avif(data) => Some(Self::Avif),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Jpeg => "jpg",
+            Self::Png => "png",
+            Self::Webp => "webp",
+            Self::Avif => "avif",
+        }
+    }
+}
+
+/// ISO-BMFF `ftyp` box with an `avif`/`avis` major or compatible brand
+fn is_avif(data: &[u8]) -> bool {
+    if data.len() < 16 || &data[4..8] != b"ftyp" {
+        return false;
+    }
+    let box_size = u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as usize;
+    let end = box_size.clamp(16, data.len());
+
+    std::iter::once(&data[8..12])
+        .chain(data[16..end].chunks_exact(4))
+        .any(|brand| brand == b"avif" || brand == b"avis")
+}
+
+#[derive(Debug)]
+pub struct Derivative {
+    pub name: &'static str,
+    pub extension: &'static str,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct ProcessedImage {
+    pub format: ImageFormat,
+    pub width: u32,
+    pub height: u32,
+    // The original without EXIF/XMP metadata, turned upright if EXIF said so
+    pub data: Vec<u8>,
+    pub derivatives: Vec<Derivative>,
+}
+
+fn invalid_image(e: impl std::fmt::Display) -> AppError {
+    AppError::Validation(format!("Could not decode image: {}", e))
+}
+
+fn check_dimensions(width: u32, height: u32) -> Result<()> {
+    if width < MIN_IMAGE_DIMENSION || height < MIN_IMAGE_DIMENSION {
+        return Err(AppError::Validation(format!(
+            "Image is {}x{}; both sides must be at least {}px",
+            width, height, MIN_IMAGE_DIMENSION
+        )));
+    }
+    if width > MAX_IMAGE_DIMENSION || height > MAX_IMAGE_DIMENSION || width as u64 * height as u64 > MAX_IMAGE_PIXELS {
+        return Err(AppError::Validation(format!(
+            "Image is {}x{}; the limit is {}px per side and {} megapixels",
+            width,
+            height,
+            MAX_IMAGE_DIMENSION,
+            MAX_IMAGE_PIXELS / 1_000_000
+        )));
+    }
+    Ok(())
+}
+
+/// Validates an upload and prepares what gets stored. CPU bound; call it
+/// from `spawn_blocking`.
+///
+/// AVIF is recognized but refused: the build has no AVIF decoder, so its
+/// metadata could not be stripped nor derivatives made.
+pub fn process(data: Vec<u8>) -> Result<ProcessedImage> {
+    let format = ImageFormat::detect(&data).ok_or_else(|| {
+        AppError::Validation("Unsupported image type; allowed: JPEG, PNG, WebP".to_string())
+    })?;
+
+    let decode_format = match format {
+        ImageFormat::Jpeg => image::ImageFormat::Jpeg,
+        ImageFormat::Png => image::ImageFormat::Png,
+        ImageFormat::Webp => image::ImageFormat::WebP,
+        ImageFormat::Avif => {
+            return Err(AppError::Validation(
+                "AVIF images are not supported yet; upload JPEG, PNG or WebP".to_string(),
+            ));
+        }
+    };
+
+    let size = imagesize::blob_size(&data).map_err(invalid_image)?;
+    check_dimensions(size.width as u32, size.height as u32)?;
+
+    let mut reader = ImageReader::with_format(Cursor::new(&data), decode_format);
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_IMAGE_DIMENSION);
+    limits.max_image_height = Some(MAX_IMAGE_DIMENSION);
+    reader.limits(limits);
+
+    let mut decoder = reader.into_decoder().map_err(invalid_image)?;
+    let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+    let mut image = DynamicImage::from_decoder(decoder).map_err(invalid_image)?;
+    image.apply_orientation(orientation);
+
+    // Rotated images have to be re-encoded; the rest keep their exact pixels
+    let data = if orientation == Orientation::NoTransforms {
+        strip_metadata(data)?
+    } else {
+        encode(&image, decode_format, ORIGINAL_JPEG_QUALITY).map_err(invalid_image)?
+    };
+
+    // Photos become JPEG, images with transparency PNG
+    let (derivative_format, extension) = if image.color().has_alpha() {
+        (image::ImageFormat::Png, "png")
+    } else {
+        (image::ImageFormat::Jpeg, "jpg")
+    };
+
+    let mut derivatives = Vec::with_capacity(DERIVATIVES.len());
+    for (name, max_width, max_height) in DERIVATIVES {
+        let data = if image.width() > *max_width || image.height() > *max_height {
+            let resized = image.resize(*max_width, *max_height, FilterType::Lanczos3);
+            encode(&resized, derivative_format, DERIVATIVE_JPEG_QUALITY)
+        } else {
+            encode(&image, derivative_format, DERIVATIVE_JPEG_QUALITY)
+        }
+        .map_err(invalid_image)?;
+
+        derivatives.push(Derivative { name, extension, data });
+    }
+
+    Ok(ProcessedImage {
+        format,
+        width: image.width(),
+        height: image.height(),
+        data,
+        derivatives,
+    })
+}
+
+/// Drops EXIF (camera, GPS) and, for JPEG, XMP without re-encoding
+fn strip_metadata(data: Vec<u8>) -> Result<Vec<u8>> {
+    let mut image = DynImage::from_bytes(Bytes::from(data))
+        .map_err(invalid_image)?
+        .ok_or_else(|| invalid_image("unknown container"))?;
+
+    image.set_exif(None);
+    if let DynImage::Jpeg(jpeg) = &mut image {
+        jpeg.remove_segments_by_marker(img_parts::jpeg::markers::APP1);
+    }
+
+    Ok(image.encoder().bytes().to_vec())
+}
+
+fn encode(image: &DynamicImage, format: image::ImageFormat, jpeg_quality: u8) -> ImageResult<Vec<u8>> {
+    let mut out = Cursor::new(Vec::new());
+    match format {
+        image::ImageFormat::Jpeg => image
+            .to_rgb8()
+            .write_with_encoder(JpegEncoder::new_with_quality(&mut out, jpeg_quality))?,
+        image::ImageFormat::WebP if image.color().has_alpha() => {
+            image.to_rgba8().write_with_encoder(WebPEncoder::new_lossless(&mut out))?
+        }
+        image::ImageFormat::WebP => image.to_rgb8().write_with_encoder(WebPEncoder::new_lossless(&mut out))?,
+        _ => image.write_with_encoder(PngEncoder::new(&mut out))?,
+    }
+    Ok(out.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage, Rgba, RgbaImage};
+
+    // TIFF block of an EXIF segment: big endian, one IFD entry
+    // Orientation (0x0112, SHORT) = 6, i.e. rotate 90° clockwise to display
+    const EXIF_ROTATE_90: &[u8] = &[
+        b'M', b'M', 0x00, 0x2A, 0x00, 0x00, 0x00, 0x08, 0x00, 0x01, 0x01, 0x12, 0x00, 0x03, 0x00, 0x00, 0x00,
+        0x01, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+    // Same entry with Orientation = 1 (upright)
+    const EXIF_UPRIGHT: &[u8] = &[
+        b'M', b'M', 0x00, 0x2A, 0x00, 0x00, 0x00, 0x08, 0x00, 0x01, 0x01, 0x12, 0x00, 0x03, 0x00, 0x00, 0x00,
+        0x01, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    fn jpeg(width: u32, height: u32) -> Vec<u8> {
+        let image = DynamicImage::ImageRgb8(RgbImage::from_pixel(width, height, Rgb([200, 80, 40])));
+        encode(&image, image::ImageFormat::Jpeg, 90).unwrap()
+    }
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let image = DynamicImage::ImageRgba8(RgbaImage::from_pixel(width, height, Rgba([0, 120, 255, 128])));
+        encode(&image, image::ImageFormat::Png, 90).unwrap()
+    }
+
+    fn with_exif(data: Vec<u8>, exif: &[u8]) -> Vec<u8> {
+        let mut image = DynImage::from_bytes(Bytes::from(data)).unwrap().unwrap();
+        image.set_exif(Some(Bytes::copy_from_slice(exif)));
+        image.encoder().bytes().to_vec()
+    }
+
+    fn has_exif(data: &[u8]) -> bool {
+        DynImage::from_bytes(Bytes::copy_from_slice(data)).unwrap().unwrap().exif().is_some()
+    }
+
+    fn dimensions(data: &[u8]) -> (u32, u32) {
+        let size = imagesize::blob_size(data).unwrap();
+        (size.width as u32, size.height as u32)
+    }
+
+    fn is_validation_error(result: Result<ProcessedImage>) -> bool {
+        matches!(result, Err(AppError::Validation(_)))
+    }
+
+    #[test]
+    fn detects_the_format_from_magic_bytes() {
+        assert_eq!(ImageFormat::detect(&jpeg(16, 16)), Some(ImageFormat::Jpeg));
+        assert_eq!(ImageFormat::detect(&png(16, 16)), Some(ImageFormat::Png));
+        assert_eq!(ImageFormat::detect(b"RIFF\x24\0\0\0WEBPVP8 "), Some(ImageFormat::Webp));
+        assert_eq!(
+            ImageFormat::detect(b"\0\0\0\x1cftypavif\0\0\0\0avifmif1miaf"),
+            Some(ImageFormat::Avif)
+        );
+        // AVIF listed as a compatible brand only
+        assert_eq!(
+            ImageFormat::detect(b"\0\0\0\x18ftypmif1\0\0\0\0mif1avif"),
+            Some(ImageFormat::Avif)
+        );
+
+        assert_eq!(ImageFormat::detect(b"\0\0\0\x18ftypisom\0\0\0\0isommp42"), None);
+        assert_eq!(ImageFormat::detect(b"GIF89a\x01\0\x01\0"), None);
+        assert_eq!(ImageFormat::detect(b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>"), None);
+        assert_eq!(ImageFormat::detect(b""), None);
+    }
+
+    #[test]
+    fn rejects_content_that_is_not_a_supported_image() {
+        assert!(is_validation_error(process(b"<html></html>".to_vec())));
+        // Right signature, broken body
+        let mut truncated = jpeg(64, 64);
+        truncated.truncate(200);
+        assert!(is_validation_error(process(truncated)));
+    }
+
+    #[test]
+    fn enforces_dimension_and_pixel_limits() {
+        assert!(check_dimensions(MIN_IMAGE_DIMENSION, MAX_IMAGE_DIMENSION).is_ok());
+        assert!(check_dimensions(MIN_IMAGE_DIMENSION - 1, 100).is_err());
+        assert!(check_dimensions(100, MAX_IMAGE_DIMENSION + 1).is_err());
+        // Both sides within the limit, but 49 megapixels
+        assert!(check_dimensions(7000, 7000).is_err());
+
+        assert!(is_validation_error(process(png(8, 8))));
+
+        // The header is checked before decoding, so a forged size is enough
+        let mut huge = png(16, 16);
+        huge[16..20].copy_from_slice(&9000u32.to_be_bytes());
+        huge[20..24].copy_from_slice(&9000u32.to_be_bytes());
+        assert!(is_validation_error(process(huge)));
+    }
+
+    #[test]
+    fn strips_exif_without_touching_the_pixels() {
+        let original = with_exif(jpeg(64, 32), EXIF_UPRIGHT);
+        assert!(has_exif(&original));
+
+        let processed = process(original.clone()).unwrap();
+        assert_eq!(processed.format, ImageFormat::Jpeg);
+        assert!(!has_exif(&processed.data));
+        assert_eq!(dimensions(&processed.data), (64, 32));
+        // Only the metadata segment is gone, the scan data is copied as is
+        assert!(processed.data.len() < original.len());
+        assert!(original.ends_with(&processed.data[processed.data.len() - 64..]));
+    }
+
+    #[test]
+    fn turns_rotated_photos_upright() {
+        let processed = process(with_exif(jpeg(64, 32), EXIF_ROTATE_90)).unwrap();
+
+        assert_eq!((processed.width, processed.height), (32, 64));
+        assert_eq!(dimensions(&processed.data), (32, 64));
+        assert!(!has_exif(&processed.data));
+    }
+
+    #[test]
+    fn derivatives_fit_their_box_keeping_the_aspect_ratio() {
+        let processed = process(jpeg(2400, 1200)).unwrap();
+
+        let sizes: Vec<_> = processed
+            .derivatives
+            .iter()
+            .map(|d| (d.name, d.extension, dimensions(&d.data)))
+            .collect();
+        assert_eq!(
+            sizes,
+            vec![
+                ("thumbnail", "jpg", (240, 120)),
+                ("card", "jpg", (800, 400)),
+                ("hero", "jpg", (1920, 960)),
+            ]
+        );
+    }
+
+    #[test]
+    fn small_images_are_not_upscaled_and_keep_transparency() {
+        let processed = process(png(100, 50)).unwrap();
+
+        assert_eq!(processed.derivatives.len(), DERIVATIVES.len());
+        for derivative in &processed.derivatives {
+            assert_eq!(derivative.extension, "png", "{}", derivative.name);
+            assert_eq!(ImageFormat::detect(&derivative.data), Some(ImageFormat::Png));
+            assert_eq!(dimensions(&derivative.data), (100, 50), "{}", derivative.name);
+        }
+    }
+
+    #[test]
+    fn avif_is_refused_until_it_can_be_decoded() {
+        let avif = b"\0\0\0\x1cftypavif\0\0\0\0avifmif1miaf".to_vec();
+        assert_eq!(ImageFormat::detect(&avif), Some(ImageFormat::Avif));
+
+        match process(avif) {
+            Err(AppError::Validation(message)) => assert!(message.contains("AVIF"), "{}", message),
+            other => panic!("AVIF was not refused: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,330 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+use std::time::Duration;
+
+const DEFAULT_FOLDER: &str = "portfolio";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredImage {
+    pub url: String,
+    // Backend-specific id used to delete the image (Cloudinary public_id, file path or object key)
+    pub public_id: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Where uploaded images live. Selected by the IMAGE_STORAGE secret
+/// (cloudinary, local or s3); handlers only see this trait.
+#[async_trait]
+pub trait ImageStorage: Send + Sync {
+    /// Backend name for logs
+    fn name(&self) -> &'static str;
+
+    async fn upload(&self, data: Vec<u8>, filename: &str, folder: Option<&str>) -> Result<StoredImage>;
+
+    /// Deleting an image that no longer exists is not an error
+    async fn delete(&self, public_id: &str) -> Result<()>;
+}
+
+/// `menu/rolls` style folder: segments of letters, digits, `-` and `_`
+fn sanitize_folder(folder: Option<&str>) -> Result<String> {
+    let folder = folder.map(|f| f.trim().trim_matches('/')).filter(|f| !f.is_empty());
+    let Some(folder) = folder else {
+        return Ok(DEFAULT_FOLDER.to_string());
+    };
+
+    let valid = folder.split('/').all(|segment| {
+        !segment.is_empty() && segment.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    });
+    if !valid {
+        return Err(anyhow!("Invalid folder '{}'", folder));
+    }
+
+    Ok(folder.to_string())
+}
+
+/// File extension from the image content. Anything that is not a known
+/// image is refused, whatever the client's filename says: local files are
+/// served from the API's own origin.
+fn extension(data: &[u8]) -> Result<&'static str> {
+    use imagesize::ImageType;
+
+    match imagesize::image_type(data) {
+        Ok(ImageType::Jpeg) => Ok("jpg"),
+        Ok(ImageType::Png) => Ok("png"),
+        Ok(ImageType::Webp) => Ok("webp"),
+        Ok(ImageType::Gif) => Ok("gif"),
+        Ok(ImageType::Heif(_)) => Ok("avif"),
+        _ => Err(anyhow!("Unsupported image type")),
+    }
+}
+
+fn content_type(key: &str) -> &'static str {
+    match key.rsplit_once('.').map(|(_, ext)| ext) {
+        Some("jpg") => "image/jpeg",
+        Some("png") => "image/png",
+        Some("webp") => "image/webp",
+        Some("gif") => "image/gif",
+        Some("avif") => "image/avif",
+        _ => "application/octet-stream",
+    }
+}
+
+/// New unique object key: `{folder}/{uuid}.{ext}`
+fn object_key(data: &[u8], folder: Option<&str>) -> Result<String> {
+    Ok(format!(
+        "{}/{}.{}",
+        sanitize_folder(folder)?,
+        uuid::Uuid::new_v4(),
+        extension(data)?
+    ))
+}
+
+/// Rejects keys that could escape the storage root
+fn check_key(key: &str) -> Result<()> {
+    let (folder, file) = key.rsplit_once('/').unwrap_or(("", key));
+    sanitize_folder(Some(folder))?;
+    if file.is_empty() || file.starts_with('.') || !file.chars().all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c)) {
+        return Err(anyhow!("Invalid image id '{}'", key));
+    }
+    Ok(())
+}
+
+fn dimensions(data: &[u8]) -> (u32, u32) {
+    imagesize::blob_size(data)
+        .map(|size| (size.width as u32, size.height as u32))
+        .unwrap_or((0, 0))
+}
+
+// ===== LOCAL DISK =====
+
+/// Files under `root`, served by the app at /media (for development and tests)
+pub struct LocalImageStorage {
+    root: PathBuf,
+    // Public URL prefix of /media, e.g. http://localhost:8000/media
+    base_url: String,
+}
+
+impl LocalImageStorage {
+    pub fn new(root: PathBuf, base_url: String) -> Self {
+        Self {
+            root,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl ImageStorage for LocalImageStorage {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    // The key comes from the content, not from the client's filename
+    async fn upload(&self, data: Vec<u8>, _filename: &str, folder: Option<&str>) -> Result<StoredImage> {
+        let key = object_key(&data, folder)?;
+        let path = self.root.join(&key);
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        let (width, height) = dimensions(&data);
+        tokio::fs::write(&path, data).await?;
+
+        Ok(StoredImage {
+            url: format!("{}/{}", self.base_url, key),
+            public_id: key,
+            width,
+            height,
+        })
+    }
+
+    async fn delete(&self, public_id: &str) -> Result<()> {
+        check_key(public_id)?;
+        match tokio::fs::remove_file(self.root.join(public_id)).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+// ===== S3-COMPATIBLE =====
+
+#[derive(Debug, Clone)]
+pub struct S3Config {
+    // e.g. https://s3.eu-central-1.amazonaws.com or https://<account>.r2.cloudflarestorage.com
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    // Public URL prefix of the bucket (CDN); defaults to {endpoint}/{bucket}
+    pub public_url: Option<String>,
+}
+
+/// Any S3-compatible object store (AWS S3, MinIO, Cloudflare R2), path-style
+/// requests signed with AWS Signature Version 4
+pub struct S3ImageStorage {
+    config: S3Config,
+    client: reqwest::Client,
+}
+
+impl S3ImageStorage {
+    pub fn new(config: S3Config) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .connect_timeout(Duration::from_secs(10))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self { config, client }
+    }
+
+    fn object_url(&self, key: &str) -> String {
+        format!("{}/{}/{}", self.config.endpoint.trim_end_matches('/'), self.config.bucket, key)
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        match &self.config.public_url {
+            Some(base) => format!("{}/{}", base.trim_end_matches('/'), key),
+            None => self.object_url(key),
+        }
+    }
+
+    /// Sends a signed request for one object
+    async fn send(&self, method: reqwest::Method, key: &str, body: Vec<u8>) -> Result<reqwest::Response> {
+        let url = reqwest::Url::parse(&self.object_url(key))?;
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            _ => return Err(anyhow!("Invalid S3 endpoint '{}'", self.config.endpoint)),
+        };
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = hex::encode(Sha256::digest(&body));
+
+        let canonical_request = format!(
+            "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\nhost;x-amz-content-sha256;x-amz-date\n{}",
+            method,
+            url.path(),
+            host,
+            payload_hash,
+            amz_date,
+            payload_hash
+        );
+        let scope = format!("{}/{}/s3/aws4_request", date, self.config.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let mut key_bytes = format!("AWS4{}", self.config.secret_access_key).into_bytes();
+        for part in [date.as_str(), self.config.region.as_str(), "s3", "aws4_request"] {
+            key_bytes = hmac_sha256(&key_bytes, part.as_bytes());
+        }
+        let signature = hex::encode(hmac_sha256(&key_bytes, string_to_sign.as_bytes()));
+
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature={}",
+            self.config.access_key_id, scope, signature
+        );
+
+        Ok(self.client
+            .request(method, url)
+            .header("x-amz-date", amz_date)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("authorization", authorization)
+            .header("content-type", content_type(key))
+            .body(body)
+            .send()
+            .await?)
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+#[async_trait]
+impl ImageStorage for S3ImageStorage {
+    fn name(&self) -> &'static str {
+        "s3"
+    }
+
+    // The key comes from the content, not from the client's filename
+    async fn upload(&self, data: Vec<u8>, _filename: &str, folder: Option<&str>) -> Result<StoredImage> {
+        let key = object_key(&data, folder)?;
+        let (width, height) = dimensions(&data);
+
+        let response = self.send(reqwest::Method::PUT, &key, data).await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("S3 upload returned {}: {}", status, error_text));
+        }
+
+        Ok(StoredImage {
+            url: self.public_url(&key),
+            public_id: key,
+            width,
+            height,
+        })
+    }
+
+    async fn delete(&self, public_id: &str) -> Result<()> {
+        check_key(public_id)?;
+
+        // S3 answers 204 for missing keys too
+        let response = self.send(reqwest::Method::DELETE, public_id, Vec::new()).await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("S3 delete returned {}: {}", status, error_text));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\0\x01\0\0\0\x01\x08\x02\0\0\0";
+
+    #[test]
+    fn names_objects_after_the_detected_image_type() {
+        let key = object_key(PNG_SIGNATURE, Some("menu/rolls")).unwrap();
+        assert!(key.starts_with("menu/rolls/") && key.ends_with(".png"), "{}", key);
+        check_key(&key).unwrap();
+    }
+
+    #[test]
+    fn refuses_content_that_is_not_an_image() {
+        for data in [
+            &b"<html><script>alert(1)</script></html>"[..],
+            b"<svg xmlns=\"http://www.w3.org/2000/svg\" onload=\"alert(1)\"/>",
+            b"alert(document.cookie)",
+            b"",
+        ] {
+            assert!(object_key(data, None).is_err());
+        }
+    }
+
+    #[test]
+    fn refuses_folders_and_keys_outside_the_root() {
+        assert!(sanitize_folder(Some("../etc")).is_err());
+        assert!(check_key("menu/../../secret.png").is_err());
+        assert!(check_key("menu/.htaccess").is_err());
+    }
+}
@@ -0,0 +1,345 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgListener;
+use sqlx::{PgExecutor, PgPool};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::error::{AppError, Result};
+
+const JOBS_CHANNEL: &str = "jobs";
+const BATCH_SIZE: i64 = 10;
+const POLL_INTERVAL_SECS: u64 = 5;
+// Jobs stuck in `running` longer than this are assumed lost (crashed worker)
+const STALE_LOCK_MINUTES: i64 = 15;
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Job {
+    pub id: i64,
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub run_at: DateTime<Utc>,
+    pub locked_at: Option<DateTime<Utc>>,
+    pub locked_by: Option<String>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl Job {
+    /// True when this run is the last one before the job goes to `dead`
+    pub fn is_last_attempt(&self) -> bool {
+        self.attempts >= self.max_attempts
+    }
+}
+
+const JOB_COLUMNS: &str = r#"id, kind, payload, status, attempts, max_attempts, run_at,
+    locked_at, locked_by, last_error, created_at, updated_at, finished_at"#;
+
+/// Work for one job kind
+#[async_trait]
+pub trait JobHandler: Send + Sync {
+    fn kind(&self) -> &'static str;
+
+    async fn run(&self, job: &Job) -> anyhow::Result<()>;
+}
+
+/// Queue a job. Pass a transaction to make the job part of the same commit.
+pub async fn enqueue<'e, E>(
+    executor: E,
+    kind: &str,
+    payload: serde_json::Value,
+    run_at: Option<DateTime<Utc>>,
+) -> Result<i64>
+where
+    E: PgExecutor<'e>,
+{
+    // pg_notify is delivered on commit, so workers wake up only for visible jobs
+    let id: i64 = sqlx::query_scalar(
+        r#"
+        WITH job AS (
+            INSERT INTO jobs (kind, payload, run_at)
+            VALUES ($1, $2, COALESCE($3, NOW()))
+            RETURNING id
+        )
+        SELECT id FROM job, pg_notify($4, $1)
+        "#,
+    )
+    .bind(kind)
+    .bind(payload)
+    .bind(run_at)
+    .bind(JOBS_CHANNEL)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(id)
+}
+
+/// Retry delay after a failed attempt: 30s, 1m, 2m, 4m... capped at 1h
+fn backoff(attempts: i32) -> Duration {
+    let seconds = 30i64.saturating_mul(1 << (attempts.clamp(1, 8) - 1));
+    Duration::seconds(seconds.min(3600))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JobFilters {
+    pub status: Option<String>,
+    pub kind: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct JobStats {
+    pub kind: String,
+    pub status: String,
+    pub count: i64,
+}
+
+pub struct JobQueue {
+    pool: PgPool,
+    handlers: HashMap<&'static str, Arc<dyn JobHandler>>,
+    worker_id: String,
+}
+
+impl JobQueue {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool,
+            handlers: HashMap::new(),
+            worker_id: format!("worker-{}", uuid::Uuid::new_v4()),
+        }
+    }
+
+    pub fn register(mut self, handler: Arc<dyn JobHandler>) -> Self {
+        self.handlers.insert(handler.kind(), handler);
+        self
+    }
+
+    /// Claim and run one batch of due jobs. Returns how many were claimed.
+    pub async fn run_due(&self) -> Result<usize> {
+        sqlx::query(
+            r#"
+            UPDATE jobs
+            SET status = 'queued', locked_at = NULL, locked_by = NULL
+            WHERE status = 'running' AND locked_at < NOW() - make_interval(mins => $1)
+            "#,
+        )
+        .bind(STALE_LOCK_MINUTES as i32)
+        .execute(&self.pool)
+        .await?;
+
+        let kinds: Vec<&str> = self.handlers.keys().copied().collect();
+
+        // Claiming commits immediately, so a crash mid-run leaves a stale lock, not a lost job
+        let jobs = sqlx::query_as::<_, Job>(&format!(
+            r#"
+            UPDATE jobs
+            SET status = 'running', attempts = attempts + 1, locked_at = NOW(), locked_by = $1
+            WHERE id IN (
+                SELECT id FROM jobs
+                WHERE status = 'queued' AND run_at <= NOW() AND kind = ANY($2)
+                ORDER BY run_at ASC, id ASC
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING {}
+            "#,
+            JOB_COLUMNS
+        ))
+        .bind(&self.worker_id)
+        .bind(&kinds)
+        .bind(BATCH_SIZE)
+        .fetch_all(&self.pool)
+        .await?;
+
+        // A job whose result could not be saved is left to the stale-lock
+        // reset; the rest of the batch still runs
+        for job in &jobs {
+            if let Err(e) = self.execute(job).await {
+                tracing::error!("Failed to record the result of job {} ({}): {:?}", job.id, job.kind, e);
+            }
+        }
+
+        Ok(jobs.len())
+    }
+
+    async fn execute(&self, job: &Job) -> Result<()> {
+        let result = match self.handlers.get(job.kind.as_str()) {
+            Some(handler) => handler.run(job).await,
+            None => Err(anyhow::anyhow!("No handler registered for job kind '{}'", job.kind)),
+        };
+
+        match result {
+            Ok(()) => {
+                sqlx::query(
+                    r#"
+                    UPDATE jobs
+                    SET status = 'succeeded', finished_at = NOW(), locked_at = NULL, last_error = NULL
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(job.id)
+                .execute(&self.pool)
+                .await?;
+            }
+            Err(e) if job.is_last_attempt() => {
+                tracing::error!("💀 Job {} ({}) is dead after {} attempts: {}", job.id, job.kind, job.attempts, e);
+
+                sqlx::query(
+                    r#"
+                    UPDATE jobs
+                    SET status = 'dead', finished_at = NOW(), locked_at = NULL, last_error = $2
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(job.id)
+                .bind(e.to_string())
+                .execute(&self.pool)
+                .await?;
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "⚠️ Job {} ({}) failed (attempt {}/{}): {}",
+                    job.id, job.kind, job.attempts, job.max_attempts, e
+                );
+
+                sqlx::query(
+                    r#"
+                    UPDATE jobs
+                    SET status = 'queued', run_at = $2, locked_at = NULL, locked_by = NULL, last_error = $3
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(job.id)
+                .bind(Utc::now() + backoff(job.attempts))
+                .bind(e.to_string())
+                .execute(&self.pool)
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Run jobs in the background for the lifetime of the app.
+    /// Wakes up on `pg_notify` from `enqueue` and polls as a fallback.
+    pub fn spawn_worker(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut listener = match PgListener::connect_with(&self.pool).await {
+                Ok(mut listener) => match listener.listen(JOBS_CHANNEL).await {
+                    Ok(()) => Some(listener),
+                    Err(e) => {
+                        tracing::warn!("Job LISTEN failed, falling back to polling: {:?}", e);
+                        None
+                    }
+                },
+                Err(e) => {
+                    tracing::warn!("Job listener connection failed, falling back to polling: {:?}", e);
+                    None
+                }
+            };
+
+            loop {
+                loop {
+                    match self.run_due().await {
+                        // Full batch: there may be more due jobs right away
+                        Ok(claimed) if claimed as i64 == BATCH_SIZE => continue,
+                        Ok(_) => break,
+                        Err(e) => {
+                            tracing::error!("Job worker error: {:?}", e);
+                            break;
+                        }
+                    }
+                }
+
+                let sleep = tokio::time::sleep(std::time::Duration::from_secs(POLL_INTERVAL_SECS));
+                match listener.as_mut() {
+                    Some(listener) => {
+                        tokio::select! {
+                            _ = listener.recv() => {}
+                            _ = sleep => {}
+                        }
+                    }
+                    None => sleep.await,
+                }
+            }
+        });
+    }
+
+    // ===== ADMIN =====
+
+    pub async fn list(&self, filters: JobFilters) -> Result<Vec<Job>> {
+        let jobs = sqlx::query_as::<_, Job>(&format!(
+            r#"
+            SELECT {}
+            FROM jobs
+            WHERE ($1::VARCHAR IS NULL OR status = $1)
+              AND ($2::VARCHAR IS NULL OR kind = $2)
+            ORDER BY created_at DESC
+            LIMIT $3
+            "#,
+            JOB_COLUMNS
+        ))
+        .bind(filters.status)
+        .bind(filters.kind)
+        .bind(filters.limit.unwrap_or(100).clamp(1, 500))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(jobs)
+    }
+
+    pub async fn get(&self, id: i64) -> Result<Job> {
+        sqlx::query_as::<_, Job>(&format!("SELECT {} FROM jobs WHERE id = $1", JOB_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Job with ID {} not found", id)))
+    }
+
+    pub async fn stats(&self) -> Result<Vec<JobStats>> {
+        let stats = sqlx::query_as::<_, JobStats>(
+            r#"
+            SELECT kind, status, COUNT(*) AS count
+            FROM jobs
+            GROUP BY kind, status
+            ORDER BY kind, status
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(stats)
+    }
+
+    /// Put a dead job back in the queue with a fresh attempt budget
+    pub async fn retry(&self, id: i64) -> Result<Job> {
+        let job = sqlx::query_as::<_, Job>(&format!(
+            r#"
+            UPDATE jobs
+            SET status = 'queued', attempts = 0, run_at = NOW(), finished_at = NULL,
+                locked_at = NULL, locked_by = NULL
+            WHERE id = $1 AND status = 'dead'
+            RETURNING {}
+            "#,
+            JOB_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::BadRequest(format!("Job {} is not dead or does not exist", id)))?;
+
+        sqlx::query("SELECT pg_notify($1, $2)")
+            .bind(JOBS_CHANNEL)
+            .bind(&job.kind)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(job)
+    }
+}
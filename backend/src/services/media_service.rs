@@ -0,0 +1,275 @@
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::error::{AppError, Result};
+use crate::listing::{ListParams, ListQuery, Page, Sql};
+use crate::models::media::{MediaAsset, MediaAssetDetails, MediaFilters, MediaReference};
+use crate::services::background_jobs::JOB_IMAGE_DELETE;
+use crate::services::image_processing;
+use crate::services::image_storage::{ImageStorage, StoredImage};
+use crate::services::job_queue::{self, Job, JobHandler};
+
+pub const JOB_MEDIA_SWEEP: &str = "media.sweep";
+
+const SWEEP_INTERVAL_MINUTES: i64 = 60;
+const SWEEP_BATCH_SIZE: i64 = 100;
+
+const ASSET_COLUMNS: &str = r#"id, storage, public_id, url, format, width, height, bytes, folder,
+    derivatives, uploaded_by, created_at, released_at,
+    (SELECT COUNT(*) FROM media_asset_references r WHERE r.asset_id = media_assets.id) AS usage_count"#;
+
+const UNUSED: &str = "NOT EXISTS (SELECT 1 FROM media_asset_references r WHERE r.asset_id = media_assets.id)";
+
+/// Every uploaded image goes through here: validated, stored with its
+/// derivatives and recorded in `media_assets`. Images nothing references
+/// for longer than `grace` are removed by the `media.sweep` job.
+pub struct MediaService {
+    pool: PgPool,
+    storage: Arc<dyn ImageStorage>,
+    grace: Duration,
+}
+
+impl MediaService {
+    pub fn new(pool: PgPool, storage: Arc<dyn ImageStorage>, grace: Duration) -> Self {
+        Self { pool, storage, grace }
+    }
+
+    fn storage_error(&self, e: anyhow::Error) -> AppError {
+        tracing::error!("Image storage error ({}): {}", self.storage.name(), e);
+        AppError::InternalError
+    }
+
+    pub async fn upload(&self, data: Vec<u8>, folder: Option<&str>, uploaded_by: &str) -> Result<MediaAsset> {
+        let bytes = data.len() as i32;
+        let processed = tokio::task::spawn_blocking(move || image_processing::process(data))
+            .await
+            .map_err(|e| {
+                tracing::error!("Image processing panicked: {}", e);
+                AppError::InternalError
+            })??;
+
+        let extension = processed.format.extension();
+        let original = self
+            .storage
+            .upload(processed.data, &format!("image.{}", extension), folder)
+            .await
+            .map_err(|e| self.storage_error(e))?;
+
+        let mut derivatives: BTreeMap<&str, StoredImage> = BTreeMap::new();
+        for derivative in processed.derivatives {
+            let filename = format!("{}.{}", derivative.name, derivative.extension);
+            let stored = self
+                .storage
+                .upload(derivative.data, &filename, folder)
+                .await
+                .map_err(|e| self.storage_error(e))?;
+            derivatives.insert(derivative.name, stored);
+        }
+
+        let mut urls = vec![original.url.clone()];
+        urls.extend(derivatives.values().map(|d| d.url.clone()));
+
+        let asset = sqlx::query_as::<_, MediaAsset>(&format!(
+            r#"
+            INSERT INTO media_assets (storage, public_id, url, format, width, height, bytes, folder,
+                                      derivatives, urls, uploaded_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING {}
+            "#,
+            ASSET_COLUMNS
+        ))
+        .bind(self.storage.name())
+        .bind(&original.public_id)
+        .bind(&original.url)
+        .bind(extension)
+        .bind(processed.width as i32)
+        .bind(processed.height as i32)
+        .bind(bytes)
+        .bind(folder)
+        .bind(serde_json::json!(derivatives))
+        .bind(&urls)
+        .bind(uploaded_by)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(asset)
+    }
+
+    pub async fn list(&self, filters: MediaFilters, params: &ListParams) -> Result<Page<MediaAsset>> {
+        let mut list = ListQuery::new(ASSET_COLUMNS, "media_assets");
+        if let Some(unused) = filters.unused {
+            list.filter(if unused { Sql::new(UNUSED) } else { Sql::new("NOT ").sql(UNUSED) });
+        }
+        list.filter_opt("folder = ", filters.folder.filter(|f| !f.is_empty()))
+            .sort(
+                params,
+                &[("created_at", "created_at"), ("bytes", "bytes"), ("usage_count", "usage_count")],
+                "id DESC",
+            )?
+            .paginate(params)?;
+
+        list.fetch_page::<MediaAsset>(&self.pool).await
+    }
+
+    pub async fn get(&self, id: i32) -> Result<MediaAssetDetails> {
+        let asset = sqlx::query_as::<_, MediaAsset>(&format!(
+            "SELECT {} FROM media_assets WHERE id = $1",
+            ASSET_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Media asset with ID {} not found", id)))?;
+
+        let references = sqlx::query_as::<_, MediaReference>(
+            r#"
+            SELECT entity, entity_id, field
+            FROM media_asset_references
+            WHERE asset_id = $1
+            ORDER BY entity, entity_id, field
+            "#,
+        )
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(MediaAssetDetails { asset, references })
+    }
+
+    /// Removes an unused asset; its files are deleted by background jobs
+    pub async fn delete(&self, id: i32) -> Result<()> {
+        let details = self.get(id).await?;
+        if !details.references.is_empty() {
+            let used_by: Vec<String> = details
+                .references
+                .iter()
+                .map(|r| format!("{} {} ({})", r.entity, r.entity_id, r.field))
+                .collect();
+            return Err(AppError::Validation(format!(
+                "Image is still used by: {}",
+                used_by.join(", ")
+            )));
+        }
+
+        let mut tx = self.pool.begin().await?;
+        let deleted = delete_assets(&mut tx, &[id]).await?;
+        tx.commit().await?;
+
+        // Referenced between the check and the delete
+        if deleted == 0 {
+            return Err(AppError::Validation("Image is still in use".to_string()));
+        }
+        Ok(())
+    }
+
+    /// `DELETE /api/upload`: tracked images are removed like [`Self::delete`],
+    /// for anything else an `image.delete` job removes the file
+    pub async fn delete_by_public_id(&self, public_id: &str) -> Result<()> {
+        let id: Option<i32> = sqlx::query_scalar("SELECT id FROM media_assets WHERE public_id = $1 AND storage = $2")
+            .bind(public_id)
+            .bind(self.storage.name())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        match id {
+            Some(id) => self.delete(id).await,
+            None => {
+                job_queue::enqueue(&self.pool, JOB_IMAGE_DELETE, serde_json::json!({ "public_id": public_id }), None)
+                    .await?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Makes sure a sweep is queued; each sweep queues the next one
+    pub async fn schedule_sweep(&self) -> Result<()> {
+        let queued: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM jobs WHERE kind = $1 AND status IN ('queued', 'running'))",
+        )
+        .bind(JOB_MEDIA_SWEEP)
+        .fetch_one(&self.pool)
+        .await?;
+
+        if !queued {
+            job_queue::enqueue(&self.pool, JOB_MEDIA_SWEEP, serde_json::json!({}), None).await?;
+        }
+        Ok(())
+    }
+
+    /// Deletes assets of the current storage that have been unused for longer
+    /// than the grace period. Returns how many were removed.
+    pub async fn sweep(&self) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+
+        let ids: Vec<i32> = sqlx::query_scalar(&format!(
+            r#"
+            SELECT id FROM media_assets
+            WHERE storage = $1
+              AND COALESCE(released_at, created_at) < $2
+              AND {}
+            ORDER BY id
+            LIMIT $3
+            FOR UPDATE SKIP LOCKED
+            "#,
+            UNUSED
+        ))
+        .bind(self.storage.name())
+        .bind(Utc::now() - self.grace)
+        .bind(SWEEP_BATCH_SIZE)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let deleted = delete_assets(&mut tx, &ids).await?;
+        tx.commit().await?;
+
+        Ok(deleted)
+    }
+}
+
+/// Deletes the rows that are still unused and queues an `image.delete` job
+/// for the original and each derivative, so the files go only if the
+/// transaction commits
+async fn delete_assets(tx: &mut Transaction<'_, Postgres>, ids: &[i32]) -> Result<u64> {
+    let deleted: Vec<(String, serde_json::Value)> = sqlx::query_as(&format!(
+        "DELETE FROM media_assets WHERE id = ANY($1) AND {} RETURNING public_id, derivatives",
+        UNUSED
+    ))
+    .bind(ids)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    for (public_id, derivatives) in &deleted {
+        let derivative_ids = derivatives
+            .as_object()
+            .into_iter()
+            .flat_map(|d| d.values())
+            .filter_map(|d| d["public_id"].as_str());
+
+        for id in std::iter::once(public_id.as_str()).chain(derivative_ids) {
+            job_queue::enqueue(&mut **tx, JOB_IMAGE_DELETE, serde_json::json!({ "public_id": id }), None).await?;
+        }
+    }
+
+    Ok(deleted.len() as u64)
+}
+
+#[async_trait]
+impl JobHandler for MediaService {
+    fn kind(&self) -> &'static str {
+        JOB_MEDIA_SWEEP
+    }
+
+    async fn run(&self, _job: &Job) -> anyhow::Result<()> {
+        let deleted = self.sweep().await?;
+        if deleted > 0 {
+            tracing::info!("🧹 Deleted {} unused images", deleted);
+        }
+
+        let next_run = Utc::now() + Duration::minutes(SWEEP_INTERVAL_MINUTES);
+        job_queue::enqueue(&self.pool, JOB_MEDIA_SWEEP, serde_json::json!({}), Some(next_run)).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,265 @@
+use sqlx::PgPool;
+use std::sync::Arc;
+
+use crate::error::{AppError, Result};
+use crate::listing::{ListParams, ListQuery, Page};
+use crate::models::menu_bulk::{
+    BulkItemChange, BulkMenuOperation, BulkMenuReport, BulkMenuRequest, MenuBulkOperation,
+};
+use crate::models::menu_transfer::FieldChange;
+use crate::models::restaurant::MenuItem;
+use crate::services::{menu_history_service, webhook_service};
+use crate::websocket::{WsMessage, WsState};
+
+const MENU_ITEM_COLUMNS: &str = r#"id, category_id, name, name_ru, name_pl,
+    description, description_ru, description_pl,
+    price, original_price, image, images,
+    is_available, is_popular, is_new, is_vegetarian, is_spicy,
+    is_vegan, is_gluten_free, is_halal, is_lactose_free, spice_level,
+    allergens, weight, calories, cooking_time, ingredients, tags,
+    created_at, updated_at, deleted_at"#;
+
+const OPERATION_COLUMNS: &str = "id, operation, request, matched, changed, items, changed_by, created_at";
+
+/// `UPDATE` of the matched items ($1) that only touches rows it actually changes
+fn update_sql(set: &str, changes: &str) -> String {
+    format!(
+        "UPDATE menu_items SET {} WHERE id = ANY($1) AND ({}) RETURNING {}",
+        set, changes, MENU_ITEM_COLUMNS
+    )
+}
+
+/// The operation's fields that differ between two versions of an item
+fn item_changes(operation: &BulkMenuOperation, before: &MenuItem, after: &MenuItem) -> Vec<FieldChange> {
+    let before = serde_json::to_value(before).unwrap_or_default();
+    let after = serde_json::to_value(after).unwrap_or_default();
+
+    operation
+        .fields()
+        .iter()
+        .filter(|field| before.get(**field) != after.get(**field))
+        .map(|field| FieldChange {
+            field: field.to_string(),
+            from: before.get(*field).cloned().unwrap_or_default(),
+            to: after.get(*field).cloned().unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// One operation applied to many menu items at once, e.g. "everything
+/// tagged fish is unavailable" or "drinks +5%"
+pub struct MenuBulkService {
+    pool: PgPool,
+    ws_state: Arc<WsState>,
+}
+
+impl MenuBulkService {
+    pub fn new(pool: PgPool, ws_state: Arc<WsState>) -> Self {
+        Self { pool, ws_state }
+    }
+
+    /// Applies the operation in one transaction and records it in the audit
+    /// log. On a dry run the same report is returned but nothing is saved.
+    pub async fn apply(
+        &self,
+        mut request: BulkMenuRequest,
+        dry_run: bool,
+        changed_by: &str,
+    ) -> Result<BulkMenuReport> {
+        let mut errors = request.validate();
+        let operation = request.operation.clone();
+
+        let mut tx = self.pool.begin().await?;
+        menu_history_service::set_actor(
+            &mut tx,
+            changed_by,
+            Some(&format!("Bulk operation: {}", operation.name())),
+        )
+        .await?;
+
+        if let BulkMenuOperation::MoveCategory { category_id } = &operation {
+            let exists: bool =
+                sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM categories WHERE id = $1 AND deleted_at IS NULL)")
+                    .bind(category_id)
+                    .fetch_one(&mut *tx)
+                    .await?;
+            if !exists {
+                errors.push(format!("Category with ID {} not found", category_id));
+            }
+        }
+
+        if let Some(ids) = &request.filter.ids {
+            let unknown: Vec<String> = sqlx::query_scalar(
+                r#"
+                SELECT id::TEXT FROM unnest($1::INT[]) AS id
+                WHERE id NOT IN (SELECT id FROM menu_items WHERE deleted_at IS NULL)
+                "#,
+            )
+            .bind(ids)
+            .fetch_all(&mut *tx)
+            .await?;
+            if !unknown.is_empty() {
+                errors.push(format!("Menu items not found: {}", unknown.join(", ")));
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(AppError::Validation(errors.join("; ")));
+        }
+
+        let filter = &request.filter;
+        let matched = sqlx::query_as::<_, MenuItem>(&format!(
+            r#"
+            SELECT {} FROM menu_items
+            WHERE deleted_at IS NULL
+              AND ($1::INT[] IS NULL OR id = ANY($1))
+              AND ($2::INT IS NULL OR category_id = $2)
+              AND ($3::TEXT IS NULL OR $3 = ANY(tags))
+            ORDER BY id
+            FOR UPDATE
+            "#,
+            MENU_ITEM_COLUMNS
+        ))
+        .bind(filter.ids.as_ref().filter(|ids| !ids.is_empty()))
+        .bind(filter.category_id)
+        .bind(filter.tag.as_deref().filter(|tag| !tag.is_empty()))
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let ids: Vec<i32> = matched.iter().map(|item| item.id).collect();
+        let sql: String;
+        let query = match &operation {
+            BulkMenuOperation::SetAvailability { is_available } => {
+                sql = update_sql("is_available = $2", "is_available IS DISTINCT FROM $2");
+                sqlx::query_as::<_, MenuItem>(&sql).bind(&ids).bind(*is_available)
+            }
+            BulkMenuOperation::SetFlags { is_popular, is_new } => {
+                sql = update_sql(
+                    "is_popular = COALESCE($2, is_popular), is_new = COALESCE($3, is_new)",
+                    "(is_popular, is_new) IS DISTINCT FROM (COALESCE($2, is_popular), COALESCE($3, is_new))",
+                );
+                sqlx::query_as::<_, MenuItem>(&sql).bind(&ids).bind(*is_popular).bind(*is_new)
+            }
+            BulkMenuOperation::AdjustPrice { percent, amount, round_to, rounding } => {
+                // $2 percent, $3 amount, $4 step; validate() filled in round_to and rounding
+                let price = match rounding.as_deref() {
+                    Some("up") => "CEIL((price * (100 + $2) / 100 + $3) / $4) * $4",
+                    Some("down") => "FLOOR((price * (100 + $2) / 100 + $3) / $4) * $4",
+                    _ => "ROUND((price * (100 + $2) / 100 + $3) / $4) * $4",
+                };
+                sql = update_sql(&format!("price = {}", price), &format!("price IS DISTINCT FROM {}", price));
+                sqlx::query_as::<_, MenuItem>(&sql)
+                    .bind(&ids)
+                    .bind(percent.clone().unwrap_or_default())
+                    .bind(amount.clone().unwrap_or_default())
+                    .bind(round_to.clone().unwrap_or_default())
+            }
+            BulkMenuOperation::MoveCategory { category_id } => {
+                sql = update_sql("category_id = $2", "category_id IS DISTINCT FROM $2");
+                sqlx::query_as::<_, MenuItem>(&sql).bind(&ids).bind(*category_id)
+            }
+            BulkMenuOperation::AddTags { tags } => {
+                sql = update_sql(
+                    "tags = COALESCE(tags, '{}') || ARRAY(SELECT t FROM unnest($2::TEXT[]) AS t WHERE t <> ALL(COALESCE(tags, '{}')))",
+                    "NOT COALESCE(tags, '{}') @> $2::TEXT[]",
+                );
+                sqlx::query_as::<_, MenuItem>(&sql).bind(&ids).bind(tags)
+            }
+            BulkMenuOperation::RemoveTags { tags } => {
+                sql = update_sql(
+                    "tags = ARRAY(SELECT t FROM unnest(tags) WITH ORDINALITY AS u(t, n) WHERE t <> ALL($2::TEXT[]) ORDER BY n)",
+                    "COALESCE(tags, '{}') && $2::TEXT[]",
+                );
+                sqlx::query_as::<_, MenuItem>(&sql).bind(&ids).bind(tags)
+            }
+        };
+        let mut updated = query.fetch_all(&mut *tx).await?;
+        updated.sort_by_key(|item| item.id);
+
+        let zero = bigdecimal::BigDecimal::from(0);
+        let free: Vec<String> = updated
+            .iter()
+            .filter(|item| item.price <= zero)
+            .map(|item| item.id.to_string())
+            .collect();
+        if !free.is_empty() {
+            return Err(AppError::Validation(format!(
+                "The new price would be zero or negative for menu items: {}",
+                free.join(", ")
+            )));
+        }
+
+        let items: Vec<BulkItemChange> = updated
+            .iter()
+            .filter_map(|after| {
+                let before = matched.iter().find(|item| item.id == after.id)?;
+                Some(BulkItemChange {
+                    id: after.id,
+                    name: after.name.clone(),
+                    changes: item_changes(&operation, before, after),
+                })
+            })
+            .collect();
+
+        let mut report = BulkMenuReport {
+            id: None,
+            dry_run,
+            operation: operation.name(),
+            matched: matched.len(),
+            changed: items.len(),
+            items,
+        };
+
+        if dry_run {
+            tx.rollback().await?;
+            return Ok(report);
+        }
+
+        let id: i32 = sqlx::query_scalar(
+            r#"
+            INSERT INTO menu_bulk_operations (operation, request, matched, changed, items, changed_by)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id
+            "#,
+        )
+        .bind(report.operation)
+        .bind(serde_json::json!(&request))
+        .bind(report.matched as i32)
+        .bind(report.changed as i32)
+        .bind(serde_json::json!(&report.items))
+        .bind(changed_by)
+        .fetch_one(&mut *tx)
+        .await?;
+        report.id = Some(id);
+
+        for item in &updated {
+            webhook_service::dispatch_menu_change(
+                &mut tx,
+                "menu_item",
+                "updated",
+                item.id,
+                Some(serde_json::json!(item)),
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        if !updated.is_empty() {
+            self.ws_state.broadcast(WsMessage::MenuUpdated {
+                action: format!("bulk_{}", report.operation),
+                menu_item_ids: updated.iter().map(|item| item.id).collect(),
+            });
+        }
+
+        Ok(report)
+    }
+
+    /// Audit log, newest first
+    pub async fn history(&self, params: &ListParams) -> Result<Page<MenuBulkOperation>> {
+        let mut list = ListQuery::new(OPERATION_COLUMNS, "menu_bulk_operations");
+        list.sort(params, &[("created_at", "created_at")], "id DESC")?.paginate(params)?;
+
+        list.fetch_page::<MenuBulkOperation>(&self.pool).await
+    }
+}
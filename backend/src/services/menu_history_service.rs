@@ -0,0 +1,131 @@
+use sqlx::{PgPool, Postgres, Transaction};
+
+use crate::error::{AppError, Result};
+use crate::models::menu_version::MenuItemVersion;
+use crate::services::webhook_service;
+
+const VERSION_COLUMNS: &str = r#"id, menu_item_id, version, action, changed_fields, category_id,
+    name, name_ru, name_pl, price, original_price, is_available, changed_by, note, created_at"#;
+
+/// Who made the menu changes of this transaction (and why), recorded on the
+/// versions written by the `menu_items` trigger
+pub async fn set_actor(tx: &mut Transaction<'_, Postgres>, changed_by: &str, note: Option<&str>) -> Result<()> {
+    sqlx::query("SELECT set_config('app.changed_by', $1, true), set_config('app.change_note', $2, true)")
+        .bind(changed_by)
+        .bind(note.unwrap_or_default())
+        .execute(&mut **tx)
+        .await?;
+    Ok(())
+}
+
+/// Version history of menu items
+pub struct MenuHistoryService {
+    pool: PgPool,
+}
+
+impl MenuHistoryService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Newest first; also works for deleted items
+    pub async fn versions(&self, menu_item_id: i32) -> Result<Vec<MenuItemVersion>> {
+        let versions = sqlx::query_as::<_, MenuItemVersion>(&format!(
+            "SELECT {} FROM menu_item_versions WHERE menu_item_id = $1 ORDER BY version DESC",
+            VERSION_COLUMNS
+        ))
+        .bind(menu_item_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        if versions.is_empty() {
+            return Err(AppError::NotFound(format!("Menu item with ID {} not found", menu_item_id)));
+        }
+
+        Ok(versions)
+    }
+
+    /// Restores the versioned fields of `version` and returns the version this creates
+    pub async fn rollback(&self, menu_item_id: i32, version: i32, changed_by: &str) -> Result<MenuItemVersion> {
+        let mut tx = self.pool.begin().await?;
+
+        let target = sqlx::query_as::<_, MenuItemVersion>(&format!(
+            "SELECT {} FROM menu_item_versions WHERE menu_item_id = $1 AND version = $2",
+            VERSION_COLUMNS
+        ))
+        .bind(menu_item_id)
+        .bind(version)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(format!("Version {} of menu item {} not found", version, menu_item_id))
+        })?;
+
+        let latest: i32 = sqlx::query_scalar("SELECT MAX(version) FROM menu_item_versions WHERE menu_item_id = $1")
+            .bind(menu_item_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        set_actor(&mut tx, changed_by, Some(&format!("Rollback to version {}", version))).await?;
+
+        // A category deleted since then cannot be restored; the item keeps its current one.
+        // Deleted items have to be restored before they can be rolled back.
+        let result = sqlx::query(
+            r#"
+            UPDATE menu_items
+            SET name = $2, name_ru = $3, name_pl = $4, price = $5, original_price = $6,
+                is_available = $7,
+                category_id = CASE
+                    WHEN EXISTS (SELECT 1 FROM categories WHERE id = $8 AND deleted_at IS NULL) THEN $8
+                    ELSE category_id
+                END
+            WHERE id = $1 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(menu_item_id)
+        .bind(&target.name)
+        .bind(&target.name_ru)
+        .bind(&target.name_pl)
+        .bind(&target.price)
+        .bind(&target.original_price)
+        .bind(target.is_available)
+        .bind(target.category_id)
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "Menu item with ID {} no longer exists or is deleted",
+                menu_item_id
+            )));
+        }
+
+        let current = sqlx::query_as::<_, MenuItemVersion>(&format!(
+            "SELECT {} FROM menu_item_versions WHERE menu_item_id = $1 ORDER BY version DESC LIMIT 1",
+            VERSION_COLUMNS
+        ))
+        .bind(menu_item_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        // The trigger only records real changes
+        if current.version == latest {
+            return Err(AppError::Validation(format!(
+                "Menu item {} already matches version {}",
+                menu_item_id, version
+            )));
+        }
+
+        webhook_service::dispatch_menu_change(
+            &mut tx,
+            "menu_item",
+            "rolled_back",
+            menu_item_id,
+            Some(serde_json::json!(&current)),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(current)
+    }
+}
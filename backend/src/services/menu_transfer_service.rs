@@ -0,0 +1,708 @@
+use bigdecimal::BigDecimal;
+use sqlx::{Acquire, PgConnection, PgPool, Postgres, Transaction};
+use std::collections::{HashMap, HashSet};
+
+use crate::error::{AppError, Result};
+use crate::models::allergen::DietaryInfo;
+use crate::models::menu_transfer::{
+    CategoryRecord, FieldChange, ImportReport, ImportRowResult, ImportSummary, MenuCsvRow,
+    MenuExport, MenuItemRecord,
+};
+use crate::models::restaurant::{Category, MenuItem};
+use crate::services::menu_history_service;
+use crate::services::webhook_service::{self, EVENT_MENU_UPDATED};
+
+const CATEGORY_COLUMNS: &str = r#"id, name, name_ru, name_pl, slug, description, image,
+    "order", is_active, created_at, updated_at, deleted_at"#;
+
+const MENU_ITEM_COLUMNS: &str = r#"id, category_id, name, name_ru, name_pl,
+    description, description_ru, description_pl,
+    price, original_price, image, images,
+    is_available, is_popular, is_new, is_vegetarian, is_spicy,
+    is_vegan, is_gluten_free, is_halal, is_lactose_free, spice_level,
+    allergens, weight, calories, cooking_time, ingredients, tags,
+    created_at, updated_at, deleted_at"#;
+
+// Separator for list columns (allergens, tags...) in CSV files
+const CSV_LIST_SEPARATOR: char = '|';
+
+struct CategoryInput {
+    row: usize,
+    record: CategoryRecord,
+    // CSV rows only carry names; other fields keep their current values
+    names_only: bool,
+}
+
+/// Import file after parsing, before it touches the database
+pub struct ParsedImport {
+    categories: Vec<CategoryInput>,
+    items: Vec<(usize, MenuItemRecord)>,
+    // Rows that could not be parsed at all
+    errors: Vec<ImportRowResult>,
+}
+
+fn error_row(row: usize, entity: &'static str, key: String, error: String) -> ImportRowResult {
+    ImportRowResult {
+        row,
+        entity,
+        key,
+        action: "error",
+        id: None,
+        changes: Vec::new(),
+        errors: vec![error],
+    }
+}
+
+fn split_list(value: Option<String>) -> Vec<String> {
+    value
+        .unwrap_or_default()
+        .split(CSV_LIST_SEPARATOR)
+        .map(|part| part.trim().to_string())
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+fn join_list(values: &[String]) -> Option<String> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.join(&CSV_LIST_SEPARATOR.to_string()))
+    }
+}
+
+fn empty_to_none(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.trim().is_empty())
+}
+
+/// Parse `{ "categories": [...], "items": [...] }`. Each record is checked on
+/// its own so one bad row does not hide problems in the others.
+pub fn parse_json(body: &str) -> Result<ParsedImport> {
+    #[derive(serde::Deserialize)]
+    struct RawImport {
+        #[serde(default)]
+        categories: Vec<serde_json::Value>,
+        #[serde(default)]
+        items: Vec<serde_json::Value>,
+    }
+
+    let raw: RawImport = serde_json::from_str(body)
+        .map_err(|e| AppError::Validation(format!("Invalid JSON: {}", e)))?;
+
+    let mut parsed = ParsedImport { categories: Vec::new(), items: Vec::new(), errors: Vec::new() };
+
+    for (index, value) in raw.categories.into_iter().enumerate() {
+        let key = value["slug"].as_str().unwrap_or_default().to_string();
+        match serde_json::from_value::<CategoryRecord>(value) {
+            Ok(record) => parsed.categories.push(CategoryInput { row: index + 1, record, names_only: false }),
+            Err(e) => parsed.errors.push(error_row(index + 1, "category", key, e.to_string())),
+        }
+    }
+
+    for (index, value) in raw.items.into_iter().enumerate() {
+        let key = value["name"].as_str().unwrap_or_default().to_string();
+        match serde_json::from_value::<MenuItemRecord>(value) {
+            Ok(record) => parsed.items.push((index + 1, record)),
+            Err(e) => parsed.errors.push(error_row(index + 1, "menu_item", key, e.to_string())),
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Parse the one-row-per-item CSV layout (see `MenuCsvRow`)
+pub fn parse_csv(body: &str) -> Result<ParsedImport> {
+    let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(body.as_bytes());
+    let mut parsed = ParsedImport { categories: Vec::new(), items: Vec::new(), errors: Vec::new() };
+    let mut category_slugs = HashSet::new();
+
+    for (index, result) in reader.deserialize::<MenuCsvRow>().enumerate() {
+        // Header is line 1
+        let line = index + 2;
+        let row = match result {
+            Ok(row) => row,
+            Err(e) => {
+                parsed.errors.push(error_row(line, "menu_item", String::new(), e.to_string()));
+                continue;
+            }
+        };
+
+        if let Some(category_name) = empty_to_none(row.category_name) {
+            // First row that names a category defines it
+            if category_slugs.insert(row.category_slug.clone()) {
+                let record = CategoryRecord {
+                    slug: row.category_slug.clone(),
+                    name_ru: empty_to_none(row.category_name_ru).unwrap_or_else(|| category_name.clone()),
+                    name_pl: empty_to_none(row.category_name_pl).unwrap_or_else(|| category_name.clone()),
+                    name: category_name,
+                    description: None,
+                    image: None,
+                    order: 0,
+                    is_active: true,
+                };
+                parsed.categories.push(CategoryInput { row: line, record, names_only: true });
+            }
+        }
+
+        parsed.items.push((
+            line,
+            MenuItemRecord {
+                id: row.id,
+                category_slug: row.category_slug,
+                name_ru: row.name_ru.unwrap_or_default(),
+                name_pl: row.name_pl.unwrap_or_default(),
+                name: row.name,
+                description: row.description.unwrap_or_default(),
+                description_ru: row.description_ru.unwrap_or_default(),
+                description_pl: row.description_pl.unwrap_or_default(),
+                price: row.price,
+                original_price: row.original_price,
+                image: row.image.unwrap_or_default(),
+                images: split_list(row.images),
+                is_available: row.is_available.unwrap_or(true),
+                is_popular: row.is_popular.unwrap_or(false),
+                is_new: row.is_new.unwrap_or(false),
+                is_vegetarian: row.is_vegetarian.unwrap_or(false),
+                is_spicy: row.is_spicy.unwrap_or(false),
+                is_vegan: row.is_vegan.unwrap_or(false),
+                is_gluten_free: row.is_gluten_free.unwrap_or(false),
+                is_halal: row.is_halal.unwrap_or(false),
+                is_lactose_free: row.is_lactose_free.unwrap_or(false),
+                spice_level: row.spice_level.unwrap_or(0),
+                allergens: split_list(row.allergens),
+                weight: empty_to_none(row.weight),
+                calories: row.calories,
+                cooking_time: row.cooking_time,
+                ingredients: split_list(row.ingredients),
+                tags: split_list(row.tags),
+            },
+        ));
+    }
+
+    Ok(parsed)
+}
+
+/// One CSV row per menu item; categories without items are not included
+pub fn export_csv(menu: &MenuExport) -> Result<String> {
+    let categories: HashMap<&str, &CategoryRecord> =
+        menu.categories.iter().map(|c| (c.slug.as_str(), c)).collect();
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for item in &menu.items {
+        let category = categories.get(item.category_slug.as_str());
+        writer
+            .serialize(MenuCsvRow {
+                id: item.id,
+                category_slug: item.category_slug.clone(),
+                category_name: category.map(|c| c.name.clone()),
+                category_name_ru: category.map(|c| c.name_ru.clone()),
+                category_name_pl: category.map(|c| c.name_pl.clone()),
+                name: item.name.clone(),
+                name_ru: Some(item.name_ru.clone()),
+                name_pl: Some(item.name_pl.clone()),
+                description: Some(item.description.clone()),
+                description_ru: Some(item.description_ru.clone()),
+                description_pl: Some(item.description_pl.clone()),
+                price: item.price.clone(),
+                original_price: item.original_price.clone(),
+                image: Some(item.image.clone()),
+                images: join_list(&item.images),
+                is_available: Some(item.is_available),
+                is_popular: Some(item.is_popular),
+                is_new: Some(item.is_new),
+                is_vegetarian: Some(item.is_vegetarian),
+                is_spicy: Some(item.is_spicy),
+                is_vegan: Some(item.is_vegan),
+                is_gluten_free: Some(item.is_gluten_free),
+                is_halal: Some(item.is_halal),
+                is_lactose_free: Some(item.is_lactose_free),
+                spice_level: Some(item.spice_level),
+                allergens: join_list(&item.allergens),
+                weight: item.weight.clone(),
+                calories: item.calories,
+                cooking_time: item.cooking_time,
+                ingredients: join_list(&item.ingredients),
+                tags: join_list(&item.tags),
+            })
+            .map_err(anyhow::Error::from)?;
+    }
+    let bytes = writer.into_inner().map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+    Ok(String::from_utf8(bytes).map_err(anyhow::Error::from)?)
+}
+
+fn category_record(category: &Category) -> CategoryRecord {
+    CategoryRecord {
+        slug: category.slug.clone(),
+        name: category.name.clone(),
+        name_ru: category.name_ru.clone(),
+        name_pl: category.name_pl.clone(),
+        description: category.description.clone(),
+        image: category.image.clone(),
+        order: category.order.unwrap_or(0),
+        is_active: category.is_active.unwrap_or(true),
+    }
+}
+
+fn item_record(item: &MenuItem, category_slug: &str) -> MenuItemRecord {
+    MenuItemRecord {
+        id: Some(item.id),
+        category_slug: category_slug.to_string(),
+        name: item.name.clone(),
+        name_ru: item.name_ru.clone(),
+        name_pl: item.name_pl.clone(),
+        description: item.description.clone(),
+        description_ru: item.description_ru.clone(),
+        description_pl: item.description_pl.clone(),
+        price: item.price.round(2),
+        original_price: item.original_price.as_ref().map(|p| p.round(2)),
+        image: item.image.clone(),
+        images: item.images.clone().unwrap_or_default(),
+        is_available: item.is_available.unwrap_or(true),
+        is_popular: item.is_popular.unwrap_or(false),
+        is_new: item.is_new.unwrap_or(false),
+        is_vegetarian: item.is_vegetarian.unwrap_or(false),
+        is_spicy: item.is_spicy.unwrap_or(false),
+        is_vegan: item.is_vegan,
+        is_gluten_free: item.is_gluten_free,
+        is_halal: item.is_halal,
+        is_lactose_free: item.is_lactose_free,
+        spice_level: item.spice_level,
+        allergens: item.allergens.clone().unwrap_or_default(),
+        weight: item.weight.clone(),
+        calories: item.calories,
+        cooking_time: item.cooking_time,
+        ingredients: item.ingredients.clone().unwrap_or_default(),
+        tags: item.tags.clone().unwrap_or_default(),
+    }
+}
+
+/// Field-by-field difference between the stored and imported record
+fn diff<T: serde::Serialize>(current: &T, incoming: &T) -> Vec<FieldChange> {
+    let current = serde_json::to_value(current).unwrap_or_default();
+    let incoming = serde_json::to_value(incoming).unwrap_or_default();
+
+    let (Some(current), Some(incoming)) = (current.as_object(), incoming.as_object()) else {
+        return Vec::new();
+    };
+
+    incoming
+        .iter()
+        .filter(|(field, _)| field.as_str() != "id")
+        .filter(|(field, value)| current.get(field.as_str()) != Some(value))
+        .map(|(field, value)| FieldChange {
+            field: field.clone(),
+            from: current.get(field.as_str()).cloned().unwrap_or_default(),
+            to: value.clone(),
+        })
+        .collect()
+}
+
+fn validate_category(record: &CategoryRecord) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    if record.slug.is_empty()
+        || !record.slug.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        errors.push("slug must contain only lowercase letters, digits and '-'".to_string());
+    }
+    if record.name.trim().is_empty() {
+        errors.push("name is required".to_string());
+    }
+
+    errors
+}
+
+/// Also normalizes the dietary fields (allergen codes, spice level) in place
+fn validate_item(record: &mut MenuItemRecord) -> Vec<String> {
+    let zero = BigDecimal::from(0);
+
+    if record.is_spicy && record.spice_level == 0 {
+        record.spice_level = 1;
+    }
+    let mut dietary = DietaryInfo {
+        allergens: std::mem::take(&mut record.allergens),
+        is_vegetarian: record.is_vegetarian,
+        is_vegan: record.is_vegan,
+        is_gluten_free: record.is_gluten_free,
+        spice_level: record.spice_level,
+    };
+    let mut errors = dietary.validate();
+    record.allergens = dietary.allergens;
+    record.is_vegetarian = dietary.is_vegetarian;
+    record.is_spicy = record.spice_level > 0;
+
+    if record.name.trim().is_empty() {
+        errors.push("name is required".to_string());
+    }
+    if record.price < zero {
+        errors.push("price must not be negative".to_string());
+    }
+    if record.original_price.as_ref().is_some_and(|p| *p < zero) {
+        errors.push("original_price must not be negative".to_string());
+    }
+    if record.calories.is_some_and(|c| c < 0) {
+        errors.push("calories must not be negative".to_string());
+    }
+    if record.cooking_time.is_some_and(|t| t < 0) {
+        errors.push("cooking_time must not be negative".to_string());
+    }
+
+    errors
+}
+
+pub struct MenuTransferService {
+    pool: PgPool,
+}
+
+impl MenuTransferService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Full menu, including inactive categories and unavailable items but not
+    /// deleted ones. Items without a category are skipped (they cannot be re-imported).
+    pub async fn export(&self) -> Result<MenuExport> {
+        let categories = sqlx::query_as::<_, Category>(&format!(
+            r#"SELECT {} FROM categories WHERE deleted_at IS NULL ORDER BY "order" ASC, id ASC"#,
+            CATEGORY_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        let items = sqlx::query_as::<_, MenuItem>(&format!(
+            "SELECT {} FROM menu_items WHERE deleted_at IS NULL ORDER BY category_id ASC, id ASC",
+            MENU_ITEM_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        let slugs: HashMap<i32, &str> = categories.iter().map(|c| (c.id, c.slug.as_str())).collect();
+        let items = items
+            .iter()
+            .filter_map(|item| {
+                let slug = slugs.get(&item.category_id?)?;
+                Some(item_record(item, slug))
+            })
+            .collect();
+
+        Ok(MenuExport {
+            categories: categories.iter().map(category_record).collect(),
+            items,
+        })
+    }
+
+    /// Upsert categories (by slug) and menu items in one transaction.
+    /// Nothing is saved on a dry run or when any row has an error; the
+    /// report lists what would change either way.
+    pub async fn import(&self, parsed: ParsedImport, dry_run: bool, changed_by: &str) -> Result<ImportReport> {
+        let mut tx = self.pool.begin().await?;
+        menu_history_service::set_actor(&mut tx, changed_by, Some("Menu import")).await?;
+        let mut rows = parsed.errors;
+
+        // ===== CATEGORIES =====
+
+        let existing_categories = sqlx::query_as::<_, Category>(&format!(
+            "SELECT {} FROM categories",
+            CATEGORY_COLUMNS
+        ))
+        .fetch_all(&mut *tx)
+        .await?;
+
+        // Items cannot be imported into deleted categories
+        let mut category_ids: HashMap<String, i32> = existing_categories
+            .iter()
+            .filter(|c| c.deleted_at.is_none())
+            .map(|c| (c.slug.clone(), c.id))
+            .collect();
+        let categories_by_slug: HashMap<&str, &Category> =
+            existing_categories.iter().map(|c| (c.slug.as_str(), c)).collect();
+        let mut seen_slugs = HashSet::new();
+
+        for input in parsed.categories {
+            let existing = categories_by_slug.get(input.record.slug.as_str()).copied();
+            let mut record = input.record;
+            if let (Some(existing), true) = (existing, input.names_only) {
+                record = CategoryRecord {
+                    name: record.name,
+                    name_ru: record.name_ru,
+                    name_pl: record.name_pl,
+                    ..category_record(existing)
+                };
+            }
+
+            let mut result = ImportRowResult {
+                row: input.row,
+                entity: "category",
+                key: record.slug.clone(),
+                action: "unchanged",
+                id: existing.map(|c| c.id),
+                changes: Vec::new(),
+                errors: validate_category(&record),
+            };
+            if existing.is_some_and(|c| c.deleted_at.is_some()) {
+                result.errors.push(format!("category '{}' is deleted; restore it first", record.slug));
+            }
+            if !seen_slugs.insert(record.slug.clone()) {
+                result.errors.push(format!("duplicate category slug '{}'", record.slug));
+            }
+
+            if result.errors.is_empty() {
+                match existing {
+                    Some(existing) => {
+                        result.changes = diff(&category_record(existing), &record);
+                        if !result.changes.is_empty() {
+                            result.action = "update";
+                        }
+                    }
+                    None => result.action = "create",
+                }
+
+                if result.action != "unchanged" {
+                    let mut savepoint = tx.begin().await?;
+                    let saved = Self::save_category(&mut savepoint, existing.map(|c| c.id), &record).await;
+                    match Self::release(savepoint, saved).await {
+                        Ok(id) => {
+                            category_ids.insert(record.slug.clone(), id);
+                            result.id = Some(id);
+                        }
+                        Err(e) => result.errors.push(e.to_string()),
+                    }
+                }
+            }
+
+            rows.push(result);
+        }
+
+        // ===== MENU ITEMS =====
+
+        let existing_items = sqlx::query_as::<_, MenuItem>(&format!(
+            "SELECT {} FROM menu_items WHERE deleted_at IS NULL",
+            MENU_ITEM_COLUMNS
+        ))
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let category_slugs: HashMap<i32, &str> =
+            existing_categories.iter().map(|c| (c.id, c.slug.as_str())).collect();
+        let items_by_id: HashMap<i32, &MenuItem> = existing_items.iter().map(|i| (i.id, i)).collect();
+        let items_by_name: HashMap<(Option<i32>, String), &MenuItem> = existing_items
+            .iter()
+            .map(|i| ((i.category_id, i.name.to_lowercase()), i))
+            .collect();
+        let mut seen_items = HashSet::new();
+
+        for (row, mut record) in parsed.items {
+            record.price = record.price.round(2);
+            record.original_price = record.original_price.map(|p| p.round(2));
+
+            let category_id = category_ids.get(&record.category_slug).copied();
+            let existing = record
+                .id
+                .and_then(|id| items_by_id.get(&id))
+                .or_else(|| items_by_name.get(&(category_id, record.name.to_lowercase())))
+                .copied();
+
+            let mut result = ImportRowResult {
+                row,
+                entity: "menu_item",
+                key: format!("{}/{}", record.category_slug, record.name),
+                action: "unchanged",
+                id: existing.map(|i| i.id),
+                changes: Vec::new(),
+                errors: validate_item(&mut record),
+            };
+            if category_id.is_none() {
+                result.errors.push(format!("unknown category '{}'", record.category_slug));
+            }
+            let identity = existing
+                .map(|i| i.id.to_string())
+                .unwrap_or_else(|| format!("{}/{}", record.category_slug, record.name.to_lowercase()));
+            if !seen_items.insert(identity) {
+                result.errors.push("menu item appears more than once in the file".to_string());
+            }
+
+            if let (true, Some(category_id)) = (result.errors.is_empty(), category_id) {
+                match existing {
+                    Some(existing) => {
+                        let current_slug = existing
+                            .category_id
+                            .and_then(|id| category_slugs.get(&id).copied())
+                            .unwrap_or_default();
+                        result.changes = diff(&item_record(existing, current_slug), &record);
+                        if !result.changes.is_empty() {
+                            result.action = "update";
+                        }
+                    }
+                    None => result.action = "create",
+                }
+
+                if result.action != "unchanged" {
+                    let mut savepoint = tx.begin().await?;
+                    let saved = Self::save_item(&mut savepoint, existing.map(|i| i.id), category_id, &record).await;
+                    match Self::release(savepoint, saved).await {
+                        Ok(id) => result.id = Some(id),
+                        Err(e) => result.errors.push(e.to_string()),
+                    }
+                }
+            }
+
+            rows.push(result);
+        }
+
+        // ===== REPORT =====
+
+        let mut summary = ImportSummary::default();
+        for row in rows.iter_mut() {
+            if !row.errors.is_empty() {
+                row.action = "error";
+                summary.errors += 1;
+            } else {
+                match row.action {
+                    "create" => summary.created += 1,
+                    "update" => summary.updated += 1,
+                    _ => summary.unchanged += 1,
+                }
+            }
+        }
+
+        let committed = !dry_run && summary.errors == 0;
+        if committed {
+            if summary.created + summary.updated > 0 {
+                webhook_service::dispatch(
+                    &mut tx,
+                    EVENT_MENU_UPDATED,
+                    serde_json::json!({
+                        "entity": "menu",
+                        "action": "imported",
+                        "id": null,
+                        "data": { "created": summary.created, "updated": summary.updated },
+                    }),
+                )
+                .await?;
+            }
+            tx.commit().await?;
+        } else {
+            tx.rollback().await?;
+        }
+
+        Ok(ImportReport { dry_run, committed, summary, rows })
+    }
+
+    /// Keep a row's write if it succeeded, otherwise roll back to the savepoint
+    /// so the failed row does not abort the whole transaction
+    async fn release(savepoint: Transaction<'_, Postgres>, saved: Result<i32>) -> Result<i32> {
+        match saved {
+            Ok(id) => {
+                savepoint.commit().await?;
+                Ok(id)
+            }
+            Err(e) => {
+                savepoint.rollback().await?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn save_category(conn: &mut PgConnection, id: Option<i32>, record: &CategoryRecord) -> Result<i32> {
+        let query = match id {
+            Some(_) => r#"
+                UPDATE categories
+                SET slug = $2, name = $3, name_ru = $4, name_pl = $5,
+                    description = $6, image = $7, "order" = $8, is_active = $9
+                WHERE id = $1
+                RETURNING id
+            "#,
+            None => r#"
+                INSERT INTO categories (slug, name, name_ru, name_pl, description, image, "order", is_active)
+                VALUES ($2, $3, $4, $5, $6, $7, $8, $9)
+                RETURNING id
+            "#,
+        };
+
+        let id: i32 = sqlx::query_scalar(query)
+            .bind(id)
+            .bind(&record.slug)
+            .bind(&record.name)
+            .bind(&record.name_ru)
+            .bind(&record.name_pl)
+            .bind(&record.description)
+            .bind(&record.image)
+            .bind(record.order)
+            .bind(record.is_active)
+            .fetch_one(conn)
+            .await?;
+
+        Ok(id)
+    }
+
+    async fn save_item(
+        conn: &mut PgConnection,
+        id: Option<i32>,
+        category_id: i32,
+        record: &MenuItemRecord,
+    ) -> Result<i32> {
+        let query = match id {
+            Some(_) => r#"
+                UPDATE menu_items
+                SET category_id = $2, name = $3, name_ru = $4, name_pl = $5,
+                    description = $6, description_ru = $7, description_pl = $8,
+                    price = $9, original_price = $10, image = $11, images = $12,
+                    is_available = $13, is_popular = $14, is_new = $15,
+                    is_vegetarian = $16, is_spicy = $17, allergens = $18,
+                    weight = $19, calories = $20, cooking_time = $21,
+                    ingredients = $22, tags = $23,
+                    is_vegan = $24, is_gluten_free = $25, is_halal = $26,
+                    is_lactose_free = $27, spice_level = $28
+                WHERE id = $1
+                RETURNING id
+            "#,
+            None => r#"
+                INSERT INTO menu_items (
+                    category_id, name, name_ru, name_pl,
+                    description, description_ru, description_pl,
+                    price, original_price, image, images,
+                    is_available, is_popular, is_new, is_vegetarian, is_spicy,
+                    allergens, weight, calories, cooking_time, ingredients, tags,
+                    is_vegan, is_gluten_free, is_halal, is_lactose_free, spice_level
+                )
+                VALUES ($2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23,
+                        $24, $25, $26, $27, $28)
+                RETURNING id
+            "#,
+        };
+
+        let id: i32 = sqlx::query_scalar(query)
+            .bind(id)
+            .bind(category_id)
+            .bind(&record.name)
+            .bind(&record.name_ru)
+            .bind(&record.name_pl)
+            .bind(&record.description)
+            .bind(&record.description_ru)
+            .bind(&record.description_pl)
+            .bind(&record.price)
+            .bind(&record.original_price)
+            .bind(&record.image)
+            .bind(&record.images)
+            .bind(record.is_available)
+            .bind(record.is_popular)
+            .bind(record.is_new)
+            .bind(record.is_vegetarian)
+            .bind(record.is_spicy)
+            .bind(&record.allergens)
+            .bind(&record.weight)
+            .bind(record.calories)
+            .bind(record.cooking_time)
+            .bind(&record.ingredients)
+            .bind(&record.tags)
+            .bind(record.is_vegan)
+            .bind(record.is_gluten_free)
+            .bind(record.is_halal)
+            .bind(record.is_lactose_free)
+            .bind(record.spice_level)
+            .fetch_one(conn)
+            .await?;
+
+        Ok(id)
+    }
+}
@@ -0,0 +1,157 @@
+use async_trait::async_trait;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::error::Result;
+use crate::models::restaurant::Order;
+use crate::services::job_queue::{self, Job, JobHandler};
+use crate::services::notification_templates::{self, OrderEvent};
+use crate::services::notifier::{Notifier, CHANNEL_EMAIL, CHANNEL_SMS};
+
+pub const JOB_NOTIFICATION_DELIVER: &str = "notification.deliver";
+
+#[derive(Debug, sqlx::FromRow)]
+struct OutboxMessage {
+    id: i32,
+    channel: String,
+    recipient: String,
+    subject: String,
+    body: String,
+    status: String,
+}
+
+/// Queue customer notifications for an order event.
+/// Must be called inside the transaction that changes the order, so the
+/// notification is stored if and only if the change is committed.
+pub async fn enqueue_order_event(
+    tx: &mut Transaction<'_, Postgres>,
+    order: &Order,
+    event: OrderEvent,
+) -> Result<()> {
+    let locale: String = sqlx::query_scalar("SELECT locale FROM orders WHERE id = $1")
+        .bind(order.id)
+        .fetch_one(&mut **tx)
+        .await?;
+    let message = notification_templates::render(event, &locale, order);
+
+    let mut recipients = vec![(CHANNEL_SMS, order.customer_phone.clone())];
+    if let Some(email) = order.customer_email.as_ref().filter(|e| !e.trim().is_empty()) {
+        recipients.push((CHANNEL_EMAIL, email.clone()));
+    }
+
+    for (channel, recipient) in recipients {
+        let outbox_id: i32 = sqlx::query_scalar(
+            r#"
+            INSERT INTO notification_outbox (order_id, event, channel, recipient, locale, subject, body)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id
+            "#,
+        )
+        .bind(order.id)
+        .bind(event.as_str())
+        .bind(channel)
+        .bind(recipient)
+        .bind(&locale)
+        .bind(&message.subject)
+        .bind(&message.body)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        job_queue::enqueue(
+            &mut **tx,
+            JOB_NOTIFICATION_DELIVER,
+            serde_json::json!({ "outbox_id": outbox_id }),
+            None,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Delivers `notification_outbox` messages; retries are handled by the job queue
+pub struct NotificationService {
+    pool: PgPool,
+    notifiers: HashMap<&'static str, Arc<dyn Notifier>>,
+}
+
+impl NotificationService {
+    pub fn new(pool: PgPool, notifiers: Vec<Arc<dyn Notifier>>) -> Self {
+        let notifiers = notifiers
+            .into_iter()
+            .map(|notifier| (notifier.channel(), notifier))
+            .collect();
+
+        Self { pool, notifiers }
+    }
+}
+
+#[async_trait]
+impl JobHandler for NotificationService {
+    fn kind(&self) -> &'static str {
+        JOB_NOTIFICATION_DELIVER
+    }
+
+    async fn run(&self, job: &Job) -> anyhow::Result<()> {
+        let outbox_id = job.payload["outbox_id"]
+            .as_i64()
+            .ok_or_else(|| anyhow::anyhow!("Missing outbox_id"))?;
+
+        let message = sqlx::query_as::<_, OutboxMessage>(
+            r#"
+            SELECT id, channel, recipient, subject, body, status
+            FROM notification_outbox
+            WHERE id = $1
+            "#,
+        )
+        .bind(outbox_id as i32)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        // Order deleted or message already delivered by an earlier run
+        let Some(message) = message.filter(|m| m.status != "sent") else {
+            return Ok(());
+        };
+
+        let result = match self.notifiers.get(message.channel.as_str()) {
+            Some(notifier) => notifier
+                .send(&message.recipient, &message.subject, &message.body)
+                .await,
+            None => Err(anyhow::anyhow!("No notifier configured for channel '{}'", message.channel)),
+        };
+
+        match &result {
+            Ok(()) => {
+                sqlx::query(
+                    r#"
+                    UPDATE notification_outbox
+                    SET status = 'sent', attempts = attempts + 1, sent_at = NOW(), last_error = NULL
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(message.id)
+                .execute(&self.pool)
+                .await?;
+            }
+            Err(e) => {
+                let status = if job.is_last_attempt() { "failed" } else { "pending" };
+
+                sqlx::query(
+                    r#"
+                    UPDATE notification_outbox
+                    SET status = $2, attempts = attempts + 1, last_error = $3
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(message.id)
+                .bind(status)
+                .bind(e.to_string())
+                .execute(&self.pool)
+                .await?;
+            }
+        }
+
+        result
+    }
+}
@@ -0,0 +1,131 @@
+use crate::models::restaurant::Order;
+
+// Languages with translated templates (same set as name/name_ru/name_pl)
+pub const SUPPORTED_LOCALES: [&str; 3] = ["en", "ru", "pl"];
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Map a client-provided locale ("pl-PL", "RU", None) to a supported one
+pub fn normalize_locale(locale: Option<&str>) -> &'static str {
+    let lang = locale
+        .and_then(|l| l.split(['-', '_']).next())
+        .map(|l| l.trim().to_lowercase())
+        .unwrap_or_default();
+
+    SUPPORTED_LOCALES
+        .iter()
+        .find(|supported| **supported == lang)
+        .copied()
+        .unwrap_or(DEFAULT_LOCALE)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderEvent {
+    Created,
+    StatusChanged,
+    Cancelled,
+}
+
+impl OrderEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderEvent::Created => "order.created",
+            OrderEvent::StatusChanged => "order.status_changed",
+            OrderEvent::Cancelled => "order.cancelled",
+        }
+    }
+}
+
+struct LocalizedText {
+    en: &'static str,
+    ru: &'static str,
+    pl: &'static str,
+}
+
+impl LocalizedText {
+    fn get(&self, locale: &str) -> &'static str {
+        match locale {
+            "ru" => self.ru,
+            "pl" => self.pl,
+            _ => self.en,
+        }
+    }
+}
+
+const CREATED_SUBJECT: LocalizedText = LocalizedText {
+    en: "FodiFood: order {order_number} received",
+    ru: "FodiFood: заказ {order_number} принят",
+    pl: "FodiFood: zamówienie {order_number} przyjęte",
+};
+
+const CREATED_BODY: LocalizedText = LocalizedText {
+    en: "Hi {name}! We have received your order {order_number} for {total} zł. We will let you know when its status changes.",
+    ru: "Здравствуйте, {name}! Мы получили ваш заказ {order_number} на сумму {total} zł. Мы сообщим, когда его статус изменится.",
+    pl: "Cześć {name}! Otrzymaliśmy Twoje zamówienie {order_number} na kwotę {total} zł. Damy znać, gdy zmieni się jego status.",
+};
+
+const STATUS_SUBJECT: LocalizedText = LocalizedText {
+    en: "FodiFood: order {order_number} is {status}",
+    ru: "FodiFood: заказ {order_number} — {status}",
+    pl: "FodiFood: zamówienie {order_number} — {status}",
+};
+
+const STATUS_BODY: LocalizedText = LocalizedText {
+    en: "Hi {name}! Your order {order_number} is now {status}.",
+    ru: "Здравствуйте, {name}! Статус вашего заказа {order_number}: {status}.",
+    pl: "Cześć {name}! Status Twojego zamówienia {order_number}: {status}.",
+};
+
+const CANCELLED_SUBJECT: LocalizedText = LocalizedText {
+    en: "FodiFood: order {order_number} cancelled",
+    ru: "FodiFood: заказ {order_number} отменён",
+    pl: "FodiFood: zamówienie {order_number} anulowane",
+};
+
+const CANCELLED_BODY: LocalizedText = LocalizedText {
+    en: "Hi {name}! Your order {order_number} has been cancelled. If you have any questions, please contact us.",
+    ru: "Здравствуйте, {name}! Ваш заказ {order_number} отменён. Если у вас есть вопросы, свяжитесь с нами.",
+    pl: "Cześć {name}! Twoje zamówienie {order_number} zostało anulowane. W razie pytań skontaktuj się z nami.",
+};
+
+fn status_label(status: &str, locale: &str) -> String {
+    let label = match status {
+        "pending" => LocalizedText { en: "received", ru: "принят", pl: "przyjęte" },
+        "confirmed" => LocalizedText { en: "confirmed", ru: "подтверждён", pl: "potwierdzone" },
+        "preparing" => LocalizedText { en: "being prepared", ru: "готовится", pl: "w przygotowaniu" },
+        "ready" => LocalizedText { en: "ready", ru: "готов", pl: "gotowe" },
+        "delivering" => LocalizedText { en: "on its way", ru: "в пути", pl: "w drodze" },
+        "delivered" => LocalizedText { en: "delivered", ru: "доставлен", pl: "dostarczone" },
+        "cancelled" => LocalizedText { en: "cancelled", ru: "отменён", pl: "anulowane" },
+        other => return other.to_string(),
+    };
+
+    label.get(locale).to_string()
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderedMessage {
+    pub subject: String,
+    pub body: String,
+}
+
+/// Render the customer message for an order event in the given locale
+pub fn render(event: OrderEvent, locale: &str, order: &Order) -> RenderedMessage {
+    let (subject, body) = match event {
+        OrderEvent::Created => (&CREATED_SUBJECT, &CREATED_BODY),
+        OrderEvent::StatusChanged => (&STATUS_SUBJECT, &STATUS_BODY),
+        OrderEvent::Cancelled => (&CANCELLED_SUBJECT, &CANCELLED_BODY),
+    };
+
+    let fill = |template: &str| {
+        template
+            .replace("{name}", &order.customer_name)
+            .replace("{order_number}", &order.order_number)
+            .replace("{total}", &order.total.to_string())
+            .replace("{status}", &status_label(&order.status, locale))
+    };
+
+    RenderedMessage {
+        subject: fill(subject.get(locale)),
+        body: fill(body.get(locale)),
+    }
+}
@@ -0,0 +1,141 @@
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    transport::smtp::authentication::Credentials,
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+
+use crate::services::sms_sender::SmsSender;
+
+pub const CHANNEL_EMAIL: &str = "email";
+pub const CHANNEL_SMS: &str = "sms";
+
+/// Delivery channel for customer notifications (one implementation per channel)
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Channel name as stored in `notification_outbox.channel`
+    fn channel(&self) -> &'static str;
+
+    async fn send(&self, recipient: &str, subject: &str, body: &str) -> Result<()>;
+}
+
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+    pub from: String,
+}
+
+/// Email over SMTP (STARTTLS)
+pub struct SmtpNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpNotifier {
+    pub fn new(config: SmtpConfig) -> Result<Self> {
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)?
+            .port(config.port)
+            .credentials(Credentials::new(config.username, config.password))
+            .build();
+
+        Ok(Self {
+            transport,
+            from: config.from.parse()?,
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for SmtpNotifier {
+    fn channel(&self) -> &'static str {
+        CHANNEL_EMAIL
+    }
+
+    async fn send(&self, recipient: &str, subject: &str, body: &str) -> Result<()> {
+        let email = Message::builder()
+            .from(self.from.clone())
+            .to(recipient.parse()?)
+            .subject(subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(body.to_string())?;
+
+        self.transport
+            .send(email)
+            .await
+            .map_err(|e| anyhow!("SMTP send failed: {}", e))?;
+
+        Ok(())
+    }
+}
+
+/// SMS through the configured `SmsSender` (gateway or console)
+pub struct SmsNotifier {
+    sender: Arc<dyn SmsSender>,
+}
+
+impl SmsNotifier {
+    pub fn new(sender: Arc<dyn SmsSender>) -> Self {
+        Self { sender }
+    }
+}
+
+#[async_trait]
+impl Notifier for SmsNotifier {
+    fn channel(&self) -> &'static str {
+        CHANNEL_SMS
+    }
+
+    async fn send(&self, recipient: &str, _subject: &str, body: &str) -> Result<()> {
+        self.sender.send(recipient, body).await
+    }
+}
+
+/// Stand-in for development and tests: appends messages to a file, or logs them
+pub struct LogNotifier {
+    channel: &'static str,
+    file: Option<PathBuf>,
+}
+
+impl LogNotifier {
+    pub fn new(channel: &'static str, file: Option<PathBuf>) -> Self {
+        Self { channel, file }
+    }
+}
+
+#[async_trait]
+impl Notifier for LogNotifier {
+    fn channel(&self) -> &'static str {
+        self.channel
+    }
+
+    async fn send(&self, recipient: &str, subject: &str, body: &str) -> Result<()> {
+        match &self.file {
+            Some(path) => {
+                let line = serde_json::json!({
+                    "channel": self.channel,
+                    "recipient": recipient,
+                    "subject": subject,
+                    "body": body,
+                });
+                let mut file = tokio::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .await?;
+                file.write_all(format!("{}\n", line).as_bytes()).await?;
+            }
+            None => {
+                tracing::info!("✉️  [{}] to {}: {} — {}", self.channel, recipient, subject, body);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,248 @@
+use serde_json::{Map, Value};
+use std::sync::OnceLock;
+
+use crate::error::{AppError, Result};
+
+pub const PORTFOLIO_THEMES: &[&str] = &["default", "elegant", "modern", "minimal", "dark"];
+
+// Served at GET /api/portfolio/schema for the admin editor
+pub const PORTFOLIO_SCHEMA: &str = include_str!("../../schemas/portfolio.schema.json");
+
+// Reported at most; the rest is summarized as "and N more"
+const MAX_ERRORS: usize = 20;
+
+pub fn schema() -> &'static Value {
+    static SCHEMA: OnceLock<Value> = OnceLock::new();
+    SCHEMA.get_or_init(|| serde_json::from_str(PORTFOLIO_SCHEMA).expect("portfolio.schema.json is valid JSON"))
+}
+
+pub fn validate_theme(theme: &str) -> Result<()> {
+    if !PORTFOLIO_THEMES.contains(&theme) {
+        return Err(AppError::Validation(format!(
+            "Unknown theme '{}', expected one of: {}",
+            theme,
+            PORTFOLIO_THEMES.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Checks `data` against the portfolio schema. Supports the keywords the
+/// schema uses: type, required, properties, items, minLength, maxLength,
+/// minimum, maximum, maxItems and the email, uri and date formats.
+pub fn validate_data(data: &Value) -> Result<()> {
+    let mut errors = Vec::new();
+    check(schema(), data, "data", &mut errors);
+
+    if errors.is_empty() {
+        return Ok(());
+    }
+    let total = errors.len();
+    errors.truncate(MAX_ERRORS);
+    if total > MAX_ERRORS {
+        errors.push(format!("and {} more", total - MAX_ERRORS));
+    }
+    Err(AppError::Validation(errors.join("; ")))
+}
+
+fn check(schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            other => other.as_str().into_iter().collect(),
+        };
+        if !types.iter().any(|t| has_type(value, t)) {
+            errors.push(format!("{}: expected {}", path, types.join(" or ")));
+            return;
+        }
+    }
+
+    match value {
+        Value::String(s) => check_string(schema, s, path, errors),
+        Value::Number(n) => {
+            let n = n.as_f64().unwrap_or_default();
+            if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+                if n < min {
+                    errors.push(format!("{}: must be at least {}", path, min));
+                }
+            }
+            if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+                if n > max {
+                    errors.push(format!("{}: must be at most {}", path, max));
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+                if items.len() as u64 > max {
+                    errors.push(format!("{}: at most {} items allowed", path, max));
+                }
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    check(item_schema, item, &format!("{}[{}]", path, i), errors);
+                }
+            }
+        }
+        Value::Object(fields) => check_object(schema, fields, path, errors),
+        _ => {}
+    }
+}
+
+fn check_object(schema: &Value, fields: &Map<String, Value>, path: &str, errors: &mut Vec<String>) {
+    for name in schema.get("required").and_then(Value::as_array).into_iter().flatten() {
+        if let Some(name) = name.as_str() {
+            if !fields.contains_key(name) {
+                errors.push(format!("{}.{}: is required", path, name));
+            }
+        }
+    }
+
+    // Unknown fields are kept as is
+    let properties = schema.get("properties").and_then(Value::as_object);
+    for (name, field) in fields {
+        if let Some(field_schema) = properties.and_then(|p| p.get(name)) {
+            check(field_schema, field, &format!("{}.{}", path, name), errors);
+        }
+    }
+}
+
+fn check_string(schema: &Value, s: &str, path: &str, errors: &mut Vec<String>) {
+    let length = s.chars().count() as u64;
+    if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+        if length < min {
+            errors.push(format!("{}: must be at least {} characters", path, min));
+        }
+    }
+    if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+        if length > max {
+            errors.push(format!("{}: must be at most {} characters", path, max));
+        }
+    }
+
+    // Empty strings are "not set" in the editor
+    let valid = s.is_empty()
+        || match schema.get("format").and_then(Value::as_str) {
+            Some("email") => is_email(s),
+            Some("uri") => s.starts_with("https://") || s.starts_with("http://") || s.starts_with('/'),
+            Some("date") => chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok(),
+            _ => true,
+        };
+    if !valid {
+        errors.push(format!("{}: invalid {}", path, schema["format"].as_str().unwrap_or_default()));
+    }
+}
+
+fn is_email(s: &str) -> bool {
+    match s.split_once('@') {
+        Some((user, domain)) => {
+            !user.is_empty()
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !s.contains(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn errors(data: Value) -> String {
+        match validate_data(&data) {
+            Err(AppError::Validation(message)) => message,
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn accepts_a_minimal_profile() {
+        assert!(validate_data(&json!({ "hero": { "name": "Dmytro" } })).is_ok());
+    }
+
+    #[test]
+    fn reports_missing_required_fields() {
+        assert_eq!(errors(json!({})), "data.hero: is required");
+        assert_eq!(
+            errors(json!({ "hero": { "name": "Dmytro" }, "experience": [{ "position": "Chef" }] })),
+            "data.experience[0].company: is required"
+        );
+    }
+
+    #[test]
+    fn reports_type_mismatches() {
+        assert_eq!(errors(json!([])), "data: expected object");
+        assert_eq!(
+            errors(json!({ "hero": { "name": "Dmytro", "available": "yes" }, "skills": [{ "name": "Sushi", "level": 9.5 }] })),
+            "data.hero.available: expected boolean; data.skills[0].level: expected integer"
+        );
+        // endDate may be null
+        let current_job = json!({ "position": "Chef", "company": "Fodi", "endDate": null });
+        assert!(validate_data(&json!({ "hero": { "name": "Dmytro" }, "experience": [current_job] })).is_ok());
+    }
+
+    #[test]
+    fn keeps_unknown_keys() {
+        let data = json!({
+            "hero": { "name": "Dmytro", "instagram": "@fodi" },
+            "awards": [{ "year": 2024 }],
+        });
+        assert!(validate_data(&data).is_ok());
+    }
+
+    #[test]
+    fn checks_nested_array_items_with_their_paths() {
+        let data = json!({
+            "hero": { "name": "Dmytro" },
+            "experience": [{
+                "position": "Chef",
+                "company": "Fodi",
+                "startDate": "2020-13-01",
+                "achievements": ["Opened a restaurant", 42],
+            }],
+        });
+        assert_eq!(
+            errors(data),
+            "data.experience[0].achievements[1]: expected string; data.experience[0].startDate: invalid date"
+        );
+    }
+
+    #[test]
+    fn checks_limits_and_formats() {
+        let data = json!({
+            "hero": { "name": "", "email": "chef@", "website": "javascript:alert(1)" },
+            "skills": [{ "name": "Sushi", "level": 101 }],
+        });
+        assert_eq!(
+            errors(data),
+            "data.hero.email: invalid email; data.hero.name: must be at least 1 characters; \
+             data.hero.website: invalid uri; data.skills[0].level: must be at most 100"
+        );
+        // Empty optional strings are "not set"
+        assert!(validate_data(&json!({ "hero": { "name": "Dmytro", "email": "" } })).is_ok());
+    }
+
+    #[test]
+    fn caps_the_number_of_reported_errors() {
+        let skills: Vec<Value> = (0..25).map(|_| json!({ "name": "" })).collect();
+        let message = errors(json!({ "hero": { "name": "Dmytro" }, "skills": skills }));
+        assert_eq!(message.split("; ").count(), MAX_ERRORS + 1);
+        assert!(message.ends_with("and 5 more"));
+    }
+}
@@ -0,0 +1,469 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use crate::error::{AppError, Result};
+use crate::services::background_jobs::JOB_DAILY_REPORT;
+use crate::services::job_queue;
+
+// Reports are bucketed by the restaurant's local time unless `?tz=` says otherwise
+pub const DEFAULT_TIMEZONE: &str = "Europe/Warsaw";
+
+pub const REPORT_PERIODS: [&str; 3] = ["day", "week", "month"];
+
+// Shared WHERE clause: $1 = from, $2 = to (inclusive, local dates), $3 = timezone.
+// Cancelled orders are left out of every report except the summary's cancellation rate.
+const ORDER_RANGE: &str = r#"
+    ($1::DATE IS NULL OR o.created_at >= $1::DATE::TIMESTAMP AT TIME ZONE $3)
+    AND ($2::DATE IS NULL OR o.created_at < ($2::DATE + 1)::TIMESTAMP AT TIME ZONE $3)
+"#;
+
+#[derive(Debug, Deserialize)]
+pub struct ReportFilters {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub tz: Option<String>,
+    // day, week or month (revenue report only)
+    pub period: Option<String>,
+    // Number of rows for top items/categories
+    pub limit: Option<i64>,
+    // json (default) or csv
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct SummaryReport {
+    pub orders: i64,
+    pub revenue: BigDecimal,
+    pub average_order_value: BigDecimal,
+    pub items_sold: i64,
+    pub cancelled_orders: i64,
+    // Percent of all orders in the range, cancelled ones included
+    pub cancellation_rate: BigDecimal,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct RevenueRow {
+    pub period: NaiveDate,
+    pub orders: i64,
+    pub revenue: BigDecimal,
+    pub average_order_value: BigDecimal,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct TopItemRow {
+    pub menu_item_id: Option<i32>,
+    pub name: String,
+    pub quantity: i64,
+    pub revenue: BigDecimal,
+    pub orders: i64,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct TopCategoryRow {
+    pub category_id: Option<i32>,
+    pub name: Option<String>,
+    pub quantity: i64,
+    pub revenue: BigDecimal,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct HeatmapRow {
+    // ISO day of week: 1 = Monday ... 7 = Sunday
+    pub day_of_week: i32,
+    pub hour: i32,
+    pub orders: i64,
+    pub revenue: BigDecimal,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct PaymentMethodRow {
+    pub payment_method: String,
+    pub orders: i64,
+    pub revenue: BigDecimal,
+    // Percent of orders in the range
+    pub share: BigDecimal,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct MenuSnapshotRow {
+    pub menu_item_id: i32,
+    pub version: i32,
+    pub category_id: Option<i32>,
+    // Current category name
+    pub category: Option<String>,
+    pub name: String,
+    pub price: BigDecimal,
+    pub original_price: Option<BigDecimal>,
+    pub is_available: Option<bool>,
+    // When this version was recorded
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct DailyReportRow {
+    // Local date in DEFAULT_TIMEZONE
+    pub date: NaiveDate,
+    pub orders: i64,
+    pub revenue: BigDecimal,
+    pub average_order_value: BigDecimal,
+    pub items_sold: i64,
+    pub cancelled_orders: i64,
+    pub cancellation_rate: BigDecimal,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Sales reports over `orders` and `order_items`
+pub struct ReportService {
+    pool: PgPool,
+}
+
+impl ReportService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Validates the date range and timezone, returning the timezone to use
+    async fn timezone(&self, filters: &ReportFilters) -> Result<String> {
+        if let (Some(from), Some(to)) = (filters.from, filters.to) {
+            if from > to {
+                return Err(AppError::Validation("`from` must not be after `to`".to_string()));
+            }
+        }
+
+        let tz = filters.tz.clone().unwrap_or_else(|| DEFAULT_TIMEZONE.to_string());
+        let known: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM pg_timezone_names WHERE name = $1)")
+            .bind(&tz)
+            .fetch_one(&self.pool)
+            .await?;
+
+        if !known {
+            return Err(AppError::Validation(format!("Unknown timezone: {}", tz)));
+        }
+
+        Ok(tz)
+    }
+
+    pub async fn summary(&self, filters: &ReportFilters) -> Result<SummaryReport> {
+        let tz = self.timezone(filters).await?;
+
+        let summary = sqlx::query_as::<_, SummaryReport>(&format!(
+            r#"
+            WITH range_orders AS (
+                SELECT o.id, o.total, o.status
+                FROM orders o
+                WHERE {}
+            )
+            SELECT
+                COUNT(*) FILTER (WHERE status <> 'cancelled') AS orders,
+                COALESCE(SUM(total) FILTER (WHERE status <> 'cancelled'), 0) AS revenue,
+                COALESCE(ROUND(AVG(total) FILTER (WHERE status <> 'cancelled'), 2), 0) AS average_order_value,
+                COALESCE((
+                    SELECT SUM(oi.quantity)
+                    FROM order_items oi
+                    JOIN range_orders r ON r.id = oi.order_id
+                    WHERE r.status <> 'cancelled' AND oi.parent_item_id IS NULL
+                ), 0)::BIGINT AS items_sold,
+                COUNT(*) FILTER (WHERE status = 'cancelled') AS cancelled_orders,
+                COALESCE(ROUND(100.0 * COUNT(*) FILTER (WHERE status = 'cancelled') / NULLIF(COUNT(*), 0), 2), 0)
+                    AS cancellation_rate
+            FROM range_orders
+            "#,
+            ORDER_RANGE
+        ))
+        .bind(filters.from)
+        .bind(filters.to)
+        .bind(&tz)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(summary)
+    }
+
+    /// Revenue and order count per day, week (starting Monday) or month.
+    /// Periods without orders are not returned.
+    pub async fn revenue(&self, filters: &ReportFilters) -> Result<Vec<RevenueRow>> {
+        let tz = self.timezone(filters).await?;
+
+        let period = filters.period.as_deref().unwrap_or("day");
+        if !REPORT_PERIODS.contains(&period) {
+            return Err(AppError::Validation(format!(
+                "Invalid period. Must be one of: {}",
+                REPORT_PERIODS.join(", ")
+            )));
+        }
+
+        let rows = sqlx::query_as::<_, RevenueRow>(&format!(
+            r#"
+            SELECT
+                date_trunc($4, o.created_at AT TIME ZONE $3)::DATE AS period,
+                COUNT(*) AS orders,
+                SUM(o.total) AS revenue,
+                ROUND(AVG(o.total), 2) AS average_order_value
+            FROM orders o
+            WHERE {} AND o.status <> 'cancelled'
+            GROUP BY 1
+            ORDER BY 1 ASC
+            "#,
+            ORDER_RANGE
+        ))
+        .bind(filters.from)
+        .bind(filters.to)
+        .bind(&tz)
+        .bind(period)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Best sellers by quantity. Names come from the order snapshot, so
+    /// deleted menu items still show up. A combo counts once, not per component.
+    pub async fn top_items(&self, filters: &ReportFilters) -> Result<Vec<TopItemRow>> {
+        let tz = self.timezone(filters).await?;
+
+        let rows = sqlx::query_as::<_, TopItemRow>(&format!(
+            r#"
+            SELECT
+                oi.menu_item_id,
+                MAX(oi.menu_item_name) AS name,
+                SUM(oi.quantity)::BIGINT AS quantity,
+                SUM(oi.menu_item_price * oi.quantity) AS revenue,
+                COUNT(DISTINCT oi.order_id) AS orders
+            FROM order_items oi
+            JOIN orders o ON o.id = oi.order_id
+            WHERE {} AND o.status <> 'cancelled' AND oi.parent_item_id IS NULL
+            GROUP BY oi.menu_item_id
+            ORDER BY quantity DESC, revenue DESC
+            LIMIT $4
+            "#,
+            ORDER_RANGE
+        ))
+        .bind(filters.from)
+        .bind(filters.to)
+        .bind(&tz)
+        .bind(filters.limit.unwrap_or(10).clamp(1, 100))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Sales per current menu category, including soft-deleted items and categories
+    pub async fn top_categories(&self, filters: &ReportFilters) -> Result<Vec<TopCategoryRow>> {
+        let tz = self.timezone(filters).await?;
+
+        let rows = sqlx::query_as::<_, TopCategoryRow>(&format!(
+            r#"
+            SELECT
+                c.id AS category_id,
+                MAX(c.name) AS name,
+                SUM(oi.quantity)::BIGINT AS quantity,
+                SUM(oi.menu_item_price * oi.quantity) AS revenue
+            FROM order_items oi
+            JOIN orders o ON o.id = oi.order_id
+            LEFT JOIN menu_items m ON m.id = oi.menu_item_id
+            LEFT JOIN categories c ON c.id = m.category_id
+            WHERE {} AND o.status <> 'cancelled' AND oi.parent_item_id IS NULL
+            GROUP BY c.id
+            ORDER BY revenue DESC
+            LIMIT $4
+            "#,
+            ORDER_RANGE
+        ))
+        .bind(filters.from)
+        .bind(filters.to)
+        .bind(&tz)
+        .bind(filters.limit.unwrap_or(10).clamp(1, 100))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Orders per weekday and hour of day (local time)
+    pub async fn heatmap(&self, filters: &ReportFilters) -> Result<Vec<HeatmapRow>> {
+        let tz = self.timezone(filters).await?;
+
+        let rows = sqlx::query_as::<_, HeatmapRow>(&format!(
+            r#"
+            SELECT
+                EXTRACT(ISODOW FROM o.created_at AT TIME ZONE $3)::INTEGER AS day_of_week,
+                EXTRACT(HOUR FROM o.created_at AT TIME ZONE $3)::INTEGER AS hour,
+                COUNT(*) AS orders,
+                SUM(o.total) AS revenue
+            FROM orders o
+            WHERE {} AND o.status <> 'cancelled'
+            GROUP BY 1, 2
+            ORDER BY 1, 2
+            "#,
+            ORDER_RANGE
+        ))
+        .bind(filters.from)
+        .bind(filters.to)
+        .bind(&tz)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn payment_methods(&self, filters: &ReportFilters) -> Result<Vec<PaymentMethodRow>> {
+        let tz = self.timezone(filters).await?;
+
+        let rows = sqlx::query_as::<_, PaymentMethodRow>(&format!(
+            r#"
+            SELECT
+                o.payment_method,
+                COUNT(*) AS orders,
+                SUM(o.total) AS revenue,
+                ROUND(100.0 * COUNT(*) / SUM(COUNT(*)) OVER (), 2) AS share
+            FROM orders o
+            WHERE {} AND o.status <> 'cancelled'
+            GROUP BY o.payment_method
+            ORDER BY orders DESC
+            "#,
+            ORDER_RANGE
+        ))
+        .bind(filters.from)
+        .bind(filters.to)
+        .bind(&tz)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Menu items with their price, name and availability as of the end of
+    /// `to` (local date; now when omitted), rebuilt from `menu_item_versions`
+    pub async fn menu_snapshot(&self, filters: &ReportFilters) -> Result<Vec<MenuSnapshotRow>> {
+        let tz = self.timezone(filters).await?;
+
+        let rows = sqlx::query_as::<_, MenuSnapshotRow>(
+            r#"
+            SELECT s.menu_item_id, s.version, s.category_id, c.name AS category,
+                   s.name, s.price, s.original_price, s.is_available, s.created_at AS changed_at
+            FROM (
+                SELECT DISTINCT ON (v.menu_item_id) v.*
+                FROM menu_item_versions v
+                WHERE $1::DATE IS NULL OR v.created_at < ($1::DATE + 1)::TIMESTAMP AT TIME ZONE $2
+                ORDER BY v.menu_item_id, v.version DESC
+            ) s
+            LEFT JOIN categories c ON c.id = s.category_id
+            WHERE s.action <> 'deleted'
+            ORDER BY s.category_id NULLS LAST, s.name, s.menu_item_id
+            "#,
+        )
+        .bind(filters.to)
+        .bind(&tz)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    // ===== DAILY REPORTS (report.daily job) =====
+
+    /// Stores the summary of a closed local day, replacing an earlier one
+    pub async fn close_day(&self, date: NaiveDate) -> Result<DailyReportRow> {
+        let filters = ReportFilters {
+            from: Some(date),
+            to: Some(date),
+            tz: None,
+            period: None,
+            limit: None,
+            format: None,
+        };
+        let summary = self.summary(&filters).await?;
+
+        let row = sqlx::query_as::<_, DailyReportRow>(
+            r#"
+            INSERT INTO daily_reports (date, orders, revenue, average_order_value, items_sold,
+                                       cancelled_orders, cancellation_rate)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (date) DO UPDATE
+            SET orders = EXCLUDED.orders, revenue = EXCLUDED.revenue,
+                average_order_value = EXCLUDED.average_order_value, items_sold = EXCLUDED.items_sold,
+                cancelled_orders = EXCLUDED.cancelled_orders,
+                cancellation_rate = EXCLUDED.cancellation_rate, created_at = NOW()
+            RETURNING date, orders, revenue, average_order_value, items_sold,
+                      cancelled_orders, cancellation_rate, created_at
+            "#,
+        )
+        .bind(date)
+        .bind(summary.orders)
+        .bind(summary.revenue)
+        .bind(summary.average_order_value)
+        .bind(summary.items_sold)
+        .bind(summary.cancelled_orders)
+        .bind(summary.cancellation_rate)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row)
+    }
+
+    /// Stored daily summaries, oldest first
+    pub async fn daily(&self, filters: &ReportFilters) -> Result<Vec<DailyReportRow>> {
+        self.timezone(filters).await?;
+
+        let rows = sqlx::query_as::<_, DailyReportRow>(
+            r#"
+            SELECT date, orders, revenue, average_order_value, items_sold,
+                   cancelled_orders, cancellation_rate, created_at
+            FROM daily_reports
+            WHERE ($1::DATE IS NULL OR date >= $1) AND ($2::DATE IS NULL OR date <= $2)
+            ORDER BY date ASC
+            "#,
+        )
+        .bind(filters.from)
+        .bind(filters.to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// The local day that ended last, and when the next one ends (plus a few
+    /// minutes for orders placed at midnight to commit)
+    pub async fn last_closed_day(&self) -> Result<(NaiveDate, DateTime<Utc>)> {
+        let row: (NaiveDate, DateTime<Utc>) = sqlx::query_as(
+            r#"
+            SELECT (NOW() AT TIME ZONE $1)::DATE - 1,
+                   (((NOW() AT TIME ZONE $1)::DATE + 1)::TIMESTAMP + INTERVAL '5 minutes') AT TIME ZONE $1
+            "#,
+        )
+        .bind(DEFAULT_TIMEZONE)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row)
+    }
+
+    /// Makes sure a daily report is queued; each run queues the next one
+    pub async fn schedule_daily(&self) -> Result<()> {
+        let queued: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM jobs WHERE kind = $1 AND status IN ('queued', 'running'))",
+        )
+        .bind(JOB_DAILY_REPORT)
+        .fetch_one(&self.pool)
+        .await?;
+
+        if !queued {
+            job_queue::enqueue(&self.pool, JOB_DAILY_REPORT, serde_json::json!({}), None).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Serialize report rows as CSV with a header line
+pub fn to_csv<T: Serialize>(rows: &[T]) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for row in rows {
+        writer.serialize(row).map_err(anyhow::Error::from)?;
+    }
+    let bytes = writer.into_inner().map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+    Ok(String::from_utf8(bytes).map_err(anyhow::Error::from)?)
+}
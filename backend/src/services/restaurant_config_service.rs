@@ -0,0 +1,140 @@
+use sqlx::{PgExecutor, PgPool};
+
+use crate::error::Result;
+use crate::models::restaurant::{RestaurantConfig, RestaurantInfo, RestaurantTranslation};
+
+/// Whole-config dump and restore of the restaurant settings
+pub struct RestaurantConfigService {
+    pool: PgPool,
+}
+
+impl RestaurantConfigService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn dump(&self) -> Result<RestaurantConfig> {
+        let info = info(&self.pool).await?;
+        let translations = sqlx::query_as::<_, RestaurantTranslation>(
+            r#"
+            SELECT field, locale, text FROM translations
+            WHERE entity = 'restaurant_info' AND entity_id = 1
+            ORDER BY field, locale
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(RestaurantConfig { info, translations })
+    }
+
+    /// Replaces the settings and all their translations in one transaction
+    pub async fn restore(&self, config: RestaurantConfig) -> Result<RestaurantInfo> {
+        let mut tx = self.pool.begin().await?;
+
+        // The update re-syncs the legacy ru/pl columns into translations; the
+        // dump holds those rows too, so the dumped set is written back as is
+        let info = save_info(&mut *tx, &config.info).await?;
+
+        sqlx::query("DELETE FROM translations WHERE entity = 'restaurant_info' AND entity_id = 1")
+            .execute(&mut *tx)
+            .await?;
+
+        for translation in &config.translations {
+            sqlx::query(
+                r#"
+                INSERT INTO translations (entity, entity_id, field, locale, text)
+                VALUES ('restaurant_info', 1, $1, $2, $3)
+                "#,
+            )
+            .bind(&translation.field)
+            .bind(&translation.locale)
+            .bind(&translation.text)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(info)
+    }
+}
+
+// The single restaurant_info row (id 1)
+pub async fn info(pool: &PgPool) -> Result<RestaurantInfo> {
+    let info = sqlx::query_as!(
+        RestaurantInfo,
+        r#"
+        SELECT id, name, name_ru, name_pl, description, description_ru, description_pl,
+               logo, phone, email, address, city, postal_code, opening_hours,
+               delivery_radius, minimum_order, delivery_fee, free_delivery_from,
+               average_delivery_time, social_media,
+               hero_image, hero_title, hero_subtitle, hero_description,
+               featured_dish_image, featured_dish_title, featured_dish_description,
+               featured_dish_price, updated_at
+        FROM restaurant_info
+        WHERE id = 1
+        "#
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(info)
+}
+
+pub async fn save_info<'e>(executor: impl PgExecutor<'e>, info: &RestaurantInfo) -> Result<RestaurantInfo> {
+    let updated_info = sqlx::query_as!(
+        RestaurantInfo,
+        r#"
+        UPDATE restaurant_info
+        SET name = $1, name_ru = $2, name_pl = $3,
+            description = $4, description_ru = $5, description_pl = $6,
+            logo = $7, phone = $8, email = $9, address = $10, city = $11, postal_code = $12,
+            opening_hours = $13, delivery_radius = $14, minimum_order = $15,
+            delivery_fee = $16, free_delivery_from = $17, average_delivery_time = $18,
+            social_media = $19,
+            hero_image = $20, hero_title = $21, hero_subtitle = $22, hero_description = $23,
+            featured_dish_image = $24, featured_dish_title = $25,
+            featured_dish_description = $26, featured_dish_price = $27
+        WHERE id = 1
+        RETURNING id, name, name_ru, name_pl, description, description_ru, description_pl,
+                  logo, phone, email, address, city, postal_code, opening_hours,
+                  delivery_radius, minimum_order, delivery_fee, free_delivery_from,
+                  average_delivery_time, social_media,
+                  hero_image, hero_title, hero_subtitle, hero_description,
+                  featured_dish_image, featured_dish_title, featured_dish_description,
+                  featured_dish_price, updated_at
+        "#,
+        info.name,
+        info.name_ru,
+        info.name_pl,
+        info.description,
+        info.description_ru,
+        info.description_pl,
+        info.logo,
+        info.phone,
+        info.email,
+        info.address,
+        info.city,
+        info.postal_code,
+        info.opening_hours,
+        info.delivery_radius,
+        info.minimum_order,
+        info.delivery_fee,
+        info.free_delivery_from,
+        info.average_delivery_time,
+        info.social_media,
+        info.hero_image,
+        info.hero_title,
+        info.hero_subtitle,
+        info.hero_description,
+        info.featured_dish_image,
+        info.featured_dish_title,
+        info.featured_dish_description,
+        info.featured_dish_price
+    )
+    .fetch_one(executor)
+    .await?;
+
+    Ok(updated_info)
+}
@@ -0,0 +1,275 @@
+use bigdecimal::BigDecimal;
+use sqlx::{PgPool, Postgres, Transaction};
+
+use crate::error::{AppError, Result};
+use crate::listing::Sql;
+use crate::models::schedule::{
+    AvailabilityWindow, PriceRule, SaveAvailabilityWindow, SavePriceRule, ScheduleFilters, PRICE_RULE_KINDS,
+};
+use crate::services::report_service::DEFAULT_TIMEZONE;
+use crate::services::webhook_service;
+
+const PRICE_RULE_COLUMNS: &str = r#"id, name, menu_item_id, category_id, kind, value,
+    days_of_week, start_time, end_time, starts_on, ends_on, is_active, created_at, updated_at"#;
+
+const WINDOW_COLUMNS: &str = r#"id, menu_item_id, category_id,
+    days_of_week, start_time, end_time, starts_on, ends_on, created_at, updated_at"#;
+
+/// Menu items as customers see them right now (scheduled prices and
+/// availability), for use as the FROM of a menu listing
+pub fn menu_items_now() -> Sql {
+    Sql::new("menu_items_at(now(), ")
+        .bind(DEFAULT_TIMEZONE)
+        .sql(") AS menu_items")
+}
+
+/// Price rules and availability windows of menu items and categories
+pub struct ScheduleService {
+    pool: PgPool,
+}
+
+impl ScheduleService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn check_target(
+        tx: &mut Transaction<'_, Postgres>,
+        menu_item_id: Option<i32>,
+        category_id: Option<i32>,
+        errors: &mut Vec<String>,
+    ) -> Result<()> {
+        let (table, label, id) = match (menu_item_id, category_id) {
+            (Some(id), None) => ("menu_items", "Menu item", id),
+            (None, Some(id)) => ("categories", "Category", id),
+            _ => {
+                errors.push("Exactly one of menu_item_id or category_id is required".to_string());
+                return Ok(());
+            }
+        };
+
+        let exists: bool = sqlx::query_scalar(&format!(
+            "SELECT EXISTS(SELECT 1 FROM {} WHERE id = $1 AND deleted_at IS NULL)",
+            table
+        ))
+        .bind(id)
+        .fetch_one(&mut **tx)
+        .await?;
+        if !exists {
+            errors.push(format!("{} with ID {} not found", label, id));
+        }
+
+        Ok(())
+    }
+
+    pub async fn price_rules(&self, filters: &ScheduleFilters) -> Result<Vec<PriceRule>> {
+        Ok(sqlx::query_as::<_, PriceRule>(&format!(
+            r#"
+            SELECT {} FROM price_rules
+            WHERE ($1::INT IS NULL OR menu_item_id = $1)
+              AND ($2::INT IS NULL OR category_id = $2)
+            ORDER BY id
+            "#,
+            PRICE_RULE_COLUMNS
+        ))
+        .bind(filters.menu_item_id)
+        .bind(filters.category_id)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Creates a rule, or replaces rule `id`
+    pub async fn save_price_rule(&self, id: Option<i32>, mut input: SavePriceRule) -> Result<PriceRule> {
+        let mut tx = self.pool.begin().await?;
+
+        let mut errors = input.schedule.validate();
+        if input.name.trim().is_empty() {
+            errors.push("name is required".to_string());
+        }
+        if !PRICE_RULE_KINDS.contains(&input.kind.as_str()) {
+            errors.push(format!("kind must be one of: {}", PRICE_RULE_KINDS.join(", ")));
+        }
+        if input.value < BigDecimal::from(0) || (input.kind == "percent" && input.value > BigDecimal::from(100)) {
+            errors.push("value must be >= 0 (and at most 100 for percent)".to_string());
+        }
+        Self::check_target(&mut tx, input.menu_item_id, input.category_id, &mut errors).await?;
+        if !errors.is_empty() {
+            return Err(AppError::Validation(errors.join("; ")));
+        }
+
+        let sql = match id {
+            None => format!(
+                r#"
+                INSERT INTO price_rules (
+                    name, menu_item_id, category_id, kind, value,
+                    days_of_week, start_time, end_time, starts_on, ends_on, is_active
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                RETURNING {}
+                "#,
+                PRICE_RULE_COLUMNS
+            ),
+            Some(_) => format!(
+                r#"
+                UPDATE price_rules
+                SET name = $1, menu_item_id = $2, category_id = $3, kind = $4, value = $5,
+                    days_of_week = $6, start_time = $7, end_time = $8, starts_on = $9, ends_on = $10,
+                    is_active = $11
+                WHERE id = $12
+                RETURNING {}
+                "#,
+                PRICE_RULE_COLUMNS
+            ),
+        };
+
+        let mut query = sqlx::query_as::<_, PriceRule>(&sql)
+            .bind(input.name.trim())
+            .bind(input.menu_item_id)
+            .bind(input.category_id)
+            .bind(&input.kind)
+            .bind(&input.value)
+            .bind(&input.schedule.days_of_week)
+            .bind(input.schedule.start_time)
+            .bind(input.schedule.end_time)
+            .bind(input.schedule.starts_on)
+            .bind(input.schedule.ends_on)
+            .bind(input.is_active.unwrap_or(true));
+        if let Some(id) = id {
+            query = query.bind(id);
+        }
+        let rule = query
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Price rule with ID {} not found", id.unwrap_or_default())))?;
+
+        webhook_service::dispatch_menu_change(
+            &mut tx,
+            "price_rule",
+            if id.is_some() { "updated" } else { "created" },
+            rule.id,
+            Some(serde_json::json!(&rule)),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(rule)
+    }
+
+    pub async fn delete_price_rule(&self, id: i32) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query("DELETE FROM price_rules WHERE id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Price rule with ID {} not found", id)));
+        }
+
+        webhook_service::dispatch_menu_change(&mut tx, "price_rule", "deleted", id, None).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn availability_windows(&self, filters: &ScheduleFilters) -> Result<Vec<AvailabilityWindow>> {
+        Ok(sqlx::query_as::<_, AvailabilityWindow>(&format!(
+            r#"
+            SELECT {} FROM availability_windows
+            WHERE ($1::INT IS NULL OR menu_item_id = $1)
+              AND ($2::INT IS NULL OR category_id = $2)
+            ORDER BY id
+            "#,
+            WINDOW_COLUMNS
+        ))
+        .bind(filters.menu_item_id)
+        .bind(filters.category_id)
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Creates a window, or replaces window `id`
+    pub async fn save_availability_window(
+        &self,
+        id: Option<i32>,
+        mut input: SaveAvailabilityWindow,
+    ) -> Result<AvailabilityWindow> {
+        let mut tx = self.pool.begin().await?;
+
+        let mut errors = input.schedule.validate();
+        Self::check_target(&mut tx, input.menu_item_id, input.category_id, &mut errors).await?;
+        if !errors.is_empty() {
+            return Err(AppError::Validation(errors.join("; ")));
+        }
+
+        let sql = match id {
+            None => format!(
+                r#"
+                INSERT INTO availability_windows (
+                    menu_item_id, category_id, days_of_week, start_time, end_time, starts_on, ends_on
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                RETURNING {}
+                "#,
+                WINDOW_COLUMNS
+            ),
+            Some(_) => format!(
+                r#"
+                UPDATE availability_windows
+                SET menu_item_id = $1, category_id = $2, days_of_week = $3, start_time = $4,
+                    end_time = $5, starts_on = $6, ends_on = $7
+                WHERE id = $8
+                RETURNING {}
+                "#,
+                WINDOW_COLUMNS
+            ),
+        };
+
+        let mut query = sqlx::query_as::<_, AvailabilityWindow>(&sql)
+            .bind(input.menu_item_id)
+            .bind(input.category_id)
+            .bind(&input.schedule.days_of_week)
+            .bind(input.schedule.start_time)
+            .bind(input.schedule.end_time)
+            .bind(input.schedule.starts_on)
+            .bind(input.schedule.ends_on);
+        if let Some(id) = id {
+            query = query.bind(id);
+        }
+        let window = query
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Availability window with ID {} not found", id.unwrap_or_default()))
+            })?;
+
+        webhook_service::dispatch_menu_change(
+            &mut tx,
+            "availability_window",
+            if id.is_some() { "updated" } else { "created" },
+            window.id,
+            Some(serde_json::json!(&window)),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(window)
+    }
+
+    pub async fn delete_availability_window(&self, id: i32) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query("DELETE FROM availability_windows WHERE id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Availability window with ID {} not found", id)));
+        }
+
+        webhook_service::dispatch_menu_change(&mut tx, "availability_window", "deleted", id, None).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
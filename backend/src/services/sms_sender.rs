@@ -0,0 +1,67 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Outgoing SMS transport (login codes, order notifications)
+#[async_trait]
+pub trait SmsSender: Send + Sync {
+    async fn send(&self, phone: &str, message: &str) -> Result<()>;
+}
+
+/// Development stand-in: writes messages to the log instead of sending them
+pub struct ConsoleSmsSender;
+
+#[async_trait]
+impl SmsSender for ConsoleSmsSender {
+    async fn send(&self, phone: &str, message: &str) -> Result<()> {
+        tracing::info!("📱 SMS to {}: {}", phone, message);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SmsGatewayConfig {
+    pub url: String,
+    pub api_key: String,
+    pub sender_id: String,
+}
+
+/// HTTP SMS gateway: POSTs `{ "from", "to", "text" }` as JSON with a bearer API key
+pub struct HttpSmsSender {
+    config: SmsGatewayConfig,
+    client: reqwest::Client,
+}
+
+impl HttpSmsSender {
+    pub fn new(config: SmsGatewayConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self { config, client }
+    }
+}
+
+#[async_trait]
+impl SmsSender for HttpSmsSender {
+    async fn send(&self, phone: &str, message: &str) -> Result<()> {
+        let response = self.client
+            .post(&self.config.url)
+            .bearer_auth(&self.config.api_key)
+            .json(&serde_json::json!({
+                "from": self.config.sender_id,
+                "to": phone,
+                "text": message,
+            }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!("SMS gateway returned {}: {}", status, error_text));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use sqlx::{FromRow, PgPool};
+
+use crate::error::{AppError, Result};
+use crate::models_single::StaffUser;
+
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+// Taken by the ADMIN_PASSWORD_HASH account
+const RESERVED_USERNAMES: &[&str] = &["admin"];
+
+#[derive(FromRow)]
+struct StaffLogin {
+    #[sqlx(flatten)]
+    user: StaffUser,
+    password_hash: String,
+}
+
+pub struct StaffService {
+    pool: PgPool,
+}
+
+impl StaffService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list(&self) -> Result<Vec<StaffUser>> {
+        let users = sqlx::query_as::<_, StaffUser>(
+            "SELECT id, username, created_at, updated_at FROM staff_users ORDER BY username",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(users)
+    }
+
+    pub async fn create(&self, username: &str, password: &str) -> Result<StaffUser> {
+        validate_username(username)?;
+        let password_hash = hash_password(password)?;
+
+        let user = sqlx::query_as::<_, StaffUser>(
+            r#"
+            INSERT INTO staff_users (username, password_hash)
+            VALUES ($1, $2)
+            ON CONFLICT (username) DO NOTHING
+            RETURNING id, username, created_at, updated_at
+            "#,
+        )
+        .bind(username)
+        .bind(&password_hash)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::Validation(format!("Staff user '{}' already exists", username)))?;
+
+        Ok(user)
+    }
+
+    pub async fn reset_password(&self, username: &str, password: &str) -> Result<StaffUser> {
+        let password_hash = hash_password(password)?;
+
+        let user = sqlx::query_as::<_, StaffUser>(
+            r#"
+            UPDATE staff_users SET password_hash = $2
+            WHERE username = $1
+            RETURNING id, username, created_at, updated_at
+            "#,
+        )
+        .bind(username)
+        .bind(&password_hash)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Staff user '{}' not found", username)))?;
+
+        Ok(user)
+    }
+
+    /// `None` for an unknown username or a wrong password
+    pub async fn authenticate(&self, username: &str, password: &str) -> Result<Option<StaffUser>> {
+        let row = sqlx::query_as::<_, StaffLogin>(
+            "SELECT id, username, created_at, updated_at, password_hash FROM staff_users WHERE username = $1",
+        )
+        .bind(username)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(login) if verify_password(password, &login.password_hash)? => Ok(Some(login.user)),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// bcrypt hash for ADMIN_PASSWORD_HASH or a staff account
+pub fn hash_password(password: &str) -> Result<String> {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(AppError::Validation(format!(
+            "Password must be at least {} characters",
+            MIN_PASSWORD_LENGTH
+        )));
+    }
+
+    bcrypt::hash(password, bcrypt::DEFAULT_COST).map_err(|e| AppError::Anyhow(e.into()))
+}
+
+fn verify_password(password: &str, password_hash: &str) -> Result<bool> {
+    bcrypt::verify(password, password_hash).map_err(|e| AppError::Anyhow(e.into()))
+}
+
+fn validate_username(username: &str) -> Result<()> {
+    let valid_chars = username
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '.' | '_' | '-'));
+
+    if !(3..=50).contains(&username.len()) || !valid_chars {
+        return Err(AppError::Validation(
+            "Username must be 3-50 characters: lowercase letters, digits, '.', '_' or '-'".to_string(),
+        ));
+    }
+    if RESERVED_USERNAMES.contains(&username) {
+        return Err(AppError::Validation(format!("Username '{}' is reserved", username)));
+    }
+
+    Ok(())
+}
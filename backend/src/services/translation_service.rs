@@ -0,0 +1,428 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use crate::error::{AppError, Result};
+use crate::locale::{ContentLocale, LocaleConfig, LEGACY_LOCALES};
+use crate::models::restaurant::{Category, MenuItem, RestaurantInfo};
+use crate::services::webhook_service;
+
+/// A translatable table. Base columns hold the default locale.
+struct EntitySpec {
+    entity: &'static str,
+    table: &'static str,
+    fields: &'static [&'static str],
+    // Fields that also have `<field>_ru` / `<field>_pl` columns
+    legacy_fields: &'static [&'static str],
+    // Whether changes are reported as `menu.updated` webhooks
+    is_menu: bool,
+}
+
+const ENTITIES: [EntitySpec; 3] = [
+    EntitySpec {
+        entity: "category",
+        table: "categories",
+        fields: &["name", "description"],
+        legacy_fields: &["name"],
+        is_menu: true,
+    },
+    EntitySpec {
+        entity: "menu_item",
+        table: "menu_items",
+        fields: &["name", "description"],
+        legacy_fields: &["name", "description"],
+        is_menu: true,
+    },
+    EntitySpec {
+        entity: "restaurant_info",
+        table: "restaurant_info",
+        fields: &[
+            "name",
+            "description",
+            "hero_title",
+            "hero_subtitle",
+            "hero_description",
+            "featured_dish_title",
+            "featured_dish_description",
+        ],
+        legacy_fields: &["name", "description"],
+        is_menu: false,
+    },
+];
+
+fn spec(entity: &str) -> Result<&'static EntitySpec> {
+    ENTITIES.iter().find(|spec| spec.entity == entity).ok_or_else(|| {
+        let known: Vec<&str> = ENTITIES.iter().map(|spec| spec.entity).collect();
+        AppError::NotFound(format!("Unknown entity '{}'. Must be one of: {}", entity, known.join(", ")))
+    })
+}
+
+/// Model with text fields that can be swapped for a translation
+pub trait Translatable {
+    const ENTITY: &'static str;
+
+    fn entity_id(&self) -> i32;
+
+    fn set_text(&mut self, field: &str, text: String);
+}
+
+impl Translatable for Category {
+    const ENTITY: &'static str = "category";
+
+    fn entity_id(&self) -> i32 {
+        self.id
+    }
+
+    fn set_text(&mut self, field: &str, text: String) {
+        match field {
+            "name" => self.name = text,
+            "description" => self.description = Some(text),
+            _ => {}
+        }
+    }
+}
+
+impl Translatable for MenuItem {
+    const ENTITY: &'static str = "menu_item";
+
+    fn entity_id(&self) -> i32 {
+        self.id
+    }
+
+    fn set_text(&mut self, field: &str, text: String) {
+        match field {
+            "name" => self.name = text,
+            "description" => self.description = text,
+            _ => {}
+        }
+    }
+}
+
+impl Translatable for RestaurantInfo {
+    const ENTITY: &'static str = "restaurant_info";
+
+    fn entity_id(&self) -> i32 {
+        self.id
+    }
+
+    fn set_text(&mut self, field: &str, text: String) {
+        match field {
+            "name" => self.name = text,
+            "description" => self.description = Some(text),
+            "hero_title" => self.hero_title = Some(text),
+            "hero_subtitle" => self.hero_subtitle = Some(text),
+            "hero_description" => self.hero_description = Some(text),
+            "featured_dish_title" => self.featured_dish_title = Some(text),
+            "featured_dish_description" => self.featured_dish_description = Some(text),
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct TranslationRow {
+    entity_id: i32,
+    field: String,
+    locale: String,
+    text: String,
+}
+
+/// Replace translatable fields with the best match for the requested locale.
+/// Does nothing unless the client asked for resolved strings (`?lang=`).
+pub async fn localize<T: Translatable>(pool: &PgPool, items: &mut [T], locale: &ContentLocale) -> Result<()> {
+    let Some(chain) = &locale.chain else {
+        return Ok(());
+    };
+
+    // Locales preferred over the base columns, best first
+    let wanted: Vec<String> = chain
+        .iter()
+        .take_while(|l| **l != locale.default_locale)
+        .cloned()
+        .collect();
+    if wanted.is_empty() || items.is_empty() {
+        return Ok(());
+    }
+
+    let ids: Vec<i32> = items.iter().map(Translatable::entity_id).collect();
+    let rows = sqlx::query_as::<_, TranslationRow>(
+        r#"
+        SELECT entity_id, field, locale, text
+        FROM translations
+        WHERE entity = $1 AND entity_id = ANY($2) AND locale = ANY($3)
+        "#,
+    )
+    .bind(T::ENTITY)
+    .bind(&ids)
+    .bind(&wanted)
+    .fetch_all(pool)
+    .await?;
+
+    // (entity_id, field) -> (rank in chain, text) of the best translation so far
+    let mut best: HashMap<(i32, String), (usize, String)> = HashMap::new();
+    for row in rows {
+        let rank = wanted.iter().position(|l| *l == row.locale).unwrap_or(usize::MAX);
+        let entry = best.entry((row.entity_id, row.field)).or_insert((usize::MAX, String::new()));
+        if rank < entry.0 {
+            *entry = (rank, row.text);
+        }
+    }
+
+    for item in items.iter_mut() {
+        let id = item.entity_id();
+        for field in spec(T::ENTITY)?.fields {
+            if let Some((_, text)) = best.remove(&(id, field.to_string())) {
+                item.set_text(field, text);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+pub struct EntityTranslations {
+    pub entity: String,
+    pub entity_id: i32,
+    // field -> locale -> text (the default locale comes from the base column)
+    pub fields: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+// locale -> field -> text; null or "" removes the translation
+pub type TranslationUpdate = BTreeMap<String, BTreeMap<String, Option<String>>>;
+
+#[derive(Debug, Deserialize)]
+pub struct MissingFilters {
+    pub locale: String,
+    pub entity: Option<String>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct MissingTranslation {
+    pub entity: String,
+    pub entity_id: i32,
+    pub field: String,
+    // Default-locale text to translate from
+    pub source: String,
+}
+
+/// Admin management of translations for any configured locale
+pub struct TranslationService {
+    pool: PgPool,
+    config: Arc<LocaleConfig>,
+}
+
+impl TranslationService {
+    pub fn new(pool: PgPool, config: Arc<LocaleConfig>) -> Self {
+        Self { pool, config }
+    }
+
+    pub fn config(&self) -> &LocaleConfig {
+        &self.config
+    }
+
+    pub async fn get(&self, entity: &str, id: i32) -> Result<EntityTranslations> {
+        let spec = spec(entity)?;
+
+        let base: serde_json::Value = sqlx::query_scalar(&format!(
+            "SELECT to_jsonb(t) FROM {} t WHERE id = $1",
+            spec.table
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("{} with ID {} not found", spec.entity, id)))?;
+
+        let rows = sqlx::query_as::<_, TranslationRow>(
+            r#"
+            SELECT entity_id, field, locale, text
+            FROM translations
+            WHERE entity = $1 AND entity_id = $2
+            "#,
+        )
+        .bind(spec.entity)
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut fields: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+        for field in spec.fields {
+            let mut locales = BTreeMap::new();
+            if let Some(text) = base[field].as_str().filter(|t| !t.is_empty()) {
+                locales.insert(self.config.default_locale.clone(), text.to_string());
+            }
+            fields.insert(field.to_string(), locales);
+        }
+        for row in rows {
+            fields.entry(row.field).or_default().insert(row.locale, row.text);
+        }
+
+        Ok(EntityTranslations { entity: spec.entity.to_string(), entity_id: id, fields })
+    }
+
+    /// Set or remove translations in one transaction.
+    /// The default locale writes the base column; `ru`/`pl` also keep the
+    /// legacy `<field>_ru`/`<field>_pl` columns in sync.
+    pub async fn update(&self, entity: &str, id: i32, update: TranslationUpdate) -> Result<EntityTranslations> {
+        let spec = spec(entity)?;
+
+        for (locale, fields) in &update {
+            if !self.config.is_supported(locale) {
+                return Err(AppError::Validation(format!(
+                    "Unsupported locale '{}'. Configured: {}",
+                    locale,
+                    self.config.locales.join(", ")
+                )));
+            }
+            if let Some(field) = fields.keys().find(|f| !spec.fields.contains(&f.as_str())) {
+                return Err(AppError::Validation(format!(
+                    "'{}' is not translatable for {}. Fields: {}",
+                    field,
+                    spec.entity,
+                    spec.fields.join(", ")
+                )));
+            }
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        let exists: bool = sqlx::query_scalar(&format!("SELECT EXISTS(SELECT 1 FROM {} WHERE id = $1)", spec.table))
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+        if !exists {
+            return Err(AppError::NotFound(format!("{} with ID {} not found", spec.entity, id)));
+        }
+
+        for (locale, fields) in &update {
+            for (field, text) in fields {
+                let text = text.as_deref().map(str::trim).filter(|t| !t.is_empty());
+                Self::write(&mut tx, spec, id, locale, field, text, &self.config.default_locale).await?;
+            }
+        }
+
+        if spec.is_menu {
+            webhook_service::dispatch_menu_change(
+                &mut tx,
+                spec.entity,
+                "translated",
+                id,
+                Some(serde_json::json!(&update)),
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        self.get(entity, id).await
+    }
+
+    /// Remove every field of one locale
+    pub async fn delete_locale(&self, entity: &str, id: i32, locale: &str) -> Result<EntityTranslations> {
+        if locale == self.config.default_locale {
+            return Err(AppError::Validation("The default locale cannot be removed".to_string()));
+        }
+
+        let spec = spec(entity)?;
+        let fields = spec.fields.iter().map(|field| (field.to_string(), None)).collect();
+        self.update(entity, id, BTreeMap::from([(locale.to_string(), fields)])).await
+    }
+
+    /// Texts that have a default-locale value but no translation in `locale`
+    pub async fn missing(&self, filters: MissingFilters) -> Result<Vec<MissingTranslation>> {
+        if !self.config.is_supported(&filters.locale) || filters.locale == self.config.default_locale {
+            return Err(AppError::Validation(format!(
+                "Locale must be one of: {}",
+                self.config.locales.iter().skip(1).cloned().collect::<Vec<_>>().join(", ")
+            )));
+        }
+
+        let mut missing = Vec::new();
+        for spec in ENTITIES.iter().filter(|s| filters.entity.as_deref().is_none_or(|e| e == s.entity)) {
+            for field in spec.fields {
+                let rows = sqlx::query_as::<_, MissingTranslation>(&format!(
+                    r#"
+                    SELECT $1::VARCHAR AS entity, t.id AS entity_id, $2::VARCHAR AS field, t.{field} AS source
+                    FROM {table} t
+                    WHERE btrim(COALESCE(t.{field}, '')) <> ''
+                      AND NOT EXISTS (
+                          SELECT 1 FROM translations tr
+                          WHERE tr.entity = $1 AND tr.entity_id = t.id AND tr.field = $2 AND tr.locale = $3
+                      )
+                    ORDER BY t.id
+                    "#,
+                    field = field,
+                    table = spec.table
+                ))
+                .bind(spec.entity)
+                .bind(field)
+                .bind(&filters.locale)
+                .fetch_all(&self.pool)
+                .await?;
+
+                missing.extend(rows);
+            }
+        }
+
+        Ok(missing)
+    }
+
+    async fn write(
+        tx: &mut Transaction<'_, Postgres>,
+        spec: &EntitySpec,
+        id: i32,
+        locale: &str,
+        field: &str,
+        text: Option<&str>,
+        default_locale: &str,
+    ) -> Result<()> {
+        // Field and table names come from ENTITIES, never from the request
+        let field = spec.fields.iter().find(|f| **f == field).copied().unwrap_or_default();
+
+        if locale == default_locale {
+            let text = text.ok_or_else(|| {
+                AppError::Validation(format!("{} in the default locale cannot be empty", field))
+            })?;
+            sqlx::query(&format!("UPDATE {} SET {} = $2 WHERE id = $1", spec.table, field))
+                .bind(id)
+                .bind(text)
+                .execute(&mut **tx)
+                .await?;
+        } else if LEGACY_LOCALES.contains(&locale) && spec.legacy_fields.contains(&field) {
+            // The sync trigger updates `translations`
+            sqlx::query(&format!("UPDATE {} SET {}_{} = $2 WHERE id = $1", spec.table, field, locale))
+                .bind(id)
+                .bind(text.unwrap_or_default())
+                .execute(&mut **tx)
+                .await?;
+        } else if let Some(text) = text {
+            sqlx::query(
+                r#"
+                INSERT INTO translations (entity, entity_id, field, locale, text)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (entity, entity_id, field, locale) DO UPDATE SET text = EXCLUDED.text
+                "#,
+            )
+            .bind(spec.entity)
+            .bind(id)
+            .bind(field)
+            .bind(locale)
+            .bind(text)
+            .execute(&mut **tx)
+            .await?;
+        } else {
+            sqlx::query(
+                "DELETE FROM translations WHERE entity = $1 AND entity_id = $2 AND field = $3 AND locale = $4",
+            )
+            .bind(spec.entity)
+            .bind(id)
+            .bind(field)
+            .bind(locale)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Ok(())
+    }
+}
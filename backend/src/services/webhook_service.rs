@@ -0,0 +1,441 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::Sha256;
+use sqlx::{PgPool, Postgres, Transaction};
+
+use crate::error::{AppError, Result};
+use crate::models::webhook::{
+    CreateWebhookEndpoint, UpdateWebhookEndpoint, WebhookDelivery, WebhookDeliveryFilters,
+    WebhookEndpoint,
+};
+use crate::services::job_queue::{self, Job, JobHandler};
+
+pub const JOB_WEBHOOK_DELIVER: &str = "webhook.deliver";
+
+pub const EVENT_ORDER_CREATED: &str = "order.created";
+pub const EVENT_ORDER_STATUS_CHANGED: &str = "order.status_changed";
+pub const EVENT_MENU_UPDATED: &str = "menu.updated";
+
+pub const WEBHOOK_EVENTS: [&str; 3] = [EVENT_ORDER_CREATED, EVENT_ORDER_STATUS_CHANGED, EVENT_MENU_UPDATED];
+
+pub const SIGNATURE_HEADER: &str = "X-Fodi-Signature";
+
+// Only the start of the receiver's response is kept in the delivery log
+const MAX_RESPONSE_BODY_CHARS: usize = 2000;
+
+const ENDPOINT_COLUMNS: &str = "id, url, secret, events, description, is_active, created_at, updated_at";
+
+const DELIVERY_COLUMNS: &str = r#"id, endpoint_id, event, payload, status, attempts,
+    response_status, response_body, last_error, created_at, delivered_at"#;
+
+#[derive(Debug, sqlx::FromRow)]
+struct PendingDelivery {
+    id: i64,
+    event: String,
+    payload: serde_json::Value,
+    status: String,
+    url: String,
+    secret: String,
+    is_active: bool,
+}
+
+/// Queue `event` for every active endpoint subscribed to it.
+/// Must be called inside the transaction that makes the change, like
+/// `notification_service::enqueue_order_event`.
+pub async fn dispatch(
+    tx: &mut Transaction<'_, Postgres>,
+    event: &str,
+    data: serde_json::Value,
+) -> Result<()> {
+    let endpoint_ids: Vec<i32> = sqlx::query_scalar(
+        "SELECT id FROM webhook_endpoints WHERE is_active AND $1 = ANY(events)",
+    )
+    .bind(event)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    if endpoint_ids.is_empty() {
+        return Ok(());
+    }
+
+    let payload = serde_json::json!({
+        "id": uuid::Uuid::new_v4(),
+        "event": event,
+        "created_at": Utc::now(),
+        "data": data,
+    });
+
+    for endpoint_id in endpoint_ids {
+        let delivery_id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO webhook_deliveries (endpoint_id, event, payload)
+            VALUES ($1, $2, $3)
+            RETURNING id
+            "#,
+        )
+        .bind(endpoint_id)
+        .bind(event)
+        .bind(&payload)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        job_queue::enqueue(
+            &mut **tx,
+            JOB_WEBHOOK_DELIVER,
+            serde_json::json!({ "delivery_id": delivery_id }),
+            None,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// `menu.updated` for a created, updated, deleted or restored menu item or category
+pub async fn dispatch_menu_change(
+    tx: &mut Transaction<'_, Postgres>,
+    entity: &str,
+    action: &str,
+    id: i32,
+    data: Option<serde_json::Value>,
+) -> Result<()> {
+    dispatch(
+        tx,
+        EVENT_MENU_UPDATED,
+        serde_json::json!({ "entity": entity, "action": action, "id": id, "data": data }),
+    )
+    .await
+}
+
+/// `sha256=<hex>` HMAC of `"{timestamp}.{body}"` keyed with the endpoint secret
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("whsec_{}", hex::encode(bytes))
+}
+
+fn validate_url(url: &str) -> Result<()> {
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|_| AppError::Validation(format!("Invalid webhook URL: {}", url)))?;
+
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(AppError::Validation("Webhook URL must use http or https".to_string()));
+    }
+
+    Ok(())
+}
+
+fn validate_events(events: &[String]) -> Result<()> {
+    if events.is_empty() {
+        return Err(AppError::Validation("At least one event is required".to_string()));
+    }
+
+    if let Some(unknown) = events.iter().find(|e| !WEBHOOK_EVENTS.contains(&e.as_str())) {
+        return Err(AppError::Validation(format!(
+            "Unknown event '{}'. Must be one of: {}",
+            unknown,
+            WEBHOOK_EVENTS.join(", ")
+        )));
+    }
+
+    Ok(())
+}
+
+/// Webhook endpoint management and delivery (`webhook.deliver` jobs)
+pub struct WebhookService {
+    pool: PgPool,
+    client: reqwest::Client,
+}
+
+impl WebhookService {
+    pub fn new(pool: PgPool) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self { pool, client }
+    }
+
+    // ===== ENDPOINTS =====
+
+    pub async fn list_endpoints(&self) -> Result<Vec<WebhookEndpoint>> {
+        let endpoints = sqlx::query_as::<_, WebhookEndpoint>(&format!(
+            "SELECT {} FROM webhook_endpoints ORDER BY id ASC",
+            ENDPOINT_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(endpoints)
+    }
+
+    pub async fn get_endpoint(&self, id: i32) -> Result<WebhookEndpoint> {
+        sqlx::query_as::<_, WebhookEndpoint>(&format!(
+            "SELECT {} FROM webhook_endpoints WHERE id = $1",
+            ENDPOINT_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Webhook endpoint with ID {} not found", id)))
+    }
+
+    pub async fn create_endpoint(&self, data: CreateWebhookEndpoint) -> Result<WebhookEndpoint> {
+        validate_url(&data.url)?;
+        validate_events(&data.events)?;
+
+        let endpoint = sqlx::query_as::<_, WebhookEndpoint>(&format!(
+            r#"
+            INSERT INTO webhook_endpoints (url, secret, events, description)
+            VALUES ($1, $2, $3, $4)
+            RETURNING {}
+            "#,
+            ENDPOINT_COLUMNS
+        ))
+        .bind(data.url)
+        .bind(data.secret.filter(|s| !s.is_empty()).unwrap_or_else(generate_secret))
+        .bind(data.events)
+        .bind(data.description)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(endpoint)
+    }
+
+    pub async fn update_endpoint(&self, id: i32, data: UpdateWebhookEndpoint) -> Result<WebhookEndpoint> {
+        let mut endpoint = self.get_endpoint(id).await?;
+
+        if let Some(url) = data.url {
+            validate_url(&url)?;
+            endpoint.url = url;
+        }
+        if let Some(secret) = data.secret.filter(|s| !s.is_empty()) {
+            endpoint.secret = secret;
+        }
+        if let Some(events) = data.events {
+            validate_events(&events)?;
+            endpoint.events = events;
+        }
+        if let Some(description) = data.description {
+            endpoint.description = Some(description);
+        }
+        if let Some(is_active) = data.is_active {
+            endpoint.is_active = is_active;
+        }
+
+        let endpoint = sqlx::query_as::<_, WebhookEndpoint>(&format!(
+            r#"
+            UPDATE webhook_endpoints
+            SET url = $2, secret = $3, events = $4, description = $5, is_active = $6
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            ENDPOINT_COLUMNS
+        ))
+        .bind(id)
+        .bind(endpoint.url)
+        .bind(endpoint.secret)
+        .bind(endpoint.events)
+        .bind(endpoint.description)
+        .bind(endpoint.is_active)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(endpoint)
+    }
+
+    /// Deletes the endpoint together with its delivery log
+    pub async fn delete_endpoint(&self, id: i32) -> Result<()> {
+        let result = sqlx::query("DELETE FROM webhook_endpoints WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Webhook endpoint with ID {} not found", id)));
+        }
+
+        Ok(())
+    }
+
+    // ===== DELIVERIES =====
+
+    pub async fn list_deliveries(&self, filters: WebhookDeliveryFilters) -> Result<Vec<WebhookDelivery>> {
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(&format!(
+            r#"
+            SELECT {}
+            FROM webhook_deliveries
+            WHERE ($1::INTEGER IS NULL OR endpoint_id = $1)
+              AND ($2::VARCHAR IS NULL OR status = $2)
+            ORDER BY created_at DESC
+            LIMIT $3
+            "#,
+            DELIVERY_COLUMNS
+        ))
+        .bind(filters.endpoint_id)
+        .bind(filters.status)
+        .bind(filters.limit.unwrap_or(100).clamp(1, 500))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    pub async fn get_delivery(&self, id: i64) -> Result<WebhookDelivery> {
+        sqlx::query_as::<_, WebhookDelivery>(&format!(
+            "SELECT {} FROM webhook_deliveries WHERE id = $1",
+            DELIVERY_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Webhook delivery with ID {} not found", id)))
+    }
+
+    /// Send a logged delivery again (same payload and delivery ID)
+    pub async fn redeliver(&self, id: i64) -> Result<WebhookDelivery> {
+        let mut tx = self.pool.begin().await?;
+
+        let delivery = sqlx::query_as::<_, WebhookDelivery>(&format!(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = 'pending', last_error = NULL
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            DELIVERY_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Webhook delivery with ID {} not found", id)))?;
+
+        job_queue::enqueue(
+            &mut *tx,
+            JOB_WEBHOOK_DELIVER,
+            serde_json::json!({ "delivery_id": delivery.id, "redelivery": true }),
+            None,
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(delivery)
+    }
+
+    async fn send(&self, delivery: &PendingDelivery) -> anyhow::Result<(u16, String)> {
+        let body = delivery.payload.to_string();
+        let timestamp = Utc::now().timestamp();
+
+        let response = self.client
+            .post(&delivery.url)
+            .header("Content-Type", "application/json")
+            .header("User-Agent", "FodiFood-Webhooks/1.0")
+            .header("X-Fodi-Event", &delivery.event)
+            .header("X-Fodi-Delivery", delivery.id.to_string())
+            .header("X-Fodi-Timestamp", timestamp.to_string())
+            .header(SIGNATURE_HEADER, sign(&delivery.secret, timestamp, &body))
+            .body(body)
+            .send()
+            .await?;
+
+        let status = response.status().as_u16();
+        let text: String = response
+            .text()
+            .await
+            .unwrap_or_default()
+            .chars()
+            .take(MAX_RESPONSE_BODY_CHARS)
+            .collect();
+
+        Ok((status, text))
+    }
+}
+
+#[async_trait]
+impl JobHandler for WebhookService {
+    fn kind(&self) -> &'static str {
+        JOB_WEBHOOK_DELIVER
+    }
+
+    async fn run(&self, job: &Job) -> anyhow::Result<()> {
+        let delivery_id = job.payload["delivery_id"]
+            .as_i64()
+            .ok_or_else(|| anyhow::anyhow!("Missing delivery_id"))?;
+
+        let delivery = sqlx::query_as::<_, PendingDelivery>(
+            r#"
+            SELECT d.id, d.event, d.payload, d.status, e.url, e.secret, e.is_active
+            FROM webhook_deliveries d
+            JOIN webhook_endpoints e ON e.id = d.endpoint_id
+            WHERE d.id = $1
+            "#,
+        )
+        .bind(delivery_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        // Endpoint deleted or delivery already succeeded in an earlier run
+        let Some(delivery) = delivery.filter(|d| d.status != "delivered") else {
+            return Ok(());
+        };
+
+        if !delivery.is_active {
+            sqlx::query(
+                "UPDATE webhook_deliveries SET status = 'failed', last_error = 'Endpoint is disabled' WHERE id = $1",
+            )
+            .bind(delivery.id)
+            .execute(&self.pool)
+            .await?;
+
+            return Ok(());
+        }
+
+        let (response_status, response_body, result) = match self.send(&delivery).await {
+            Ok((status, body)) if (200..300).contains(&status) => (Some(status as i32), Some(body), Ok(())),
+            Ok((status, body)) => (
+                Some(status as i32),
+                Some(body),
+                Err(anyhow::anyhow!("Endpoint responded with HTTP {}", status)),
+            ),
+            Err(e) => (None, None, Err(e)),
+        };
+
+        let (status, last_error) = match &result {
+            Ok(()) => ("delivered", None),
+            Err(e) if job.is_last_attempt() => ("failed", Some(e.to_string())),
+            Err(e) => ("pending", Some(e.to_string())),
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = $2, attempts = attempts + 1, response_status = $3, response_body = $4,
+                last_error = $5, delivered_at = CASE WHEN $2 = 'delivered' THEN NOW() END
+            WHERE id = $1
+            "#,
+        )
+        .bind(delivery.id)
+        .bind(status)
+        .bind(response_status)
+        .bind(response_body)
+        .bind(last_error)
+        .execute(&self.pool)
+        .await?;
+
+        result
+    }
+}
@@ -37,6 +37,11 @@ pub enum WsMessage {
         order_number: String,
         status: String,
     },
+    // Menu items changed (e.g. by a bulk admin operation); clients refetch them
+    MenuUpdated {
+        action: String,
+        menu_item_ids: Vec<i32>,
+    },
     // Analytics update
     AnalyticsUpdate {
         total_orders: i64,
@@ -72,30 +77,23 @@ impl WsState {
         }
     }
 
-    /// Get current analytics from database
+    /// Get current analytics from database (cancelled orders are not counted)
     async fn get_analytics(&self) -> Result<WsMessage, sqlx::Error> {
-        // Get total orders
-        let total_orders: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM orders")
-            .fetch_one(&self.pool)
-            .await
-            .unwrap_or(0);
-
-        // Get total revenue
-        let total_revenue: Option<BigDecimal> = 
-            sqlx::query_scalar("SELECT COALESCE(SUM(total), 0) FROM orders")
-                .fetch_one(&self.pool)
-                .await
-                .ok();
-
-        // Get pending orders
-        let pending_orders: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM orders WHERE status = 'pending'")
-            .fetch_one(&self.pool)
-            .await
-            .unwrap_or(0);
+        let (total_orders, total_revenue, pending_orders): (i64, BigDecimal, i64) = sqlx::query_as(
+            r#"
+            SELECT
+                COUNT(*) FILTER (WHERE status <> 'cancelled'),
+                COALESCE(SUM(total) FILTER (WHERE status <> 'cancelled'), 0),
+                COUNT(*) FILTER (WHERE status = 'pending')
+            FROM orders
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await?;
 
         Ok(WsMessage::AnalyticsUpdate {
             total_orders,
-            total_revenue: total_revenue.unwrap_or_default().to_string(),
+            total_revenue: total_revenue.to_string(),
             pending_orders,
         })
     }
@@ -0,0 +1,83 @@
+mod support;
+
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use jsonwebtoken::{encode, EncodingKey, Header};
+use serde_json::json;
+use sqlx::PgPool;
+
+use portfolio_api::models_single::{Claims, ROLE_ADMIN};
+use portfolio_api::services::staff_service::StaffService;
+use support::{TestApp, JWT_SECRET};
+
+const ADMIN_ROUTE: &str = "/api/restaurant/admin/categories";
+
+fn token_signed_with(secret: &str, sub: &str, role: &str) -> String {
+    let claims = Claims {
+        sub: sub.to_string(),
+        role: role.to_string(),
+        exp: (Utc::now() + Duration::hours(1)).timestamp() as usize,
+    };
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_bytes())).unwrap()
+}
+
+#[sqlx::test]
+async fn admin_routes_require_a_valid_token(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+
+    assert_eq!(app.get("/api/restaurant/categories", None).await.status, StatusCode::OK);
+    assert_eq!(app.get(ADMIN_ROUTE, None).await.status, StatusCode::UNAUTHORIZED);
+    assert_eq!(app.get(ADMIN_ROUTE, Some("not-a-jwt")).await.status, StatusCode::UNAUTHORIZED);
+
+    let forged = token_signed_with("some-other-secret", "admin", ROLE_ADMIN);
+    assert_eq!(app.get(ADMIN_ROUTE, Some(&forged)).await.status, StatusCode::UNAUTHORIZED);
+
+    let token = app.admin_token().await;
+    assert_eq!(app.get(ADMIN_ROUTE, Some(&token)).await.status, StatusCode::OK);
+
+    let created = app
+        .post(
+            ADMIN_ROUTE,
+            None,
+            json!({ "name": "Ramen", "name_ru": "Рамен", "name_pl": "Ramen", "slug": "ramen" }),
+        )
+        .await;
+    assert_eq!(created.status, StatusCode::UNAUTHORIZED);
+    let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM categories WHERE slug = 'ramen'")
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, 0);
+}
+
+#[sqlx::test]
+async fn wrong_admin_password_gets_no_token(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+
+    let response = app.post("/api/auth/login", None, json!({ "password": "wrong" })).await;
+    assert_ne!(response.status, StatusCode::OK);
+    assert!(response.body.get("token").is_none());
+}
+
+#[sqlx::test]
+async fn staff_accounts_log_in_with_username(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    StaffService::new(app.pool.clone())
+        .create("kitchen", "correct horse battery")
+        .await
+        .unwrap();
+
+    let token = app
+        .login(json!({ "username": "kitchen", "password": "correct horse battery" }))
+        .await;
+    assert_eq!(app.get(ADMIN_ROUTE, Some(&token)).await.status, StatusCode::OK);
+
+    let response = app
+        .post("/api/auth/login", None, json!({ "username": "kitchen", "password": "wrong password" }))
+        .await;
+    assert_ne!(response.status, StatusCode::OK);
+
+    // A token for another role is valid but not enough for the admin panel
+    let customer_like = token_signed_with(JWT_SECRET, "kitchen", "customer");
+    assert_eq!(app.get(ADMIN_ROUTE, Some(&customer_like)).await.status, StatusCode::FORBIDDEN);
+}
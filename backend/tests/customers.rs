@@ -0,0 +1,84 @@
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+use support::{CategoryBuilder, MenuItemBuilder, OrderBuilder, TestApp};
+
+fn registration(email: &str, phone: &str) -> Value {
+    json!({ "name": "Anna Nowak", "email": email, "phone": phone, "password": "correct horse" })
+}
+
+#[sqlx::test]
+async fn phone_numbers_identify_one_account_whatever_the_format(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+
+    let response = app.post("/api/customer/register", None, registration("anna@example.com", "600 100 200")).await;
+    assert_eq!(response.status, StatusCode::CREATED, "{}", response.body);
+    assert_eq!(response.body["customer"]["phone"], "+48600100200");
+
+    let same_phone = registration("other@example.com", "+48 600-100-200");
+    assert_eq!(app.post("/api/customer/register", None, same_phone).await.status, StatusCode::CONFLICT);
+}
+
+#[sqlx::test]
+async fn concurrent_sign_ups_with_one_email_end_in_a_conflict(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+
+    // Password hashing keeps them all between the lookup and the insert
+    let attempts = (0..4).map(|i| {
+        let phone = format!("60010020{}", i);
+        let app = &app;
+        async move { app.post("/api/customer/register", None, registration("anna@example.com", &phone)).await }
+    });
+    let mut statuses: Vec<StatusCode> = futures::future::join_all(attempts)
+        .await
+        .into_iter()
+        .map(|response| response.status)
+        .collect();
+    statuses.sort();
+
+    assert_eq!(
+        statuses,
+        vec![StatusCode::CREATED, StatusCode::CONFLICT, StatusCode::CONFLICT, StatusCode::CONFLICT]
+    );
+}
+
+#[sqlx::test]
+async fn guest_orders_are_linked_when_typed_without_country_code(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let sushi = CategoryBuilder::new("Sushi").insert(&app.pool).await;
+    let roll = MenuItemBuilder::new(sushi, "California Roll").insert(&app.pool).await;
+
+    // Phones on orders are kept as the guest typed them
+    for phone in ["600 100 200", "0048600100200", "+48 600 100 200", "601 000 000"] {
+        let order = OrderBuilder::new().item(roll, 1).create(&app).await;
+        sqlx::query("UPDATE orders SET customer_phone = $1 WHERE id = $2")
+            .bind(phone)
+            .bind(order["id"].as_i64().unwrap() as i32)
+            .execute(&app.pool)
+            .await
+            .unwrap();
+    }
+
+    // A code as if sent by SMS
+    sqlx::query(
+        "INSERT INTO customer_otp_codes (phone, code_hash, expires_at) VALUES ($1, $2, NOW() + INTERVAL '5 minutes')",
+    )
+    .bind("+48600100200")
+    .bind(bcrypt::hash("123456", 4).unwrap())
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    let response = app
+        .post("/api/customer/otp/verify", None, json!({ "phone": "600100200", "code": "123456", "name": "Anna" }))
+        .await;
+    assert_eq!(response.status, StatusCode::OK, "{}", response.body);
+    let token = response.body["token"].as_str().unwrap().to_string();
+
+    let orders = app.get("/api/customer/orders", Some(&token)).await;
+    assert_eq!(orders.status, StatusCode::OK);
+    assert_eq!(orders.body.as_array().unwrap().len(), 3);
+}
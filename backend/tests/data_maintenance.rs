@@ -0,0 +1,73 @@
+mod support;
+
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use sqlx::PgPool;
+
+use portfolio_api::services::data_maintenance_service::DataMaintenanceService;
+use support::{assert_money, CategoryBuilder, MenuItemBuilder, OrderBuilder, TestApp};
+
+// What OrderBuilder puts in every order
+const PERSONAL_DATA: &[&str] = &["%Anna Nowak%", "%600 100 200%", "%anna@example.com%", "%Marszałkowska%"];
+
+async fn rows_with_personal_data(app: &TestApp, table: &str) -> i64 {
+    sqlx::query_scalar(&format!(
+        "SELECT COUNT(*) FROM {table} t WHERE row_to_json(t)::TEXT ILIKE ANY($1)"
+    ))
+    .bind(PERSONAL_DATA)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap()
+}
+
+#[sqlx::test]
+async fn anonymized_orders_keep_no_personal_data(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let token = app.admin_token().await;
+    let webhook = app
+        .post(
+            "/api/restaurant/admin/webhooks",
+            Some(&token),
+            json!({ "url": "https://pos.example.com/hooks", "events": ["order.created", "order.status_changed"] }),
+        )
+        .await;
+    assert_eq!(webhook.status, StatusCode::CREATED, "webhook failed: {}", webhook.body);
+
+    let sushi = CategoryBuilder::new("Sushi").insert(&app.pool).await;
+    let roll = MenuItemBuilder::new(sushi, "California Roll").price("45.00").insert(&app.pool).await;
+    let order = OrderBuilder::new().item(roll, 2).create(&app).await;
+    let uri = format!("/api/restaurant/admin/orders/{}/status", order["id"]);
+    app.put(&uri, Some(&token), json!({ "status": "delivered" })).await;
+
+    sqlx::query("UPDATE orders SET created_at = NOW() - INTERVAL '400 days'")
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    for table in ["orders", "webhook_deliveries"] {
+        assert!(rows_with_personal_data(&app, table).await > 0, "nothing to anonymize in {}", table);
+    }
+
+    let report = DataMaintenanceService::new(app.pool.clone())
+        .anonymize_customers(Utc::now() - Duration::days(365), false)
+        .await
+        .unwrap();
+    assert_eq!(report.orders, 1);
+
+    for table in ["orders", "order_items", "webhook_deliveries", "notification_outbox", "jobs"] {
+        assert_eq!(rows_with_personal_data(&app, table).await, 0, "personal data left in {}", table);
+    }
+
+    // The delivery log and the amounts stay
+    let payloads: Vec<serde_json::Value> =
+        sqlx::query_scalar("SELECT payload FROM webhook_deliveries ORDER BY id")
+            .fetch_all(&app.pool)
+            .await
+            .unwrap();
+    assert_eq!(payloads.len(), 2);
+    for payload in &payloads {
+        assert_eq!(payload["data"]["customer_name"], "Anonymized");
+        assert_eq!(payload["data"]["order_number"], order["order_number"]);
+        assert_money(&payload["data"]["total"], "100.00");
+    }
+}
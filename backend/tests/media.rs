@@ -0,0 +1,115 @@
+mod support;
+
+use axum::http::StatusCode;
+use chrono::Duration;
+use serde_json::json;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+use portfolio_api::services::image_storage::ImageStorage;
+use portfolio_api::services::media_service::MediaService;
+use support::TestApp;
+
+const GRACE_HOURS: i64 = 24;
+
+/// Runs the orphan sweep as if every upload and release happened two days ago
+async fn sweep_after_grace(app: &TestApp) -> u64 {
+    sqlx::query(
+        "UPDATE media_assets SET created_at = NOW() - INTERVAL '2 days', released_at = released_at - INTERVAL '2 days'",
+    )
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    let storage: Arc<dyn ImageStorage> = app.storage.clone();
+    MediaService::new(app.pool.clone(), storage, Duration::hours(GRACE_HOURS))
+        .sweep()
+        .await
+        .unwrap()
+}
+
+async fn asset_exists(app: &TestApp, public_id: &serde_json::Value) -> bool {
+    sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM media_assets WHERE public_id = $1)")
+        .bind(public_id.as_str().unwrap())
+        .fetch_one(&app.pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn images_used_by_blog_posts_survive_the_sweep(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let token = app.admin_token().await;
+    let cover = app.upload_image(&token, "blog").await;
+    let inline = app.upload_image(&token, "blog").await;
+    let referenced = app.upload_image(&token, "blog").await;
+    let unused = app.upload_image(&token, "blog").await;
+
+    // The body may point at any derivative of an upload
+    let content = format!(
+        "Intro\n\n![Plating]({} \"Plating\")\n\n![Kitchen][kitchen]\n\n[kitchen]: {}\n",
+        inline["derivatives"]["card"]["url"].as_str().unwrap(),
+        referenced["url"].as_str().unwrap()
+    );
+    let post = app
+        .post(
+            "/api/admin/blog",
+            Some(&token),
+            json!({
+                "slug": "summer-menu",
+                "title": "Summer menu",
+                "excerpt": "New dishes",
+                "content": content,
+                "date": "2026-06-01",
+                "author": "Chef",
+                "image": cover["url"],
+                "category": "News",
+                "read_time": "3 min",
+            }),
+        )
+        .await;
+    assert_eq!(post.status, StatusCode::CREATED, "create failed: {}", post.body);
+
+    assert_eq!(sweep_after_grace(&app).await, 1);
+    assert!(asset_exists(&app, &cover["public_id"]).await);
+    assert!(asset_exists(&app, &inline["public_id"]).await);
+    assert!(asset_exists(&app, &referenced["public_id"]).await);
+    assert!(!asset_exists(&app, &unused["public_id"]).await);
+
+    // Dropped from the post: released, then swept once the grace period passes
+    let uri = format!("/api/admin/blog/{}", post.body["id"].as_str().unwrap());
+    let updated = app.put(&uri, Some(&token), json!({ "content": "No pictures today" })).await;
+    assert_eq!(updated.status, StatusCode::OK, "update failed: {}", updated.body);
+
+    assert_eq!(sweep_after_grace(&app).await, 2);
+    assert!(asset_exists(&app, &cover["public_id"]).await);
+    assert!(!asset_exists(&app, &inline["public_id"]).await);
+}
+
+#[sqlx::test]
+async fn images_in_the_chef_profile_survive_the_sweep(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let token = app.admin_token().await;
+    let avatar = app.upload_image(&token, "portfolio").await;
+    let dish = app.upload_image(&token, "portfolio").await;
+    let unused = app.upload_image(&token, "portfolio").await;
+
+    let data = json!({
+        "hero": { "name": "Chef", "avatar": avatar["url"] },
+        "portfolio": [{ "title": "Tasting menu", "image": dish["derivatives"]["hero"]["url"] }],
+    });
+    let response = app.put("/api/portfolio", Some(&token), json!({ "data": data })).await;
+    assert_eq!(response.status, StatusCode::OK, "update failed: {}", response.body);
+
+    assert_eq!(sweep_after_grace(&app).await, 1);
+    assert!(asset_exists(&app, &avatar["public_id"]).await);
+    assert!(asset_exists(&app, &dish["public_id"]).await);
+    assert!(!asset_exists(&app, &unused["public_id"]).await);
+
+    let data = json!({ "hero": { "name": "Chef", "avatar": avatar["url"] } });
+    app.put("/api/portfolio", Some(&token), json!({ "data": data })).await;
+
+    assert_eq!(sweep_after_grace(&app).await, 1);
+    assert!(asset_exists(&app, &avatar["public_id"]).await);
+    assert!(!asset_exists(&app, &dish["public_id"]).await);
+}
@@ -0,0 +1,138 @@
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use sqlx::PgPool;
+
+use support::{assert_money, CategoryBuilder, MenuItemBuilder, OrderBuilder, TestApp};
+
+#[sqlx::test]
+async fn order_totals_come_from_menu_prices_and_delivery_fee(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let sushi = CategoryBuilder::new("Sushi").insert(&app.pool).await;
+    let roll = MenuItemBuilder::new(sushi, "California Roll").price("45.00").insert(&app.pool).await;
+    let miso = MenuItemBuilder::new(sushi, "Miso Soup").price("12.50").insert(&app.pool).await;
+
+    let order = OrderBuilder::new().item(roll, 2).item(miso, 3).create(&app).await;
+
+    assert_money(&order["subtotal"], "127.50");
+    assert_money(&order["delivery_fee"], "10");
+    assert_money(&order["total"], "137.50");
+    assert_eq!(order["status"], "pending");
+
+    let items = order["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["menu_item_name"], "California Roll");
+    assert_money(&items[0]["menu_item_price"], "45.00");
+    assert_eq!(items[0]["quantity"], 2);
+
+    // The customer's order page shows the same snapshot
+    let number = order["order_number"].as_str().unwrap();
+    let response = app.get(&format!("/api/restaurant/orders/{}", number), None).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_money(&response.body["total"], "137.50");
+}
+
+#[sqlx::test]
+async fn invalid_orders_are_rejected_and_not_stored(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let sushi = CategoryBuilder::new("Sushi").insert(&app.pool).await;
+    let sold_out = MenuItemBuilder::new(sushi, "Dragon Roll").unavailable().insert(&app.pool).await;
+
+    let empty = app.post("/api/restaurant/orders", None, OrderBuilder::new().payload()).await;
+    assert_eq!(empty.status, StatusCode::BAD_REQUEST);
+
+    let unavailable = OrderBuilder::new().item(sold_out, 1).payload();
+    let response = app.post("/api/restaurant/orders", None, unavailable).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+
+    let unknown = OrderBuilder::new().item(9999, 1).payload();
+    let response = app.post("/api/restaurant/orders", None, unknown).await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+
+    let orders: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM orders")
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(orders, 0);
+}
+
+#[sqlx::test]
+async fn combo_component_quantity_overflow_is_rejected(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let sets = CategoryBuilder::new("Sets").insert(&app.pool).await;
+    let combo = MenuItemBuilder::new(sets, "Party Set").insert(&app.pool).await;
+    let roll = MenuItemBuilder::new(sets, "California Roll").insert(&app.pool).await;
+    let slot: i32 = sqlx::query_scalar(
+        "INSERT INTO combo_slots (combo_item_id, name, min_choices, max_choices) VALUES ($1, 'Rolls', 1, 100000) RETURNING id",
+    )
+    .bind(combo)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO combo_slot_options (slot_id, menu_item_id) VALUES ($1, $2)")
+        .bind(slot)
+        .bind(roll)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    // 100000 sets of 100000 rolls do not fit the stored quantity
+    let mut payload = OrderBuilder::new().payload();
+    payload["items"] = json!([{
+        "menu_item_id": combo,
+        "quantity": 100000,
+        "components": [{ "slot_id": slot, "menu_item_id": roll, "quantity": 100000 }],
+    }]);
+    let response = app.post("/api/restaurant/orders", None, payload).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST, "{}", response.body);
+
+    // Two picks for one slot that only overflow when added up
+    let mut payload = OrderBuilder::new().payload();
+    payload["items"] = json!([{
+        "menu_item_id": combo,
+        "quantity": 1,
+        "components": [
+            { "slot_id": slot, "menu_item_id": roll, "quantity": 2000000000 },
+            { "slot_id": slot, "menu_item_id": roll, "quantity": 2000000000 },
+        ],
+    }]);
+    let response = app.post("/api/restaurant/orders", None, payload).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST, "{}", response.body);
+
+    let orders: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM orders")
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(orders, 0);
+}
+
+#[sqlx::test]
+async fn status_updates_move_the_order_through_its_lifecycle(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let sushi = CategoryBuilder::new("Sushi").insert(&app.pool).await;
+    let roll = MenuItemBuilder::new(sushi, "California Roll").insert(&app.pool).await;
+    let order = OrderBuilder::new().item(roll, 1).create(&app).await;
+    let uri = format!("/api/restaurant/admin/orders/{}/status", order["id"]);
+    let token = app.admin_token().await;
+
+    let response = app.put(&uri, Some(&token), json!({ "status": "preparing" })).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["status"], "preparing");
+    assert!(response.body["completed_at"].is_null());
+
+    let response = app.put(&uri, Some(&token), json!({ "status": "delivered" })).await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.body["status"], "delivered");
+    assert!(response.body["completed_at"].is_string());
+
+    let response = app.put(&uri, Some(&token), json!({ "status": "lost" })).await;
+    assert_eq!(response.status, StatusCode::BAD_REQUEST);
+
+    // Delivery queues the loyalty credit job
+    let jobs: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE kind = 'loyalty.credit'")
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(jobs, 1);
+}
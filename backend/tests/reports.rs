@@ -0,0 +1,86 @@
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+use portfolio_api::services::background_jobs::{DailyReportJob, JOB_DAILY_REPORT};
+use portfolio_api::services::job_queue::JobQueue;
+use portfolio_api::services::report_service::ReportService;
+use support::{assert_money, CategoryBuilder, MenuItemBuilder, OrderBuilder, TestApp};
+
+#[sqlx::test]
+async fn daily_report_job_stores_yesterday_and_queues_the_next_day(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let token = app.admin_token().await;
+    let sushi = CategoryBuilder::new("Sushi").insert(&app.pool).await;
+    let roll = MenuItemBuilder::new(sushi, "California Roll").price("45.00").insert(&app.pool).await;
+
+    OrderBuilder::new().item(roll, 2).create(&app).await;
+    let cancelled = OrderBuilder::new().item(roll, 1).create(&app).await;
+    let uri = format!("/api/restaurant/admin/orders/{}/cancel", cancelled["id"]);
+    assert_eq!(app.put(&uri, Some(&token), json!({})).await.status, StatusCode::OK);
+
+    // Noon of the previous day in the restaurant's timezone
+    sqlx::query(
+        r#"
+        UPDATE orders SET created_at =
+            ((NOW() AT TIME ZONE 'Europe/Warsaw')::DATE - 1 + TIME '12:00') AT TIME ZONE 'Europe/Warsaw'
+        "#,
+    )
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    // Queued at startup, due right away
+    let reports = Arc::new(ReportService::new(app.pool.clone()));
+    let queue = JobQueue::new(app.pool.clone()).register(Arc::new(DailyReportJob::new(app.pool.clone(), reports)));
+    assert_eq!(queue.run_due().await.unwrap(), 1);
+
+    let response = app.get("/api/restaurant/admin/reports/daily", Some(&token)).await;
+    assert_eq!(response.status, StatusCode::OK);
+    let rows = response.body.as_array().unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["orders"], 1);
+    assert_eq!(rows[0]["cancelled_orders"], 1);
+    assert_money(&rows[0]["revenue"], "100.00");
+
+    let next_runs: Vec<bool> = sqlx::query_scalar(
+        "SELECT run_at > NOW() FROM jobs WHERE kind = $1 AND status = 'queued'",
+    )
+    .bind(JOB_DAILY_REPORT)
+    .fetch_all(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(next_runs, vec![true]);
+}
+
+#[sqlx::test]
+async fn daily_report_schedule_survives_a_failed_run(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+
+    // The startup run gets one attempt and cannot store its report
+    sqlx::query("UPDATE jobs SET max_attempts = 1 WHERE kind = $1")
+        .bind(JOB_DAILY_REPORT)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    sqlx::query("ALTER TABLE daily_reports RENAME TO daily_reports_moved")
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    let reports = Arc::new(ReportService::new(app.pool.clone()));
+    let queue = JobQueue::new(app.pool.clone()).register(Arc::new(DailyReportJob::new(app.pool.clone(), reports)));
+    queue.run_due().await.unwrap();
+
+    let jobs: Vec<(String, bool)> = sqlx::query_as(
+        "SELECT status, run_at > NOW() FROM jobs WHERE kind = $1 ORDER BY id",
+    )
+    .bind(JOB_DAILY_REPORT)
+    .fetch_all(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(jobs, vec![("dead".to_string(), false), ("queued".to_string(), true)]);
+}
@@ -0,0 +1,343 @@
+//! Shared by the HTTP-level tests: the router from `create_app` over a fresh
+//! database, a fake image storage and fixture builders.
+//!
+//! Tests take the pool from `#[sqlx::test]`, which creates a throwaway
+//! database on the server in DATABASE_URL for every test, applies
+//! backend/migrations and drops it when the test passes.
+#![allow(dead_code)] // each test file uses its own part
+
+use async_trait::async_trait;
+use axum::body::Body;
+use axum::http::{header, Method, Request, StatusCode};
+use axum::Router;
+use base64::Engine;
+use bigdecimal::BigDecimal;
+use image::{ImageFormat, RgbImage};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::io::Cursor;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tower::ServiceExt;
+
+use portfolio_api::config::{AppConfig, Settings};
+use portfolio_api::{create_app, create_app_with_storage};
+use portfolio_api::services::image_storage::{ImageStorage, StoredImage};
+
+pub const ADMIN_PASSWORD: &str = "test-admin-password";
+pub const JWT_SECRET: &str = "integration-test-secret";
+
+pub struct TestApp {
+    pub router: Router,
+    pub pool: PgPool,
+    pub storage: Arc<FakeImageStorage>,
+}
+
+pub struct TestResponse {
+    pub status: StatusCode,
+    // Null for an empty body, a string for a body that is not JSON
+    pub body: Value,
+}
+
+impl TestApp {
+    /// Production settings, so no dev seeds: besides the sample menu from the
+    /// migrations the database holds only what the test inserts. The job
+    /// worker is off, so tests see queued jobs in `jobs` instead of racing it.
+    pub async fn new(pool: PgPool) -> Self {
+        let storage = Arc::new(FakeImageStorage::default());
+        let router = create_app_with_storage(pool.clone(), test_config(&[]), storage.clone())
+            .await
+            .expect("app starts");
+
+        Self { router, pool, storage }
+    }
+
+    /// Image storage chosen by `settings` (IMAGE_STORAGE, MEDIA_DIR...) like
+    /// in production; `storage` stays unused
+    pub async fn with_settings(pool: PgPool, settings: &[(&str, &str)]) -> Self {
+        let router = create_app(pool.clone(), test_config(settings)).await.expect("app starts");
+
+        Self {
+            router,
+            pool,
+            storage: Arc::new(FakeImageStorage::default()),
+        }
+    }
+
+    pub async fn send(&self, method: Method, uri: &str, token: Option<&str>, body: Option<Value>) -> TestResponse {
+        let mut request = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        }
+        let request = match body {
+            Some(body) => request
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => request.body(Body::empty()),
+        }
+        .expect("valid request");
+
+        let response = self.router.clone().oneshot(request).await.expect("router is infallible");
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        let body = if bytes.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&bytes).unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&bytes).into_owned()))
+        };
+
+        TestResponse { status, body }
+    }
+
+    pub async fn get(&self, uri: &str, token: Option<&str>) -> TestResponse {
+        self.send(Method::GET, uri, token, None).await
+    }
+
+    pub async fn post(&self, uri: &str, token: Option<&str>, body: Value) -> TestResponse {
+        self.send(Method::POST, uri, token, Some(body)).await
+    }
+
+    pub async fn put(&self, uri: &str, token: Option<&str>, body: Value) -> TestResponse {
+        self.send(Method::PUT, uri, token, Some(body)).await
+    }
+
+    pub async fn admin_token(&self) -> String {
+        self.login(json!({ "password": ADMIN_PASSWORD })).await
+    }
+
+    pub async fn login(&self, credentials: Value) -> String {
+        let response = self.post("/api/auth/login", None, credentials).await;
+        assert_eq!(response.status, StatusCode::OK, "login failed: {}", response.body);
+        response.body["token"].as_str().expect("token in login response").to_string()
+    }
+
+    /// Uploads a generated PNG as the admin; returns the upload response
+    pub async fn upload_image(&self, token: &str, folder: &str) -> Value {
+        let body = json!({ "image": png_data_url(1200, 800), "folder": folder });
+        let response = self.post("/api/upload/base64", Some(token), body).await;
+        assert_eq!(response.status, StatusCode::OK, "upload failed: {}", response.body);
+        response.body
+    }
+
+    /// Serves the router on a random local port, for clients that need a real
+    /// connection (WebSocket). The server stops with the test runtime.
+    pub async fn serve(&self) -> SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("bind test server");
+        let addr = listener.local_addr().expect("test server address");
+        let router = self.router.clone();
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.expect("test server");
+        });
+        addr
+    }
+}
+
+pub fn png_data_url(width: u32, height: u32) -> String {
+    let mut bytes = Vec::new();
+    RgbImage::from_pixel(width, height, image::Rgb([200, 80, 40]))
+        .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+        .unwrap();
+    format!("data:image/png;base64,{}", base64::engine::general_purpose::STANDARD.encode(bytes))
+}
+
+/// Money fields are serialized as decimal strings whose scale follows the
+/// column, so they are compared as numbers
+pub fn assert_money(value: &Value, expected: &str) {
+    let actual: BigDecimal = value.as_str().expect("decimal string").parse().expect("decimal");
+    assert_eq!(actual, expected.parse::<BigDecimal>().unwrap(), "got {}", value);
+}
+
+fn test_config(extra: &[(&str, &str)]) -> AppConfig {
+    let password_hash = bcrypt::hash(ADMIN_PASSWORD, 4).expect("hash admin password");
+    let settings: Settings = [
+        ("ENVIRONMENT", "production"),
+        ("JWT_SECRET", JWT_SECRET),
+        ("ADMIN_PASSWORD_HASH", password_hash.as_str()),
+        ("JOB_WORKER", "false"),
+    ]
+    .iter()
+    .chain(extra)
+    .map(|(key, value)| (key.to_string(), value.to_string()))
+    .collect();
+
+    AppConfig::from_settings(settings).expect("test config")
+}
+
+/// Keeps uploads in memory and records what was stored and deleted
+#[derive(Default)]
+pub struct FakeImageStorage {
+    next_id: AtomicUsize,
+    pub uploaded: Mutex<Vec<String>>,
+    pub deleted: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl ImageStorage for FakeImageStorage {
+    fn name(&self) -> &'static str {
+        "fake"
+    }
+
+    async fn upload(&self, data: Vec<u8>, filename: &str, folder: Option<&str>) -> anyhow::Result<StoredImage> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let public_id = format!("{}/{}-{}", folder.unwrap_or("fodi"), id, filename);
+        let size = imagesize::blob_size(&data)?;
+
+        self.uploaded.lock().unwrap().push(public_id.clone());
+        Ok(StoredImage {
+            url: format!("https://images.test/{}", public_id),
+            public_id,
+            width: size.width as u32,
+            height: size.height as u32,
+        })
+    }
+
+    async fn delete(&self, public_id: &str) -> anyhow::Result<()> {
+        self.deleted.lock().unwrap().push(public_id.to_string());
+        Ok(())
+    }
+}
+
+// Keeps slugs unique next to the sample categories from the migrations
+static NEXT_SLUG: AtomicUsize = AtomicUsize::new(1);
+
+pub struct CategoryBuilder {
+    name: String,
+    slug: String,
+    is_active: bool,
+}
+
+impl CategoryBuilder {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            slug: format!(
+                "{}-{}",
+                name.to_lowercase().replace(' ', "-"),
+                NEXT_SLUG.fetch_add(1, Ordering::SeqCst)
+            ),
+            is_active: true,
+        }
+    }
+
+    pub fn inactive(mut self) -> Self {
+        self.is_active = false;
+        self
+    }
+
+    pub async fn insert(self, pool: &PgPool) -> i32 {
+        sqlx::query_scalar(
+            r#"
+            INSERT INTO categories (name, name_ru, name_pl, slug, is_active)
+            VALUES ($1, $1, $1, $2, $3)
+            RETURNING id
+            "#,
+        )
+        .bind(&self.name)
+        .bind(&self.slug)
+        .bind(self.is_active)
+        .fetch_one(pool)
+        .await
+        .expect("insert category")
+    }
+}
+
+pub struct MenuItemBuilder {
+    category_id: i32,
+    name: String,
+    price: String,
+    is_available: bool,
+}
+
+impl MenuItemBuilder {
+    pub fn new(category_id: i32, name: &str) -> Self {
+        Self {
+            category_id,
+            name: name.to_string(),
+            price: "10.00".to_string(),
+            is_available: true,
+        }
+    }
+
+    pub fn price(mut self, price: &str) -> Self {
+        self.price = price.to_string();
+        self
+    }
+
+    pub fn unavailable(mut self) -> Self {
+        self.is_available = false;
+        self
+    }
+
+    pub async fn insert(self, pool: &PgPool) -> i32 {
+        sqlx::query_scalar(
+            r#"
+            INSERT INTO menu_items (category_id, name, name_ru, name_pl, description, description_ru,
+                                    description_pl, price, image, is_available)
+            VALUES ($1, $2, $2, $2, '', '', '', $3::NUMERIC, '', $4)
+            RETURNING id
+            "#,
+        )
+        .bind(self.category_id)
+        .bind(&self.name)
+        .bind(&self.price)
+        .bind(self.is_available)
+        .fetch_one(pool)
+        .await
+        .expect("insert menu item")
+    }
+}
+
+/// Checkout payload for POST /api/restaurant/orders
+pub struct OrderBuilder {
+    customer_name: String,
+    items: Vec<Value>,
+}
+
+impl Default for OrderBuilder {
+    fn default() -> Self {
+        Self {
+            customer_name: "Anna Nowak".to_string(),
+            items: Vec::new(),
+        }
+    }
+}
+
+impl OrderBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn customer_name(mut self, name: &str) -> Self {
+        self.customer_name = name.to_string();
+        self
+    }
+
+    pub fn item(mut self, menu_item_id: i32, quantity: i32) -> Self {
+        self.items.push(json!({ "menu_item_id": menu_item_id, "quantity": quantity }));
+        self
+    }
+
+    pub fn payload(&self) -> Value {
+        json!({
+            "customer_name": self.customer_name,
+            "customer_phone": "+48 600 100 200",
+            "customer_email": "anna@example.com",
+            "delivery_street": "Marszałkowska",
+            "delivery_building": "10",
+            "delivery_city": "Warszawa",
+            "delivery_postal_code": "00-001",
+            "payment_method": "cash",
+            "items": self.items,
+        })
+    }
+
+    /// Places the order and returns it with its `items`
+    pub async fn create(&self, app: &TestApp) -> Value {
+        let response = app.post("/api/restaurant/orders", None, self.payload()).await;
+        assert_eq!(response.status, StatusCode::CREATED, "order failed: {}", response.body);
+        response.body
+    }
+}
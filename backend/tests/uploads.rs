@@ -0,0 +1,89 @@
+mod support;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use sqlx::PgPool;
+
+use support::{png_data_url, TestApp};
+
+#[sqlx::test]
+async fn uploads_go_to_the_configured_storage(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let body = json!({ "image": png_data_url(1200, 800), "folder": "menu" });
+
+    let response = app.post("/api/upload/base64", None, body.clone()).await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+    assert!(app.storage.uploaded.lock().unwrap().is_empty());
+
+    let token = app.admin_token().await;
+    let response = app.post("/api/upload/base64", Some(&token), body).await;
+    assert_eq!(response.status, StatusCode::OK, "upload failed: {}", response.body);
+
+    let public_id = response.body["public_id"].as_str().unwrap();
+    assert!(public_id.starts_with("menu/"));
+    assert_eq!(response.body["url"], format!("https://images.test/{}", public_id));
+    assert_eq!(response.body["width"], 1200);
+    assert_eq!(response.body["height"], 800);
+
+    // The original and its scaled-down copies
+    let uploaded = app.storage.uploaded.lock().unwrap().clone();
+    assert!(uploaded.contains(&public_id.to_string()));
+    assert!(uploaded.len() > 1, "no derivatives stored: {:?}", uploaded);
+
+    let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM media_assets WHERE public_id = $1 AND storage = 'fake'")
+        .bind(public_id)
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, 1);
+}
+
+#[sqlx::test]
+async fn deleting_an_untracked_upload_queues_the_file_removal(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let token = app.admin_token().await;
+
+    let response = app
+        .send(
+            axum::http::Method::DELETE,
+            "/api/upload",
+            Some(&token),
+            Some(json!({ "public_id": "menu/legacy-photo" })),
+        )
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+
+    // Removed by the job worker, not during the request
+    assert!(app.storage.deleted.lock().unwrap().is_empty());
+    let queued: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM jobs WHERE kind = 'image.delete' AND payload ->> 'public_id' = 'menu/legacy-photo'",
+    )
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(queued, 1);
+}
+
+#[sqlx::test]
+async fn local_files_are_served_at_the_path_of_the_base_url(pool: PgPool) {
+    let dir = std::env::temp_dir().join(format!("fodi-media-{}", uuid::Uuid::new_v4()));
+    let app = TestApp::with_settings(
+        pool,
+        &[
+            ("IMAGE_STORAGE", "local"),
+            ("MEDIA_DIR", dir.to_str().unwrap()),
+            ("MEDIA_BASE_URL", "http://localhost:8000/files"),
+        ],
+    )
+    .await;
+    let token = app.admin_token().await;
+
+    let upload = app.upload_image(&token, "menu").await;
+    let url = upload["url"].as_str().unwrap();
+    let path = url.strip_prefix("http://localhost:8000").unwrap();
+    assert!(path.starts_with("/files/menu/") && path.ends_with(".png"), "{}", url);
+
+    let response = app.get(path, None).await;
+    std::fs::remove_dir_all(&dir).ok();
+    assert_eq!(response.status, StatusCode::OK);
+}
@@ -0,0 +1,79 @@
+mod support;
+
+use futures::StreamExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+use support::{assert_money, CategoryBuilder, MenuItemBuilder, OrderBuilder, TestApp};
+
+type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Next message of the given type, skipping the others (heartbeats, analytics)
+async fn next_of_type(client: &mut Client, message_type: &str) -> Value {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let message = client.next().await.expect("socket open").expect("socket message");
+            if !message.is_text() {
+                continue;
+            }
+            let value: Value = serde_json::from_str(message.to_text().unwrap()).expect("JSON message");
+            if value["type"] == message_type {
+                return value;
+            }
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("no {} message within 5s", message_type))
+}
+
+async fn connect(addr: SocketAddr) -> Client {
+    let (mut client, _) = connect_async(format!("ws://{}/api/ws", addr)).await.expect("connect");
+    // Sent once the connection is subscribed to broadcasts
+    next_of_type(&mut client, "analytics_update").await;
+    client
+}
+
+#[sqlx::test]
+async fn order_events_are_broadcast_to_connected_clients(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let sushi = CategoryBuilder::new("Sushi").insert(&app.pool).await;
+    let roll = MenuItemBuilder::new(sushi, "California Roll").price("45.00").insert(&app.pool).await;
+    let mut client = connect(app.serve().await).await;
+
+    let order = OrderBuilder::new().customer_name("Jan Kowalski").item(roll, 2).create(&app).await;
+
+    let new_order = next_of_type(&mut client, "new_order").await;
+    assert_eq!(new_order["order_id"], order["id"]);
+    assert_eq!(new_order["order_number"], order["order_number"]);
+    assert_eq!(new_order["customer_name"], "Jan Kowalski");
+    assert_money(&new_order["total"], "100.00");
+
+    let token = app.admin_token().await;
+    let uri = format!("/api/restaurant/admin/orders/{}/status", order["id"]);
+    app.put(&uri, Some(&token), json!({ "status": "confirmed" })).await;
+
+    let update = next_of_type(&mut client, "order_status_update").await;
+    assert_eq!(update["order_id"], order["id"]);
+    assert_eq!(update["status"], "confirmed");
+}
+
+#[sqlx::test]
+async fn every_client_receives_the_broadcast(pool: PgPool) {
+    let app = TestApp::new(pool).await;
+    let sushi = CategoryBuilder::new("Sushi").insert(&app.pool).await;
+    let roll = MenuItemBuilder::new(sushi, "California Roll").insert(&app.pool).await;
+    let addr = app.serve().await;
+    let mut kitchen = connect(addr).await;
+    let mut dashboard = connect(addr).await;
+
+    let order = OrderBuilder::new().item(roll, 1).create(&app).await;
+
+    for client in [&mut kitchen, &mut dashboard] {
+        let message = next_of_type(client, "new_order").await;
+        assert_eq!(message["order_number"], order["order_number"]);
+    }
+}